@@ -0,0 +1,6 @@
+-- Track listings used to be fetched as a single page without disc numbers, so
+-- long albums were truncated and track numbers collided across discs.
+ALTER TABLE album_tracks ADD COLUMN disc_number INTEGER NOT NULL DEFAULT 1;
+
+-- The table is only a cache: drop the incomplete listings so they are refetched.
+DELETE FROM album_tracks;
@@ -0,0 +1,22 @@
+-- Album metadata fetched from the catalog when an album is picked.
+-- Keyed by album rather than meeting so picking the same album again reuses it.
+CREATE TABLE IF NOT EXISTS albums (
+    album_id     TEXT    NOT NULL PRIMARY KEY,
+    release_date TEXT,
+    label        TEXT,
+    genres       TEXT    NOT NULL DEFAULT '[]', -- JSON array of strings
+    total_tracks INTEGER,
+    duration_ms  INTEGER,
+    artist_ids   TEXT    NOT NULL DEFAULT '[]', -- JSON array of strings
+    upc          TEXT,
+    fetched_at   TEXT    NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Every cover size the catalog offers for an album.
+CREATE TABLE IF NOT EXISTS album_images (
+    album_id TEXT    NOT NULL REFERENCES albums(album_id) ON DELETE CASCADE,
+    url      TEXT    NOT NULL,
+    width    INTEGER,
+    height   INTEGER,
+    PRIMARY KEY (album_id, url)
+);
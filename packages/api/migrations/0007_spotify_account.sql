@@ -0,0 +1,21 @@
+-- Authorisations started from the admin page and not yet completed, keyed by the
+-- OAuth `state` sent to Spotify. The PKCE verifier never leaves the server.
+CREATE TABLE IF NOT EXISTS spotify_auth_requests (
+    state         TEXT NOT NULL PRIMARY KEY,
+    code_verifier TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- The Spotify account the club playlist is exported to. At most one row (id = 1).
+CREATE TABLE IF NOT EXISTS spotify_account (
+    id              INTEGER NOT NULL PRIMARY KEY CHECK (id = 1),
+    spotify_user_id TEXT    NOT NULL,
+    display_name    TEXT,
+    access_token    TEXT    NOT NULL,
+    refresh_token   TEXT    NOT NULL,
+    expires_at      INTEGER NOT NULL, -- unix seconds
+    scope           TEXT,
+    playlist_id     TEXT,
+    playlist_url    TEXT,
+    connected_at    TEXT    NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,13 @@
+-- Album covers downloaded once and served from our own route instead of hot-linking
+-- the catalog's CDN. Every size is re-encoded as JPEG from the same source image.
+CREATE TABLE IF NOT EXISTS album_art (
+    album_id     TEXT    NOT NULL,
+    size         TEXT    NOT NULL, -- 'small', 'medium' or 'large'
+    width        INTEGER NOT NULL,
+    height       INTEGER NOT NULL,
+    content_type TEXT    NOT NULL,
+    data         BLOB    NOT NULL,
+    source_url   TEXT    NOT NULL,
+    fetched_at   TEXT    NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (album_id, size)
+);
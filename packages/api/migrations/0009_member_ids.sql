@@ -0,0 +1,98 @@
+-- Members get a surrogate ID so names become mutable display labels, and meetings and
+-- reviews reference members by ID with foreign keys. SQLite can't change a primary key
+-- or add a foreign key in place, so the affected tables are rebuilt. This relies on
+-- migrations running with foreign keys off (see `db::run_migrations`): dropping the old
+-- `members` and `meetings` tables would otherwise cascade into the rebuilt reviews.
+
+CREATE TABLE members_new (
+    id            INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name          TEXT    NOT NULL UNIQUE,
+    sort_order    INTEGER NOT NULL DEFAULT 0,
+    password_hash TEXT,
+    deleted_at    TEXT
+);
+
+INSERT INTO members_new (name, sort_order, password_hash, deleted_at)
+SELECT name, sort_order, password_hash, deleted_at
+FROM members
+ORDER BY sort_order, name;
+
+-- Pickers and reviewers without a member row (the picker never had a foreign key)
+-- become removed members so no history is lost.
+INSERT INTO members_new (name, deleted_at)
+SELECT DISTINCT name, datetime('now')
+FROM (
+    SELECT picker AS name FROM meetings
+    UNION SELECT member_name FROM album_reviews
+    UNION SELECT member_name FROM track_reviews
+)
+WHERE name NOT IN (SELECT name FROM members_new);
+
+CREATE TABLE meetings_new (
+    id                TEXT    NOT NULL PRIMARY KEY,
+    is_current        INTEGER NOT NULL DEFAULT 0,
+    album_id          TEXT    NOT NULL,
+    album_name        TEXT    NOT NULL,
+    album_artist      TEXT    NOT NULL,
+    album_art_url     TEXT    NOT NULL,
+    album_spotify_url TEXT    NOT NULL,
+    picker_id         INTEGER NOT NULL REFERENCES members(id),
+    meeting_date      TEXT    NOT NULL,
+    meeting_time      TEXT,
+    meeting_location  TEXT,
+    recorded_at       TEXT    NOT NULL DEFAULT (datetime('now'))
+);
+
+INSERT INTO meetings_new
+    (id, is_current, album_id, album_name, album_artist, album_art_url, album_spotify_url,
+     picker_id, meeting_date, meeting_time, meeting_location, recorded_at)
+SELECT m.id, m.is_current, m.album_id, m.album_name, m.album_artist, m.album_art_url,
+       m.album_spotify_url, mem.id, m.meeting_date, m.meeting_time, m.meeting_location,
+       m.recorded_at
+FROM meetings m
+JOIN members_new mem ON mem.name = m.picker;
+
+CREATE TABLE album_reviews_new (
+    id         TEXT    NOT NULL PRIMARY KEY,
+    meeting_id TEXT    NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    score      INTEGER NOT NULL CHECK(score >= 0 AND score <= 10),
+    created_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(meeting_id, member_id)
+);
+
+INSERT INTO album_reviews_new (id, meeting_id, member_id, score, created_at, updated_at)
+SELECT r.id, r.meeting_id, mem.id, r.score, r.created_at, r.updated_at
+FROM album_reviews r
+JOIN members_new mem ON mem.name = r.member_name;
+
+CREATE TABLE track_reviews_new (
+    id         TEXT    NOT NULL PRIMARY KEY,
+    meeting_id TEXT    NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    track_id   TEXT    NOT NULL,
+    score      INTEGER NOT NULL CHECK(score >= 0 AND score <= 10),
+    created_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(meeting_id, member_id, track_id)
+);
+
+INSERT INTO track_reviews_new
+    (id, meeting_id, member_id, track_id, score, created_at, updated_at)
+SELECT r.id, r.meeting_id, mem.id, r.track_id, r.score, r.created_at, r.updated_at
+FROM track_reviews r
+JOIN members_new mem ON mem.name = r.member_name;
+
+DROP TABLE track_reviews;
+DROP TABLE album_reviews;
+DROP TABLE meetings;
+DROP TABLE members;
+
+ALTER TABLE members_new RENAME TO members;
+ALTER TABLE meetings_new RENAME TO meetings;
+ALTER TABLE album_reviews_new RENAME TO album_reviews;
+ALTER TABLE track_reviews_new RENAME TO track_reviews;
+
+CREATE UNIQUE INDEX IF NOT EXISTS only_one_current ON meetings (is_current)
+WHERE is_current = 1;
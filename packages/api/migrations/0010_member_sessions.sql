@@ -0,0 +1,11 @@
+-- Review logins. The client holds the token; only its SHA-256 is stored so a leaked
+-- database can't be used to log in. Logging out, a new password or removing the
+-- member deletes the member's sessions.
+CREATE TABLE IF NOT EXISTS member_sessions (
+    token_hash TEXT    NOT NULL PRIMARY KEY,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    created_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    expires_at TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS member_sessions_member ON member_sessions (member_id);
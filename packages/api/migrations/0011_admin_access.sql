@@ -0,0 +1,25 @@
+-- Named admin credentials replace the single shared ADMIN_TOKEN. The token itself is
+-- shown once when created; only its Argon2 hash is stored. `ADMIN_TOKEN` from the
+-- environment is imported as a credential on startup (see db.rs).
+CREATE TABLE IF NOT EXISTS admin_credentials (
+    id         INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    label      TEXT    NOT NULL,
+    token_hash TEXT    NOT NULL UNIQUE,
+    created_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    expires_at TEXT,
+    revoked_at TEXT
+);
+
+-- Members with admin rights can log in to the admin page with their own password.
+ALTER TABLE members ADD COLUMN is_admin INTEGER NOT NULL DEFAULT 0;
+
+-- Logged-in admins, via either a credential or an admin member. Like member sessions,
+-- only the SHA-256 of the session token is stored.
+CREATE TABLE IF NOT EXISTS admin_sessions (
+    token_hash    TEXT    NOT NULL PRIMARY KEY,
+    credential_id INTEGER REFERENCES admin_credentials(id) ON DELETE CASCADE,
+    member_id     INTEGER REFERENCES members(id) ON DELETE CASCADE,
+    created_at    TEXT    NOT NULL DEFAULT (datetime('now')),
+    expires_at    TEXT    NOT NULL,
+    CHECK ((credential_id IS NULL) <> (member_id IS NULL))
+);
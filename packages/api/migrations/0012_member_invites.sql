@@ -0,0 +1,12 @@
+-- Single-use links that let a member set their own password. As with sessions, only the
+-- SHA-256 of the token in the link is stored.
+CREATE TABLE IF NOT EXISTS member_invites (
+    token_hash TEXT    NOT NULL PRIMARY KEY,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    created_at TEXT    NOT NULL DEFAULT (datetime('now')),
+    expires_at TEXT    NOT NULL,
+    used_at    TEXT
+);
+
+CREATE INDEX IF NOT EXISTS member_invites_member ON member_invites (member_id);
+
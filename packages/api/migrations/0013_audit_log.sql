@@ -0,0 +1,26 @@
+-- Every change made through the admin page or by a member, written in the same
+-- transaction as the change. `before_json` and `after_json` are snapshots of what
+-- changed; secrets are never included. `actor` is the actor's name at the time; the
+-- member (for members and admin members) or admin credential it was is kept by ID, so
+-- renaming doesn't split anyone's entries.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id                  INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    at                  TEXT    NOT NULL DEFAULT (datetime('now')),
+    actor_kind          TEXT    NOT NULL CHECK (actor_kind IN ('admin', 'member')),
+    actor               TEXT    NOT NULL,
+    actor_member_id     INTEGER REFERENCES members(id),
+    actor_credential_id INTEGER REFERENCES admin_credentials(id),
+    action              TEXT    NOT NULL,
+    before_json         TEXT,
+    after_json          TEXT
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_action ON audit_log (action, id);
+CREATE INDEX IF NOT EXISTS audit_log_actor_member ON audit_log (actor_member_id, id);
+CREATE INDEX IF NOT EXISTS audit_log_actor_credential ON audit_log (actor_credential_id, id);
+
+-- The Spotify callback has no admin session; remember who started the authorisation so
+-- connecting an account can be attributed to them.
+ALTER TABLE spotify_auth_requests ADD COLUMN started_by TEXT;
+ALTER TABLE spotify_auth_requests ADD COLUMN started_by_credential_id INTEGER;
+ALTER TABLE spotify_auth_requests ADD COLUMN started_by_member_id INTEGER;
@@ -0,0 +1,4 @@
+-- Optional written review per member per album, and a short comment per track.
+-- NULL means nothing written.
+ALTER TABLE album_reviews ADD COLUMN review_text TEXT;
+ALTER TABLE track_reviews ADD COLUMN comment TEXT;
@@ -0,0 +1,6 @@
+-- When other members' reviews of a meeting become visible (see RevealPolicy).
+-- Meetings that are no longer current are always revealed.
+ALTER TABLE meetings ADD COLUMN reveal_policy TEXT NOT NULL DEFAULT 'after_own_review'
+    CHECK (reveal_policy IN ('after_own_review', 'at_meeting_time', 'admin_reveal'));
+-- Set when an admin reveals the reviews early, whatever the policy.
+ALTER TABLE meetings ADD COLUMN reviews_revealed_at TEXT;
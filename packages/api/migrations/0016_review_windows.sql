@@ -0,0 +1,5 @@
+-- Reviewing a meeting opens when it's set current and closes review_close_days after
+-- the meeting date (and time), or when an admin locks it.
+ALTER TABLE meetings ADD COLUMN review_close_days INTEGER NOT NULL DEFAULT 7
+    CHECK (review_close_days >= 0);
+ALTER TABLE meetings ADD COLUMN reviews_locked_at TEXT;
@@ -0,0 +1,21 @@
+-- Date ranges when a member can't pick, e.g. while travelling. Both dates are inclusive
+-- YYYY-MM-DD.
+CREATE TABLE IF NOT EXISTS member_unavailability (
+    id         INTEGER NOT NULL PRIMARY KEY,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    start_date TEXT    NOT NULL,
+    end_date   TEXT    NOT NULL CHECK (end_date >= start_date),
+    note       TEXT,
+    created_at TEXT    NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS member_unavailability_member ON member_unavailability (member_id);
+
+-- Members whose turn it was when a meeting was set, but who didn't pick: either away on the
+-- meeting date, or passed over by the admin. They keep their place for the next cycle.
+CREATE TABLE IF NOT EXISTS rotation_skips (
+    meeting_id TEXT    NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    member_id  INTEGER NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    reason     TEXT    NOT NULL CHECK (reason IN ('unavailable', 'passed')),
+    PRIMARY KEY (meeting_id, member_id)
+);
@@ -0,0 +1,4 @@
+-- When a member joined the rotation, or rejoined after being removed, so they're only
+-- expected to have picked since then. NULL for members who were there before this was
+-- recorded, who count as having been there from the start.
+ALTER TABLE members ADD COLUMN joined_at TEXT;
@@ -4,13 +4,87 @@ use serde::{Deserialize, Serialize};
 
 pub type Name = Arc<str>;
 
+pub type MemberId = i64;
+
+/// A club member. The ID is stable; the name is only a display label and can be changed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Member {
+    pub id: MemberId,
+    pub name: Name,
+}
+
+/// A logged-in member. The token authenticates reviews until it expires or is revoked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberSession {
+    pub token: String,
+    pub member: Member,
+    pub expires_at: String,
+}
+
+/// A single-use link for a member to set their own password. The token is only
+/// returned when the invite is created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberInvite {
+    pub token: String,
+    pub member: Member,
+    pub expires_at: String,
+}
+
+/// Shortest password a member can choose. Checked on the server; exported so forms can
+/// say so before submitting.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// Where a member redeems an invite.
+pub fn member_invite_path(token: &str) -> String {
+    format!("/invite/{token}")
+}
+
+/// A logged-in admin. `label` is the credential's label or the admin member's name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminSession {
+    pub token: String,
+    pub label: String,
+    pub expires_at: String,
+    /// The credential logged in with, for credential logins.
+    pub credential_id: Option<i64>,
+    /// The admin member, for member logins.
+    pub member_id: Option<MemberId>,
+}
+
+/// A named admin login token. The token itself is only shown once, when it's created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminCredential {
+    pub id: i64,
+    pub label: String,
+    pub created_at: String,
+    /// `None` for credentials that never expire.
+    pub expires_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+/// Everything that can log in to the admin page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminAccess {
+    pub credentials: Vec<AdminCredential>,
+    /// Members with admin rights, who log in with their own password.
+    pub admin_members: Vec<MemberId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Data {
     pub current_meeting_id: Option<String>,
     pub current_album: Option<Album>,
     pub next_meeting: Option<Meeting>,
-    pub current_person: Option<Name>,
-    pub members: Vec<Name>,
+    pub current_person: Option<Member>,
+    /// Whose turn it is to pick the next album: the first member in `rotation` who isn't
+    /// away.
+    pub next_person: Option<Member>,
+    pub members: Vec<Member>,
+    /// `members` in the order they pick from now on. Members who are away for the next
+    /// meeting keep their place here, but are skipped.
+    pub rotation: Vec<Member>,
+    /// Members who are away for the next meeting.
+    pub unavailable: Vec<MemberId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,8 +101,81 @@ pub struct Album {
     pub id: String,
     pub name: String,
     pub artist: String,
+    /// Our own [`album_art_path`] for the large cover, or empty if the album has no art.
     pub album_art: String,
     pub spotify_url: String,
+    /// `None` for albums picked before metadata was fetched at pick time.
+    pub metadata: Option<AlbumMetadata>,
+}
+
+/// Album details fetched from the catalog when the album was picked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlbumMetadata {
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub genres: Vec<String>,
+    pub total_tracks: Option<u32>,
+    pub duration_ms: Option<i64>,
+    pub artist_ids: Vec<String>,
+    pub upc: Option<String>,
+    /// Largest first.
+    pub images: Vec<AlbumImage>,
+}
+
+impl AlbumMetadata {
+    /// The four-digit release year, if known.
+    pub fn release_year(&self) -> Option<&str> {
+        self.release_date
+            .as_deref()
+            .and_then(|date| date.get(..4))
+            .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlbumImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// The sizes album art is cached and served in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArtSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl ArtSize {
+    pub const ALL: [ArtSize; 3] = [ArtSize::Small, ArtSize::Medium, ArtSize::Large];
+
+    /// The longest side of the image in pixels. Smaller sources aren't scaled up.
+    pub fn pixels(self) -> u32 {
+        match self {
+            ArtSize::Small => 96,
+            ArtSize::Medium => 320,
+            ArtSize::Large => 640,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArtSize::Small => "small",
+            ArtSize::Medium => "medium",
+            ArtSize::Large => "large",
+        }
+    }
+
+    pub fn parse(size: &str) -> Option<ArtSize> {
+        ArtSize::ALL.into_iter().find(|s| s.as_str() == size)
+    }
+}
+
+/// Where the server serves an album's cover in the given size.
+pub fn album_art_path(album_id: &str, size: ArtSize) -> String {
+    format!("/album-art/{album_id}/{}", size.as_str())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -36,64 +183,550 @@ pub struct Meeting {
     pub date: String,
     pub time: Option<String>,
     pub location: Option<String>,
+    pub reveal_policy: RevealPolicy,
+    /// Whether an admin has revealed the reviews ahead of the policy.
+    pub reviews_revealed: bool,
+    /// Days after the meeting that reviewing closes.
+    pub review_close_days: u32,
+    /// Whether an admin has closed reviewing ahead of time.
+    pub reviews_locked: bool,
+}
+
+/// A single meeting, current or past, with what its review page needs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeetingDetails {
+    pub id: String,
+    pub is_current: bool,
+    pub album: Album,
+    pub picker: Member,
+    pub meeting: Meeting,
+    /// Active members, to log in as.
+    pub members: Vec<Member>,
+}
+
+/// How many days after a meeting reviewing closes, unless the admin picks otherwise.
+pub const DEFAULT_REVIEW_CLOSE_DAYS: u32 = 7;
+
+/// Whether a meeting's reviews can still be submitted or changed. Reviewing opens when the
+/// meeting is set current.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewWindow {
+    pub open: bool,
+    /// When reviewing closes, in the server's local time (`YYYY-MM-DD HH:MM:SS`). `None`
+    /// if the meeting date can't be read, in which case only a lock closes it.
+    pub closes_at: Option<String>,
+    /// Closed by an admin, whatever `closes_at` says.
+    pub locked: bool,
+}
+
+/// When members get to see each other's reviews of the current meeting. Until then
+/// `get_reviews` only returns the caller's own reviews, so nobody is swayed by the rest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RevealPolicy {
+    /// Once the member has given the album a score.
+    #[default]
+    AfterOwnReview,
+    /// Once the meeting starts (its date, and time if set).
+    AtMeetingTime,
+    /// Once an admin reveals them.
+    AdminReveal,
+}
+
+impl RevealPolicy {
+    pub const ALL: [RevealPolicy; 3] = [
+        RevealPolicy::AfterOwnReview,
+        RevealPolicy::AtMeetingTime,
+        RevealPolicy::AdminReveal,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RevealPolicy::AfterOwnReview => "after_own_review",
+            RevealPolicy::AtMeetingTime => "at_meeting_time",
+            RevealPolicy::AdminReveal => "admin_reveal",
+        }
+    }
+
+    pub fn parse(policy: &str) -> Option<RevealPolicy> {
+        RevealPolicy::ALL.into_iter().find(|p| p.as_str() == policy)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub id: String,
+    pub album_id: String,
     pub album_name: String,
     pub album_artist: String,
+    /// Our own [`album_art_path`] for the large cover, or empty if the album has no art.
     pub album_art: String,
     pub spotify_url: String,
+    pub picker_id: MemberId,
     pub picker: String,
     pub recorded_at: String,
     pub meeting_date: String,
     pub meeting_time: Option<String>,
     pub meeting_location: Option<String>,
+    pub album_metadata: Option<AlbumMetadata>,
+}
+
+/// A soft-deleted member that can be restored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemovedMember {
+    pub id: MemberId,
+    pub name: String,
+    pub deleted_at: String,
+}
+
+/// A date range when a member can't pick, e.g. while travelling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unavailability {
+    pub id: i64,
+    pub member_id: MemberId,
+    pub member_name: String,
+    /// `YYYY-MM-DD`, inclusive.
+    pub start_date: String,
+    /// `YYYY-MM-DD`, inclusive.
+    pub end_date: String,
+    pub note: Option<String>,
+}
+
+pub const MAX_UNAVAILABILITY_NOTE_LEN: usize = 100;
+
+/// A member whose turn it was when a meeting was set, but who didn't pick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationSkip {
+    pub meeting_id: String,
+    pub meeting_date: String,
+    pub album_name: String,
+    pub member_id: MemberId,
+    pub member_name: String,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkipReason {
+    /// Away on the meeting date.
+    Unavailable,
+    /// Available, but the admin picked someone further down the rotation.
+    Passed,
 }
 
+impl SkipReason {
+    pub const ALL: [SkipReason; 2] = [SkipReason::Unavailable, SkipReason::Passed];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::Unavailable => "unavailable",
+            SkipReason::Passed => "passed",
+        }
+    }
+
+    pub fn parse(reason: &str) -> Option<SkipReason> {
+        SkipReason::ALL.into_iter().find(|r| r.as_str() == reason)
+    }
+}
+
+/// How evenly picks are spread over the members in the rotation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FairnessReport {
+    /// Meetings so far, the current one included.
+    pub total_meetings: u32,
+    /// In rotation order.
+    pub members: Vec<MemberFairness>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberFairness {
+    pub member: Member,
+    /// Albums picked since joining (or rejoining) the rotation.
+    pub picks: u32,
+    /// How many of the meetings since joining would have been theirs if everyone in the
+    /// rotation now took turns evenly.
+    pub expected_picks: f32,
+    /// `picks - expected_picks`: negative when they're owed a turn.
+    pub balance: f32,
+    pub imbalance: Option<PickImbalance>,
+    /// Date of their latest pick, at any time.
+    pub last_pick_date: Option<String>,
+    pub days_since_last_pick: Option<i64>,
+    /// `None` for members who were there before join dates were recorded.
+    pub joined_at: Option<String>,
+    /// Joined (or rejoined) after the club's first meeting, so picks are only counted
+    /// since then.
+    pub joined_mid_cycle: bool,
+    /// Turns skipped since joining, away or passed over.
+    pub skipped_turns: u32,
+}
+
+/// A member whose picks are off by at least [`PICK_IMBALANCE_THRESHOLD`] turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickImbalance {
+    /// Has picked less than their share.
+    Behind,
+    /// Has picked more than their share.
+    Ahead,
+}
+
+pub const PICK_IMBALANCE_THRESHOLD: f32 = 1.0;
+
 /// A single track from an album, cached from Spotify.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AlbumTrack {
     pub track_id: String,
+    /// 1-based disc number; track numbers restart on every disc.
+    pub disc_number: u32,
     pub track_number: u32,
     pub track_name: String,
     pub duration_ms: Option<i64>,
     pub spotify_url: Option<String>,
 }
 
-/// One member's album-level review score (0–10).
+/// One member's album-level review: a score (0–10) and optionally a written review.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AlbumReview {
+    pub member_id: MemberId,
     pub member_name: String,
     pub score: u8,
+    pub text: Option<String>,
 }
 
-/// One member's score (0–10) for a single track.
+/// One member's score (0–10) for a single track, and optionally a short comment.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TrackReview {
+    pub member_id: MemberId,
     pub member_name: String,
     pub track_id: String,
     pub score: u8,
+    pub comment: Option<String>,
 }
 
+/// Longest written album review, in characters.
+pub const MAX_REVIEW_TEXT_LEN: usize = 5000;
+/// Longest track comment, in characters.
+pub const MAX_TRACK_COMMENT_LEN: usize = 280;
+
 /// All reviews for a given meeting (album + individual tracks).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Reviews {
     pub album_reviews: Vec<AlbumReview>,
     pub track_reviews: Vec<TrackReview>,
+    /// `false` while the meeting's [`RevealPolicy`] hides the other members' reviews;
+    /// the lists then only hold the caller's own.
+    pub revealed: bool,
+    pub reveal_policy: RevealPolicy,
+    /// How many members have given the album a score, hidden or not.
+    pub reviewer_count: u32,
+    pub window: ReviewWindow,
 }
 
 /// Request body for `admin_set_current` and `admin_update_current`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SetCurrentRequest {
+    /// An album ID, `open.spotify.com/album/...` link or `spotify:album:...` URI.
+    /// Name, artist, cover and tracks are resolved on the server.
+    pub album: String,
+    pub picker_id: MemberId,
+    pub meeting_date: String,
+    pub meeting_time: Option<String>,
+    pub meeting_location: Option<String>,
+    pub reveal_policy: RevealPolicy,
+    pub review_close_days: u32,
+}
+
+/// The Spotify account the club playlist is exported to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpotifyAccount {
+    pub user_id: String,
+    pub display_name: Option<String>,
+    /// Set once a playlist has been exported.
+    pub playlist_url: Option<String>,
+    pub connected_at: String,
+}
+
+/// Result of `admin_spotify_export_playlist`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistExport {
+    pub playlist_url: String,
+    pub track_count: u32,
+    /// Past albums left out because none of their tracks have been reviewed.
+    pub skipped_albums: u32,
+}
+
+/// A change recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditAction {
+    SetCurrent,
+    UpdateCurrent,
+    DeleteHistoryEntry,
+    ReorderMembers,
+    AddMember,
+    RenameMember,
+    DeleteMember,
+    RestoreMember,
+    CreateMemberInvite,
+    SetMemberAdmin,
+    CreateCredential,
+    RevokeCredential,
+    ConnectSpotify,
+    DisconnectSpotify,
+    ExportPlaylist,
+    ReviewAlbum,
+    ReviewTrack,
+    ChangePassword,
+    RedeemInvite,
+    RevealReviews,
+    SetReviewsLocked,
+    AddUnavailability,
+    RemoveUnavailability,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 23] = [
+        AuditAction::SetCurrent,
+        AuditAction::UpdateCurrent,
+        AuditAction::DeleteHistoryEntry,
+        AuditAction::ReorderMembers,
+        AuditAction::AddMember,
+        AuditAction::RenameMember,
+        AuditAction::DeleteMember,
+        AuditAction::RestoreMember,
+        AuditAction::CreateMemberInvite,
+        AuditAction::SetMemberAdmin,
+        AuditAction::CreateCredential,
+        AuditAction::RevokeCredential,
+        AuditAction::ConnectSpotify,
+        AuditAction::DisconnectSpotify,
+        AuditAction::ExportPlaylist,
+        AuditAction::ReviewAlbum,
+        AuditAction::ReviewTrack,
+        AuditAction::ChangePassword,
+        AuditAction::RedeemInvite,
+        AuditAction::RevealReviews,
+        AuditAction::SetReviewsLocked,
+        AuditAction::AddUnavailability,
+        AuditAction::RemoveUnavailability,
+    ];
+
+    /// How the action is stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::SetCurrent => "set_current",
+            AuditAction::UpdateCurrent => "update_current",
+            AuditAction::DeleteHistoryEntry => "delete_history_entry",
+            AuditAction::ReorderMembers => "reorder_members",
+            AuditAction::AddMember => "add_member",
+            AuditAction::RenameMember => "rename_member",
+            AuditAction::DeleteMember => "delete_member",
+            AuditAction::RestoreMember => "restore_member",
+            AuditAction::CreateMemberInvite => "create_member_invite",
+            AuditAction::SetMemberAdmin => "set_member_admin",
+            AuditAction::CreateCredential => "create_credential",
+            AuditAction::RevokeCredential => "revoke_credential",
+            AuditAction::ConnectSpotify => "connect_spotify",
+            AuditAction::DisconnectSpotify => "disconnect_spotify",
+            AuditAction::ExportPlaylist => "export_playlist",
+            AuditAction::ReviewAlbum => "review_album",
+            AuditAction::ReviewTrack => "review_track",
+            AuditAction::ChangePassword => "change_password",
+            AuditAction::RedeemInvite => "redeem_invite",
+            AuditAction::RevealReviews => "reveal_reviews",
+            AuditAction::SetReviewsLocked => "set_reviews_locked",
+            AuditAction::AddUnavailability => "add_unavailability",
+            AuditAction::RemoveUnavailability => "remove_unavailability",
+        }
+    }
+
+    pub fn parse(action: &str) -> Option<AuditAction> {
+        AuditAction::ALL.into_iter().find(|a| a.as_str() == action)
+    }
+}
+
+/// Who made an audited change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActorKind {
+    /// Logged in to the admin page; the actor is the credential label or member name.
+    Admin,
+    /// A member, e.g. reviewing or changing their password.
+    Member,
+}
+
+impl ActorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActorKind::Admin => "admin",
+            ActorKind::Member => "member",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<ActorKind> {
+        [ActorKind::Admin, ActorKind::Member]
+            .into_iter()
+            .find(|k| k.as_str() == kind)
+    }
+}
+
+/// One audited change. `before` and `after` are JSON snapshots of what changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub at: String,
+    pub actor_kind: ActorKind,
+    /// The actor's name at the time of the change.
+    pub actor: String,
+    pub action: AuditAction,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Who made audited changes: a member, as themselves or as an admin, or an admin
+/// credential.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditActorId {
+    Member(MemberId),
+    Credential(i64),
+}
+
+impl AuditActorId {
+    /// A string form, e.g. for a `<select>` value.
+    pub fn as_key(self) -> String {
+        match self {
+            AuditActorId::Member(id) => format!("member:{id}"),
+            AuditActorId::Credential(id) => format!("credential:{id}"),
+        }
+    }
+
+    pub fn parse_key(key: &str) -> Option<AuditActorId> {
+        let (kind, id) = key.split_once(':')?;
+        let id = id.parse().ok()?;
+        match kind {
+            "member" => Some(AuditActorId::Member(id)),
+            "credential" => Some(AuditActorId::Credential(id)),
+            _ => None,
+        }
+    }
+}
+
+/// Someone to filter the audit log by, with their current name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditActor {
+    pub id: AuditActorId,
+    pub name: String,
+}
+
+/// Request body for `admin_audit_log`. Entries come newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub action: Option<AuditAction>,
+    pub actor: Option<AuditActorId>,
+    /// Only entries older than this one, to load the next page.
+    pub before_id: Option<i64>,
+}
+
+/// Result of `admin_audit_log`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditEntry>,
+    /// Everyone with entries in the log, for filtering.
+    pub actors: Vec<AuditActor>,
+    pub has_more: bool,
+}
+
+/// Club-wide statistics over every meeting whose reviews are revealed. Scores of 0 mean
+/// "not rated" and are left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClubStats {
+    /// Every reviewed album, best average first.
+    pub albums: Vec<AlbumStat>,
+    /// Best average first.
+    pub pickers: Vec<PickerStat>,
+    /// Most album reviews first.
+    pub reviewers: Vec<ReviewerStat>,
+    pub best_tracks: Vec<TrackStat>,
+    pub worst_tracks: Vec<TrackStat>,
+    /// How many album scores of 1 to 10 have been given; index 0 counts the 1s.
+    pub album_score_distribution: Vec<u32>,
+    /// Like `album_score_distribution`, for track scores.
+    pub track_score_distribution: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlbumStat {
+    pub meeting_id: String,
     pub album_id: String,
     pub album_name: String,
     pub album_artist: String,
-    pub album_art_url: String,
-    pub album_spotify_url: String,
     pub picker: String,
     pub meeting_date: String,
-    pub meeting_time: Option<String>,
-    pub meeting_location: Option<String>,
+    pub average: f32,
+    pub review_count: u32,
+}
+
+/// The scores a member's picks got.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickerStat {
+    pub member: Member,
+    pub albums_picked: u32,
+    /// `None` if none of their picks has been scored.
+    pub average_received: Option<f32>,
+    pub review_count: u32,
+}
+
+/// The scores a member has given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewerStat {
+    pub member: Member,
+    /// Average album score given.
+    pub average_given: f32,
+    pub album_reviews: u32,
+    pub track_reviews: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackStat {
+    pub meeting_id: String,
+    pub track_name: String,
+    pub album_name: String,
+    pub album_artist: String,
+    pub average: f32,
+    pub review_count: u32,
+}
+
+/// What a [`Compatibility`] compares members on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompatibilityBasis {
+    /// Album scores, one per meeting both members reviewed.
+    #[default]
+    Albums,
+    /// Track scores, one per track both members scored.
+    Tracks,
+}
+
+/// The fewest co-rated albums or tracks `get_compatibility` accepts as a threshold.
+pub const MIN_COMPATIBILITY_OVERLAP: u32 = 2;
+
+/// How much members agree with each other, pair by pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compatibility {
+    pub basis: CompatibilityBasis,
+    pub min_overlap: u32,
+    /// Active members, in rotation order.
+    pub members: Vec<Member>,
+    /// One entry per pair of members with at least `min_overlap` co-rated items, with
+    /// `a < b`.
+    pub pairs: Vec<MemberPair>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberPair {
+    pub a: MemberId,
+    pub b: MemberId,
+    /// How many albums or tracks both have scored.
+    pub overlap: u32,
+    /// Pearson correlation of their scores, `None` if either always gave the same score.
+    pub pearson: Option<f32>,
+    /// Spearman rank correlation, `None` under the same condition as `pearson`.
+    pub spearman: Option<f32>,
+    /// Mean absolute difference between their scores, 0–10.
+    pub mean_abs_diff: f32,
 }
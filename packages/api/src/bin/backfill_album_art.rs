@@ -0,0 +1,54 @@
+//! Download and store art for albums picked before art was cached locally.
+//!
+//! Uses `DATABASE_URL` like the server. Pass `--force` to download every album again.
+
+fn main() {
+    let mut args = std::env::args();
+    let program_name = args
+        .next()
+        .unwrap_or_else(|| "backfill_album_art".to_string());
+
+    let force = match args.next().as_deref() {
+        None => false,
+        Some("--force") => true,
+        Some(_) => {
+            eprintln!("Usage: {program_name} [--force]");
+            std::process::exit(2);
+        }
+    };
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            eprintln!("Failed to start runtime: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    let report = match runtime.block_on(api::backfill_album_art(force)) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("Backfill failed: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    for (album_id, error) in &report.failed {
+        eprintln!("{album_id}: {error}");
+    }
+
+    println!(
+        "Downloaded {}, already cached {}, without art {}, failed {}",
+        report.downloaded,
+        report.already_cached,
+        report.without_art,
+        report.failed.len()
+    );
+
+    if !report.failed.is_empty() {
+        std::process::exit(1);
+    }
+}
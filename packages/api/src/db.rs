@@ -6,18 +6,59 @@ use std::str::FromStr;
 
 const INITIAL_MEMBERS: &[&str] = &["Nox", "Karro", "Vidde", "Dino", "Yoda", "Carl", "Arvid"];
 
+const ADMIN_TOKEN_ENV: &str = "ADMIN_TOKEN";
+
 pub async fn init_pool(db_url: &str) -> Result<SqlitePool, sqlx::Error> {
     let options = SqliteConnectOptions::from_str(db_url)?.create_if_missing(true);
 
+    let migration_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.clone().foreign_keys(false))
+        .await?;
+    run_migrations(&migration_pool).await?;
+    migration_pool.close().await;
+
     let pool = SqlitePoolOptions::new().connect_with(options).await?;
 
+    seed_members(&pool).await?;
+    seed_admin_credential(&pool).await?;
+
+    Ok(pool)
+}
+
+/// Run pending migrations on `pool`, which must have foreign keys off. sqlx runs each
+/// SQLite migration in a transaction, where they can't be switched off, and migrations
+/// that rebuild a table would otherwise cascade deletes into the tables referencing it.
+async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     tracing::info!("Running database migrations");
-    sqlx::migrate!().run(&pool).await?;
+    sqlx::migrate!().run(pool).await?;
     tracing::info!("Migrations complete");
+    Ok(())
+}
 
-    seed_members(&pool).await?;
+/// A fresh in-memory database with every migration applied, for tests.
+#[cfg(test)]
+pub async fn test_pool() -> SqlitePool {
+    // Every connection to `:memory:` is its own database, so keep exactly one open.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(
+            SqliteConnectOptions::from_str("sqlite::memory:")
+                .expect("in-memory database URL")
+                .foreign_keys(false),
+        )
+        .await
+        .expect("open an in-memory database");
 
-    Ok(pool)
+    run_migrations(&pool).await.expect("run migrations");
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&pool)
+        .await
+        .expect("enable foreign keys");
+
+    pool
 }
 
 async fn seed_members(pool: &SqlitePool) -> Result<(), sqlx::Error> {
@@ -37,3 +78,32 @@ async fn seed_members(pool: &SqlitePool) -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+/// Import `ADMIN_TOKEN` as an admin credential, so a new install can log in and create
+/// the real credentials. Each hash is imported once: revoking it sticks, and setting a
+/// new hash is the way back in if every credential has been revoked.
+async fn seed_admin_credential(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let Ok(token_hash) = std::env::var(ADMIN_TOKEN_ENV) else {
+        return Ok(());
+    };
+    let token_hash = token_hash.trim();
+
+    if argon2::PasswordHash::new(token_hash).is_err() {
+        tracing::warn!("ADMIN_TOKEN is not a valid Argon2 PHC hash, not importing it");
+        return Ok(());
+    }
+
+    let imported =
+        sqlx::query("INSERT OR IGNORE INTO admin_credentials (label, token_hash) VALUES (?, ?)")
+            .bind(ADMIN_TOKEN_ENV)
+            .bind(token_hash)
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+    if imported > 0 {
+        tracing::info!("Imported ADMIN_TOKEN as an admin credential");
+    }
+
+    Ok(())
+}
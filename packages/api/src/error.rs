@@ -0,0 +1,87 @@
+//! The error type returned by every server function.
+
+use dioxus::fullstack::{AsStatusCode, StatusCode};
+use dioxus::prelude::ServerFnError;
+use serde::{Deserialize, Serialize};
+
+/// A server function failure, serialised to the client together with a matching HTTP
+/// status code so the UI can react to the kind of failure instead of its wording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum ApiError {
+    /// Missing or wrong credentials, or an expired session. (401)
+    Unauthorized(String),
+    /// The request itself is invalid, e.g. a score out of range. (400)
+    Validation(String),
+    /// The referenced member, meeting or album doesn't exist. (404)
+    NotFound(String),
+    /// Spotify couldn't be reached or answered with an error. (503)
+    UpstreamUnavailable(String),
+    /// Spotify is rate limiting the server. (429)
+    RateLimited { retry_after_secs: Option<u64> },
+    /// Too many failed logins for the member or from the client's IP. (429)
+    LockedOut { retry_after_secs: u64 },
+    /// The meeting's review window is closed, or an admin has locked it. (409)
+    ReviewsClosed(String),
+    /// Anything else: database failures, misconfiguration, transport errors. (500)
+    Internal(String),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized(message)
+            | Self::Validation(message)
+            | Self::NotFound(message)
+            | Self::UpstreamUnavailable(message)
+            | Self::ReviewsClosed(message)
+            | Self::Internal(message) => f.write_str(message),
+            Self::RateLimited {
+                retry_after_secs: Some(secs),
+            } => write!(f, "Rate limited by Spotify, retry after {secs}s"),
+            Self::RateLimited {
+                retry_after_secs: None,
+            } => f.write_str("Rate limited by Spotify"),
+            Self::LockedOut { retry_after_secs } => {
+                write!(f, "Too many failed logins, retry after {retry_after_secs}s")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl AsStatusCode for ApiError {
+    fn as_status_code(&self) -> StatusCode {
+        match self {
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::UpstreamUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::RateLimited { .. } | Self::LockedOut { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::ReviewsClosed(_) => StatusCode::CONFLICT,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Framework-level failures (transport errors, undecodable requests or responses)
+/// arrive as `ServerFnError`. Errors that carry a status code keep their kind.
+impl From<ServerFnError> for ApiError {
+    fn from(error: ServerFnError) -> Self {
+        match error {
+            ServerFnError::ServerError { message, code, .. } => match code {
+                400 | 422 => Self::Validation(message),
+                401 | 403 => Self::Unauthorized(message),
+                404 => Self::NotFound(message),
+                409 => Self::ReviewsClosed(message),
+                429 => Self::RateLimited {
+                    retry_after_secs: None,
+                },
+                502..=504 => Self::UpstreamUnavailable(message),
+                _ => Self::Internal(message),
+            },
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
@@ -1,9 +1,17 @@
 //! This crate contains all shared fullstack server functions.
 use dioxus::prelude::*;
 
-use crate::api_models::{AlbumTrack, Data, HistoryEntry, Reviews, SetCurrentRequest, SpotifyAlbumSearchItem};
+use crate::api_models::{
+    AdminAccess, AdminSession, AlbumTrack, AuditLogFilter, AuditLogPage, ClubStats,
+    Compatibility, CompatibilityBasis, Data, FairnessReport, HistoryEntry, MeetingDetails,
+    Member, MemberId, MemberInvite, MemberSession, PlaylistExport, RemovedMember, Reviews,
+    RotationSkip, SetCurrentRequest, SpotifyAccount, SpotifyAlbumSearchItem, Unavailability,
+};
 
 pub mod api_models;
+pub mod error;
+
+pub use error::ApiError;
 
 #[cfg(feature = "server")]
 mod db;
@@ -14,22 +22,62 @@ mod server;
 #[cfg(feature = "server")]
 pub use server::init_db;
 
+#[cfg(feature = "server")]
+pub use server::{album_art_router, backfill_album_art, BackfillReport};
+
 /// Get the current album, next meeting and member list.
 #[get("/api/info")]
-pub async fn get_current() -> Result<Data, ServerFnError> {
+pub async fn get_current() -> Result<Data, ApiError> {
     #[cfg(feature = "server")]
     { server::get_current_impl().await }
     #[cfg(not(feature = "server"))]
-    { Err(ServerFnError::new("Only available on server builds")) }
+    { Err(ServerFnError::new("Only available on server builds").into()) }
 }
 
 /// Get all past (non-current) meetings, ordered by meeting date ascending.
 #[get("/api/history")]
-pub async fn get_history() -> Result<Vec<HistoryEntry>, ServerFnError> {
+pub async fn get_history() -> Result<Vec<HistoryEntry>, ApiError> {
     #[cfg(feature = "server")]
     { server::get_history_impl().await }
     #[cfg(not(feature = "server"))]
-    { Err(ServerFnError::new("Only available on server builds")) }
+    { Err(ServerFnError::new("Only available on server builds").into()) }
+}
+
+/// Club-wide statistics over all revealed reviews.
+#[get("/api/stats")]
+pub async fn get_stats() -> Result<ClubStats, ApiError> {
+    #[cfg(feature = "server")]
+    { server::get_stats_impl().await }
+    #[cfg(not(feature = "server"))]
+    { Err(ServerFnError::new("Only available on server builds").into()) }
+}
+
+/// How much members' scores agree, pair by pair, counting only pairs that have scored at
+/// least `min_overlap` of the same albums or tracks.
+#[server]
+pub async fn get_compatibility(
+    basis: CompatibilityBasis,
+    min_overlap: u32,
+) -> Result<Compatibility, ApiError> {
+    #[cfg(feature = "server")]
+    { server::get_compatibility_impl(basis, min_overlap).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (basis, min_overlap);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Get a single meeting, current or past, with its album, picker and the member list.
+#[server]
+pub async fn get_meeting(meeting_id: String) -> Result<MeetingDetails, ApiError> {
+    #[cfg(feature = "server")]
+    { server::get_meeting_impl(meeting_id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = meeting_id;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
 }
 
 /// Set the current album, meeting info and picker. Archives the previous state to history.
@@ -37,13 +85,13 @@ pub async fn get_history() -> Result<Vec<HistoryEntry>, ServerFnError> {
 pub async fn admin_set_current(
     admin_token: String,
     req: SetCurrentRequest,
-) -> Result<(), ServerFnError> {
+) -> Result<(), ApiError> {
     #[cfg(feature = "server")]
     { server::admin_set_current_impl(admin_token, req).await }
     #[cfg(not(feature = "server"))]
     {
         let _ = (admin_token, req);
-        Err(ServerFnError::new("Only available on server builds"))
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
@@ -52,13 +100,13 @@ pub async fn admin_set_current(
 pub async fn admin_update_current(
     admin_token: String,
     req: SetCurrentRequest,
-) -> Result<(), ServerFnError> {
+) -> Result<(), ApiError> {
     #[cfg(feature = "server")]
     { server::admin_update_current_impl(admin_token, req).await }
     #[cfg(not(feature = "server"))]
     {
         let _ = (admin_token, req);
-        Err(ServerFnError::new("Only available on server builds"))
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
@@ -67,13 +115,13 @@ pub async fn admin_update_current(
 pub async fn admin_delete_history_entry(
     admin_token: String,
     id: String,
-) -> Result<(), ServerFnError> {
+) -> Result<(), ApiError> {
     #[cfg(feature = "server")]
     { server::admin_delete_history_entry_impl(admin_token, id).await }
     #[cfg(not(feature = "server"))]
     {
         let _ = (admin_token, id);
-        Err(ServerFnError::new("Only available on server builds"))
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
@@ -81,32 +129,273 @@ pub async fn admin_delete_history_entry(
 #[post("/api/admin/reorder-members")]
 pub async fn admin_reorder_members(
     admin_token: String,
-    ordered_names: Vec<String>,
-) -> Result<(), ServerFnError> {
+    ordered_ids: Vec<MemberId>,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_reorder_members_impl(admin_token, ordered_ids).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, ordered_ids);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Whose turn it is to pick a meeting on `meeting_date` (`YYYY-MM-DD`), skipping members
+/// who are away then. `None` checks the next meeting, like [`get_current`].
+#[server]
+pub async fn get_next_picker(meeting_date: Option<String>) -> Result<Option<Member>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::get_next_picker_impl(meeting_date).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = meeting_date;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Recently skipped turns: members who were away, or passed over, when a meeting was set.
+#[post("/api/admin/rotation/skips")]
+pub async fn admin_rotation_skips(admin_token: String) -> Result<Vec<RotationSkip>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_rotation_skips_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Picks per member against their share of the meetings since they joined, flagging
+/// members who are a turn or more behind or ahead.
+#[post("/api/admin/rotation/fairness")]
+pub async fn admin_fairness_report(admin_token: String) -> Result<FairnessReport, ApiError> {
     #[cfg(feature = "server")]
-    { server::admin_reorder_members_impl(admin_token, ordered_names).await }
+    { server::admin_fairness_report_impl(admin_token).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (admin_token, ordered_names);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
-/// Verify a member's credentials (name + pre-shared password). Returns Ok if valid.
+/// Upcoming and ongoing ranges when members are away.
+#[get("/api/unavailability")]
+pub async fn get_unavailability() -> Result<Vec<Unavailability>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::get_unavailability_impl().await }
+    #[cfg(not(feature = "server"))]
+    { Err(ServerFnError::new("Only available on server builds").into()) }
+}
+
+/// Register an inclusive date range when the logged-in member is away. Returns the updated
+/// list of ranges.
+#[post("/api/member/unavailability")]
+pub async fn add_unavailability(
+    session_token: String,
+    start_date: String,
+    end_date: String,
+    note: Option<String>,
+) -> Result<Vec<Unavailability>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::add_unavailability_impl(session_token, start_date, end_date, note).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (session_token, start_date, end_date, note);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Remove one of the logged-in member's own ranges. Returns the updated list of ranges.
+#[post("/api/member/unavailability/remove")]
+pub async fn remove_unavailability(
+    session_token: String,
+    id: i64,
+) -> Result<Vec<Unavailability>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::remove_unavailability_impl(session_token, id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (session_token, id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Register an inclusive date range when any member is away.
+#[post("/api/admin/unavailability")]
+pub async fn admin_add_unavailability(
+    admin_token: String,
+    member_id: MemberId,
+    start_date: String,
+    end_date: String,
+    note: Option<String>,
+) -> Result<Vec<Unavailability>, ApiError> {
+    #[cfg(feature = "server")]
+    {
+        server::admin_add_unavailability_impl(admin_token, member_id, start_date, end_date, note)
+            .await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_id, start_date, end_date, note);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Remove any member's range.
+#[post("/api/admin/unavailability/remove")]
+pub async fn admin_remove_unavailability(
+    admin_token: String,
+    id: i64,
+) -> Result<Vec<Unavailability>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_remove_unavailability_impl(admin_token, id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Verify a member's credentials (member ID + pre-shared password) and start a session.
+/// The returned token is used for reviewing instead of the password.
 #[post("/api/member/verify")]
-pub async fn verify_member(member_name: String, password: String) -> Result<(), ServerFnError> {
+pub async fn verify_member(
+    member_id: MemberId,
+    password: String,
+) -> Result<MemberSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::verify_member_impl(member_id, password).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (member_id, password);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// The member a session token belongs to. `Unauthorized` once the session has expired
+/// or been revoked.
+#[post("/api/member/session")]
+pub async fn member_session(session_token: String) -> Result<Member, ApiError> {
+    #[cfg(feature = "server")]
+    { server::member_session_impl(session_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = session_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// End a session. Unknown or expired tokens are ignored.
+#[post("/api/member/logout")]
+pub async fn member_logout(session_token: String) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::member_logout_impl(session_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = session_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Change the logged-in member's password. Ends all of the member's sessions and
+/// returns a new one.
+#[post("/api/member/password")]
+pub async fn change_member_password(
+    session_token: String,
+    current_password: String,
+    new_password: String,
+) -> Result<MemberSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::change_member_password_impl(session_token, current_password, new_password).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (session_token, current_password, new_password);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// The member an invite link is for. `NotFound` if it's used, expired or replaced.
+#[post("/api/member/invite")]
+pub async fn member_invite(invite_token: String) -> Result<Member, ApiError> {
+    #[cfg(feature = "server")]
+    { server::member_invite_impl(invite_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = invite_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Set a password with an invite link and log in. The link can only be used once.
+#[post("/api/member/invite/redeem")]
+pub async fn redeem_member_invite(
+    invite_token: String,
+    new_password: String,
+) -> Result<MemberSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::redeem_member_invite_impl(invite_token, new_password).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (invite_token, new_password);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Log in to the admin page with an admin credential's token. The returned session token
+/// is what the other admin server functions take as `admin_token`.
+#[post("/api/admin/login")]
+pub async fn admin_login(token: String) -> Result<AdminSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_login_impl(token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Log in to the admin page as a member with admin rights, using their own password.
+#[post("/api/admin/login/member")]
+pub async fn admin_login_member(
+    member_id: MemberId,
+    password: String,
+) -> Result<AdminSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_login_member_impl(member_id, password).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (member_id, password);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// The admin session a token belongs to. `Unauthorized` once it has expired or been revoked.
+#[post("/api/admin/session")]
+pub async fn admin_session(admin_token: String) -> Result<AdminSession, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_session_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// End an admin session. Unknown or expired tokens are ignored.
+#[post("/api/admin/logout")]
+pub async fn admin_logout(admin_token: String) -> Result<(), ApiError> {
     #[cfg(feature = "server")]
-    { server::verify_member_password_internal(&member_name, &password).await }
+    { server::admin_logout_impl(admin_token).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (member_name, password);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
 /// Get the cached track listing for an album. Fetches from Spotify on first call.
 #[server]
-pub async fn get_album_tracks(album_id: String) -> Result<Vec<AlbumTrack>, ServerFnError> {
+pub async fn get_album_tracks(album_id: String) -> Result<Vec<AlbumTrack>, ApiError> {
     #[cfg(feature = "server")]
     { server::get_album_tracks_impl(album_id).await }
     #[cfg(not(feature = "server"))]
@@ -116,68 +405,113 @@ pub async fn get_album_tracks(album_id: String) -> Result<Vec<AlbumTrack>, Serve
     }
 }
 
-/// Get all album and track reviews for a meeting.
+/// Get the album and track reviews for a meeting. Until the meeting's reviews are revealed,
+/// only the reviews of the member the session belongs to are included.
 #[server]
-pub async fn get_reviews(meeting_id: String) -> Result<Reviews, ServerFnError> {
+pub async fn get_reviews(
+    meeting_id: String,
+    session_token: Option<String>,
+) -> Result<Reviews, ApiError> {
     #[cfg(feature = "server")]
-    { server::get_reviews_impl(meeting_id).await }
+    { server::get_reviews_impl(meeting_id, session_token).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = meeting_id;
+        let _ = (meeting_id, session_token);
         Ok(Reviews {
             album_reviews: Vec::new(),
             track_reviews: Vec::new(),
+            revealed: true,
+            reveal_policy: Default::default(),
+            reviewer_count: 0,
+            window: api_models::ReviewWindow {
+                open: false,
+                closes_at: None,
+                locked: false,
+            },
         })
     }
 }
 
-/// Submit or update an album-level review.
+/// Close reviewing of a meeting ahead of time, or open it again.
+#[post("/api/admin/reviews-locked")]
+pub async fn admin_set_reviews_locked(
+    admin_token: String,
+    meeting_id: String,
+    locked: bool,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_set_reviews_locked_impl(admin_token, meeting_id, locked).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, meeting_id, locked);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Show everyone's reviews of a meeting before its reveal policy would.
+#[post("/api/admin/reveal-reviews")]
+pub async fn admin_reveal_reviews(
+    admin_token: String,
+    meeting_id: String,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_reveal_reviews_impl(admin_token, meeting_id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, meeting_id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Submit or update an album-level review. `text` replaces the written review; `None`
+/// leaves it as it is and an empty text removes it.
 #[post("/api/review/album")]
 pub async fn submit_album_review(
-    member_name: String,
-    password: String,
+    session_token: String,
     meeting_id: String,
     score: u8,
-) -> Result<Reviews, ServerFnError> {
+    text: Option<String>,
+) -> Result<Reviews, ApiError> {
     if score > 10 {
-        return Err(ServerFnError::new("Score must be between 0 and 10"));
+        return Err(ApiError::Validation("Score must be between 0 and 10".to_string()));
     }
     #[cfg(feature = "server")]
-    { server::submit_album_review_impl(member_name, password, meeting_id, score).await }
+    { server::submit_album_review_impl(session_token, meeting_id, score, text).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (member_name, password, meeting_id, score);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = (session_token, meeting_id, score, text);
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
-/// Submit or update a per-track review.
+/// Submit or update a per-track review. `comment` works like the album review's `text`.
 #[post("/api/review/track")]
 pub async fn submit_track_review(
-    member_name: String,
-    password: String,
+    session_token: String,
     meeting_id: String,
     track_id: String,
     score: u8,
-) -> Result<Reviews, ServerFnError> {
+    comment: Option<String>,
+) -> Result<Reviews, ApiError> {
     if score > 10 {
-        return Err(ServerFnError::new("Score must be between 0 and 10"));
+        return Err(ApiError::Validation("Score must be between 0 and 10".to_string()));
     }
     #[cfg(feature = "server")]
-    { server::submit_track_review_impl(member_name, password, meeting_id, track_id, score).await }
+    { server::submit_track_review_impl(session_token, meeting_id, track_id, score, comment).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (member_name, password, meeting_id, track_id, score);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = (session_token, meeting_id, track_id, score, comment);
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
-/// Search Spotify for albums matching a query.
+/// Search Spotify for albums matching a query. An album or track link/URI
+/// returns that album as the only result.
 #[post("/api/admin/spotify/search")]
 pub async fn admin_spotify_album_search(
     admin_token: String,
     query: String,
-) -> Result<Vec<SpotifyAlbumSearchItem>, ServerFnError> {
+) -> Result<Vec<SpotifyAlbumSearchItem>, ApiError> {
     let search_term = query.trim();
     if search_term.is_empty() {
         return Ok(Vec::new());
@@ -187,7 +521,7 @@ pub async fn admin_spotify_album_search(
     #[cfg(not(feature = "server"))]
     {
         let _ = (admin_token, search_term);
-        Err(ServerFnError::new("Only available on server builds"))
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
@@ -197,29 +531,233 @@ pub async fn admin_spotify_album_search(
 #[post("/api/admin/member/delete")]
 pub async fn admin_delete_member(
     admin_token: String,
-    member_name: String,
-) -> Result<(), ServerFnError> {
+    member_id: MemberId,
+) -> Result<(), ApiError> {
     #[cfg(feature = "server")]
-    { server::admin_delete_member_impl(admin_token, member_name).await }
+    { server::admin_delete_member_impl(admin_token, member_id).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (admin_token, member_name);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = (admin_token, member_id);
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
 
-/// Generate a new random password for a member, store its Argon2 hash, and return
-/// the plain-text password once so the admin can share it with the member.
-#[post("/api/admin/member/set-password")]
-pub async fn admin_set_member_password(
+/// Add a new member at `position` in the rotation (clamped to the end).
+/// Returns the updated member order.
+#[post("/api/admin/member/add")]
+pub async fn admin_add_member(
     admin_token: String,
     member_name: String,
-) -> Result<String, ServerFnError> {
+    position: u32,
+) -> Result<Vec<Member>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_add_member_impl(admin_token, member_name, position).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_name, position);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Change a member's display name.
+#[post("/api/admin/member/rename")]
+pub async fn admin_rename_member(
+    admin_token: String,
+    member_id: MemberId,
+    new_name: String,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_rename_member_impl(admin_token, member_id, new_name).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_id, new_name);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// List soft-deleted members, most recently removed first.
+#[post("/api/admin/member/removed")]
+pub async fn admin_removed_members(admin_token: String) -> Result<Vec<RemovedMember>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_removed_members_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Restore a soft-deleted member to the end of the rotation. Returns the updated member order.
+#[post("/api/admin/member/restore")]
+pub async fn admin_restore_member(
+    admin_token: String,
+    member_id: MemberId,
+) -> Result<Vec<Member>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_restore_member_impl(admin_token, member_id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Create a single-use invite link for a member to set their own password, valid for a
+/// week. Replaces the member's earlier unused links. See [`api_models::member_invite_path`].
+#[post("/api/admin/member/invite")]
+pub async fn admin_create_member_invite(
+    admin_token: String,
+    member_id: MemberId,
+) -> Result<MemberInvite, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_create_member_invite_impl(admin_token, member_id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// List admin credentials and the members with admin rights.
+#[post("/api/admin/access")]
+pub async fn admin_access(admin_token: String) -> Result<AdminAccess, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_access_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Create a labelled admin credential, valid for `valid_days` or forever. Returns the
+/// token once; only its hash is stored.
+#[post("/api/admin/credentials/create")]
+pub async fn admin_create_credential(
+    admin_token: String,
+    label: String,
+    valid_days: Option<u32>,
+) -> Result<String, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_create_credential_impl(admin_token, label, valid_days).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, label, valid_days);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Revoke an admin credential, ending every session logged in with it.
+#[post("/api/admin/credentials/revoke")]
+pub async fn admin_revoke_credential(
+    admin_token: String,
+    credential_id: i64,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_revoke_credential_impl(admin_token, credential_id).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, credential_id);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Grant or take away a member's admin rights.
+#[post("/api/admin/member/set-admin")]
+pub async fn admin_set_member_admin(
+    admin_token: String,
+    member_id: MemberId,
+    is_admin: bool,
+) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_set_member_admin_impl(admin_token, member_id, is_admin).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, member_id, is_admin);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// A page of the audit log, newest first, optionally filtered by action and actor.
+#[post("/api/admin/audit-log")]
+pub async fn admin_audit_log(
+    admin_token: String,
+    filter: AuditLogFilter,
+) -> Result<AuditLogPage, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_audit_log_impl(admin_token, filter).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (admin_token, filter);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Start connecting a Spotify account. Returns the Spotify authorize URL to send the
+/// admin to; Spotify redirects back to `SPOTIFY_REDIRECT_URI` with a code and state.
+#[post("/api/admin/spotify/authorize")]
+pub async fn admin_spotify_authorize(admin_token: String) -> Result<String, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_spotify_authorize_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Finish connecting a Spotify account with the code and state Spotify redirected back with.
+#[post("/api/spotify/callback")]
+pub async fn spotify_complete_authorization(
+    code: String,
+    state: String,
+) -> Result<SpotifyAccount, ApiError> {
+    #[cfg(feature = "server")]
+    { server::spotify_complete_authorization_impl(code, state).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (code, state);
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// The connected Spotify account, if any.
+#[post("/api/admin/spotify/account")]
+pub async fn admin_spotify_account(
+    admin_token: String,
+) -> Result<Option<SpotifyAccount>, ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_spotify_account_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Forget the connected Spotify account and its tokens. The playlist stays on Spotify.
+#[post("/api/admin/spotify/disconnect")]
+pub async fn admin_spotify_disconnect(admin_token: String) -> Result<(), ApiError> {
+    #[cfg(feature = "server")]
+    { server::admin_spotify_disconnect_impl(admin_token).await }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
+    }
+}
+
+/// Create or update the club playlist on the connected account with the highest-rated
+/// track of every past album.
+#[post("/api/admin/spotify/export-playlist")]
+pub async fn admin_spotify_export_playlist(
+    admin_token: String,
+) -> Result<PlaylistExport, ApiError> {
     #[cfg(feature = "server")]
-    { server::admin_set_member_password_impl(admin_token, member_name).await }
+    { server::admin_spotify_export_playlist_impl(admin_token).await }
     #[cfg(not(feature = "server"))]
     {
-        let _ = (admin_token, member_name);
-        Err(ServerFnError::new("Only available on server builds"))
+        let _ = admin_token;
+        Err(ServerFnError::new("Only available on server builds").into())
     }
 }
@@ -0,0 +1,385 @@
+//! Admin credentials, admin sessions and admin rights for members.
+
+use rand::distributions::{Alphanumeric, DistString};
+use serde_json::json;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{AdminAccess, AdminCredential, AdminSession, AuditAction, MemberId};
+use crate::error::ApiError;
+
+use super::{
+    audit::{record_audit, Actor},
+    get_db, hash_secret,
+    login_throttle::{login_keys, record_success, reserve_attempt},
+    members::verify_member_login,
+    secret_matches,
+    sessions::{hash_token, new_session_token},
+    IntoServerError,
+};
+
+/// How long an admin login lasts, as an SQLite `datetime` modifier.
+const ADMIN_SESSION_TTL: &str = "+12 hours";
+
+const CREDENTIAL_TOKEN_LEN: usize = 32;
+const MAX_CREDENTIAL_LABEL_LEN: usize = 60;
+
+/// Check the admin session token every admin server function is called with, and
+/// return who it belongs to.
+pub async fn ensure_admin(admin_token: &str) -> Result<AdminSession, ApiError> {
+    let pool = get_db().await?;
+
+    let row = sqlx::query(
+        "SELECT COALESCE(c.label, m.name) AS label, s.expires_at, s.credential_id, s.member_id
+         FROM admin_sessions s
+         LEFT JOIN admin_credentials c ON c.id = s.credential_id
+         LEFT JOIN members m ON m.id = s.member_id
+         WHERE s.token_hash = ?
+           AND s.expires_at > datetime('now')
+           AND (
+               (c.id IS NOT NULL
+                AND c.revoked_at IS NULL
+                AND (c.expires_at IS NULL OR c.expires_at > datetime('now')))
+               OR (m.is_admin = 1 AND m.deleted_at IS NULL)
+           )",
+    )
+    .bind(hash_token(admin_token))
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+
+    let row = row.ok_or_else(|| ApiError::Unauthorized("Unauthorized".to_string()))?;
+
+    Ok(AdminSession {
+        token: admin_token.to_string(),
+        label: row.get("label"),
+        expires_at: row.get("expires_at"),
+        credential_id: row.get("credential_id"),
+        member_id: row.get("member_id"),
+    })
+}
+
+/// Log in with an admin credential's token. Failures are throttled per client IP.
+pub async fn admin_login_impl(token: String) -> Result<AdminSession, ApiError> {
+    tracing::info!("POST /api/admin/login");
+    let keys = login_keys(None).await;
+    reserve_attempt(&keys)?;
+
+    let pool = get_db().await?;
+
+    let rows = sqlx::query(
+        "SELECT id, token_hash FROM admin_credentials
+         WHERE revoked_at IS NULL
+           AND (expires_at IS NULL OR expires_at > datetime('now'))",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let mut credential_id = None;
+    for row in rows {
+        if secret_matches(&token, &row.get::<String, _>("token_hash"))? {
+            credential_id = Some(row.get::<i64, _>("id"));
+            break;
+        }
+    }
+
+    let Some(credential_id) = credential_id else {
+        return Err(ApiError::Unauthorized(
+            "Unknown or revoked admin token".to_string(),
+        ));
+    };
+    record_success(&keys);
+
+    let session = start_admin_session(pool, Some(credential_id), None).await?;
+    tracing::info!("POST /api/admin/login → \"{}\"", session.label);
+    Ok(session)
+}
+
+/// Log in as a member with admin rights, using their own password.
+pub async fn admin_login_member_impl(
+    member_id: MemberId,
+    password: String,
+) -> Result<AdminSession, ApiError> {
+    tracing::info!("POST /api/admin/login/member member={member_id}");
+    // A member without admin rights gets the same answer as a wrong password, so this
+    // doesn't reveal who the admins are.
+    verify_member_login(member_id, &password, true).await?;
+
+    let pool = get_db().await?;
+    let session = start_admin_session(pool, None, Some(member_id)).await?;
+    tracing::info!("POST /api/admin/login/member {member_id} → ok");
+    Ok(session)
+}
+
+pub async fn admin_session_impl(admin_token: String) -> Result<AdminSession, ApiError> {
+    ensure_admin(&admin_token).await
+}
+
+pub async fn admin_logout_impl(admin_token: String) -> Result<(), ApiError> {
+    let pool = get_db().await?;
+
+    sqlx::query("DELETE FROM admin_sessions WHERE token_hash = ?")
+        .bind(hash_token(&admin_token))
+        .execute(pool)
+        .await
+        .server_err()?;
+
+    Ok(())
+}
+
+pub async fn admin_access_impl(admin_token: String) -> Result<AdminAccess, ApiError> {
+    ensure_admin(&admin_token).await?;
+    let pool = get_db().await?;
+
+    let rows = sqlx::query(
+        "SELECT id, label, created_at, expires_at, revoked_at FROM admin_credentials
+         ORDER BY revoked_at IS NOT NULL, created_at DESC, id DESC",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let credentials = rows
+        .into_iter()
+        .map(|row| AdminCredential {
+            id: row.get("id"),
+            label: row.get("label"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            revoked_at: row.get("revoked_at"),
+        })
+        .collect();
+
+    let admin_members = sqlx::query_scalar(
+        "SELECT id FROM members WHERE is_admin = 1 AND deleted_at IS NULL ORDER BY sort_order, id",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(AdminAccess {
+        credentials,
+        admin_members,
+    })
+}
+
+/// Create a credential and return its token. Only the hash is stored, so this is the
+/// only time the token can be seen.
+pub async fn admin_create_credential_impl(
+    admin_token: String,
+    label: String,
+    valid_days: Option<u32>,
+) -> Result<String, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    let label = validate_label(&label)?;
+    if valid_days == Some(0) {
+        return Err(ApiError::Validation(
+            "A credential must be valid for at least one day".to_string(),
+        ));
+    }
+    tracing::info!(
+        "POST /api/admin/credentials/create label=\"{label}\" valid_days={valid_days:?}"
+    );
+
+    let plain = Alphanumeric.sample_string(&mut rand::thread_rng(), CREDENTIAL_TOKEN_LEN);
+    let hash = hash_secret(&plain)?;
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    // `datetime('now', NULL)` is NULL, i.e. the credential never expires.
+    let row = sqlx::query(
+        "INSERT INTO admin_credentials (label, token_hash, expires_at)
+         VALUES (?, ?, datetime('now', ?))
+         RETURNING id, expires_at",
+    )
+    .bind(&label)
+    .bind(&hash)
+    .bind(valid_days.map(|days| format!("+{days} days")))
+    .fetch_one(&mut *tx)
+    .await
+    .server_err()?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::CreateCredential,
+        None,
+        Some(json!({
+            "id": row.get::<i64, _>("id"),
+            "label": label,
+            "expires_at": row.get::<Option<String>, _>("expires_at"),
+        })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/credentials/create \"{label}\" → ok");
+    Ok(plain)
+}
+
+/// Revoke a credential and end every session logged in with it.
+pub async fn admin_revoke_credential_impl(
+    admin_token: String,
+    credential_id: i64,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/credentials/revoke credential={credential_id}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let label: String = sqlx::query_scalar(
+        "UPDATE admin_credentials SET revoked_at = datetime('now')
+         WHERE id = ? AND revoked_at IS NULL
+         RETURNING label",
+    )
+    .bind(credential_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("No active credential {credential_id}")))?;
+
+    ensure_admin_access_remains(&mut tx).await?;
+
+    sqlx::query("DELETE FROM admin_sessions WHERE credential_id = ?")
+        .bind(credential_id)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::RevokeCredential,
+        Some(json!({ "id": credential_id, "label": label })),
+        None,
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/credentials/revoke {credential_id} → ok");
+    Ok(())
+}
+
+/// Grant or take away a member's admin rights. Taking them away ends the member's
+/// admin sessions.
+pub async fn admin_set_member_admin_impl(
+    admin_token: String,
+    member_id: MemberId,
+    is_admin: bool,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/member/set-admin member={member_id} is_admin={is_admin}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let row = sqlx::query("SELECT name, is_admin FROM members WHERE id = ? AND deleted_at IS NULL")
+        .bind(member_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .server_err()?
+        .ok_or_else(|| ApiError::NotFound(format!("Member {member_id} not found")))?;
+    let name: String = row.get("name");
+    let was_admin: bool = row.get("is_admin");
+
+    sqlx::query("UPDATE members SET is_admin = ? WHERE id = ?")
+        .bind(is_admin)
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    if !is_admin {
+        ensure_admin_access_remains(&mut tx).await?;
+
+        sqlx::query("DELETE FROM admin_sessions WHERE member_id = ?")
+            .bind(member_id)
+            .execute(&mut *tx)
+            .await
+            .server_err()?;
+    }
+
+    if was_admin != is_admin {
+        record_audit(
+            &mut tx,
+            &Actor::admin(&admin),
+            AuditAction::SetMemberAdmin,
+            Some(json!({ "id": member_id, "name": name, "is_admin": was_admin })),
+            Some(json!({ "id": member_id, "name": name, "is_admin": is_admin })),
+        )
+        .await?;
+    }
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/set-admin {member_id} → ok");
+    Ok(())
+}
+
+async fn start_admin_session(
+    pool: &SqlitePool,
+    credential_id: Option<i64>,
+    member_id: Option<MemberId>,
+) -> Result<AdminSession, ApiError> {
+    sqlx::query("DELETE FROM admin_sessions WHERE expires_at <= datetime('now')")
+        .execute(pool)
+        .await
+        .server_err()?;
+
+    let token = new_session_token();
+
+    sqlx::query(
+        "INSERT INTO admin_sessions (token_hash, credential_id, member_id, expires_at)
+         VALUES (?, ?, ?, datetime('now', ?))",
+    )
+    .bind(hash_token(&token))
+    .bind(credential_id)
+    .bind(member_id)
+    .bind(ADMIN_SESSION_TTL)
+    .execute(pool)
+    .await
+    .server_err()?;
+
+    ensure_admin(&token).await
+}
+
+/// Refuse changes that would leave nobody able to log in to the admin page. Admin members
+/// without a password can't log in, so they don't count.
+pub async fn ensure_admin_access_remains(conn: &mut SqliteConnection) -> Result<(), ApiError> {
+    let remaining: i64 = sqlx::query_scalar(
+        "SELECT (SELECT COUNT(*) FROM admin_credentials
+                 WHERE revoked_at IS NULL
+                   AND (expires_at IS NULL OR expires_at > datetime('now')))
+              + (SELECT COUNT(*) FROM members
+                 WHERE is_admin = 1 AND deleted_at IS NULL AND password_hash IS NOT NULL)",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .server_err()?;
+
+    if remaining == 0 {
+        return Err(ApiError::Validation(
+            "At least one admin credential or admin member with a password must remain".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_label(label: &str) -> Result<String, ApiError> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(ApiError::Validation(
+            "Credential label must not be empty".to_string(),
+        ));
+    }
+    if label.chars().count() > MAX_CREDENTIAL_LABEL_LEN {
+        return Err(ApiError::Validation(format!(
+            "Credential label must be at most {MAX_CREDENTIAL_LABEL_LEN} characters"
+        )));
+    }
+    Ok(label.to_string())
+}
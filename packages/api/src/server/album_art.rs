@@ -0,0 +1,314 @@
+//! Album art downloaded from the catalog, resized and served from our own route.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use dioxus::server::axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::get,
+    Router,
+};
+use image::{codecs::jpeg::JpegEncoder, imageops::FilterType};
+use sqlx::{Row, SqlitePool};
+
+use crate::api_models::{album_art_path, ArtSize};
+use crate::error::ApiError;
+
+use super::{get_db, IntoServerError};
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Covers are well under a megabyte; anything much bigger isn't one.
+const MAX_DOWNLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+const JPEG_QUALITY: u8 = 85;
+
+/// Stored art only changes if the album is downloaded again, so browsers may keep it a day.
+const CACHE_CONTROL: &str = "public, max-age=86400";
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// How many albums [`backfill_album_art`] downloaded, skipped or failed on.
+#[derive(Debug, Default)]
+pub struct BackfillReport {
+    pub downloaded: u32,
+    pub already_cached: u32,
+    /// Albums whose meetings never had a cover URL.
+    pub without_art: u32,
+    pub failed: Vec<(String, ApiError)>,
+}
+
+/// Routes serving cached album art at [`album_art_path`], mounted next to the app.
+pub fn album_art_router() -> Router {
+    Router::new().route("/album-art/{album_id}/{size}", get(serve_album_art))
+}
+
+/// The `album_art` a page should show for an album: our own route when the catalog had a
+/// cover, or empty when it didn't.
+pub fn served_album_art(album_id: &str, source_url: &str) -> String {
+    if source_url.is_empty() {
+        String::new()
+    } else {
+        album_art_path(album_id, ArtSize::Large)
+    }
+}
+
+/// Download and store the art for a picked album, unless it's already cached from the
+/// same source URL. Returns whether anything was downloaded.
+pub async fn ensure_album_art(
+    pool: &SqlitePool,
+    album_id: &str,
+    source_url: &str,
+) -> Result<bool, ApiError> {
+    let cached_from: Option<String> =
+        sqlx::query_scalar("SELECT source_url FROM album_art WHERE album_id = ? AND size = ?")
+            .bind(album_id)
+            .bind(ArtSize::Large.as_str())
+            .fetch_optional(pool)
+            .await
+            .server_err()?;
+
+    if cached_from.as_deref() == Some(source_url) {
+        return Ok(false);
+    }
+
+    cache_album_art(pool, album_id, source_url).await?;
+    Ok(true)
+}
+
+/// Download art for every album in `meetings` that doesn't have it yet, or for all of
+/// them with `force`. Failures are collected rather than stopping the backfill.
+pub async fn backfill_album_art(force: bool) -> Result<BackfillReport, ApiError> {
+    let pool = get_db().await?;
+
+    let album_ids: Vec<String> =
+        sqlx::query_scalar("SELECT DISTINCT album_id FROM meetings ORDER BY album_id")
+            .fetch_all(pool)
+            .await
+            .server_err()?;
+
+    let mut report = BackfillReport::default();
+    for album_id in album_ids {
+        let Some(source_url) = source_url(pool, &album_id).await? else {
+            report.without_art += 1;
+            continue;
+        };
+
+        let result = if force {
+            cache_album_art(pool, &album_id, &source_url)
+                .await
+                .map(|()| true)
+        } else {
+            ensure_album_art(pool, &album_id, &source_url).await
+        };
+
+        match result {
+            Ok(true) => report.downloaded += 1,
+            Ok(false) => report.already_cached += 1,
+            Err(e) => report.failed.push((album_id, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// What to answer a request for one size of an album's art with.
+enum AlbumArt {
+    Cached {
+        content_type: String,
+        data: Vec<u8>,
+    },
+    /// Not cached; send the browser to the catalog's copy instead.
+    Remote(String),
+    Missing,
+}
+
+async fn serve_album_art(Path((album_id, size)): Path<(String, String)>) -> Response {
+    let Some(size) = ArtSize::parse(&size) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match find_album_art(&album_id, size).await {
+        Ok(AlbumArt::Cached { content_type, data }) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+            ],
+            data,
+        )
+            .into_response(),
+        Ok(AlbumArt::Remote(url)) => Redirect::temporary(&url).into_response(),
+        Ok(AlbumArt::Missing) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("GET /album-art/{album_id}/{} failed: {e}", size.as_str());
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn find_album_art(album_id: &str, size: ArtSize) -> Result<AlbumArt, ApiError> {
+    let pool = get_db().await?;
+
+    if let Some(art) = load_album_art(pool, album_id, size).await? {
+        return Ok(art);
+    }
+
+    // Not cached: picked before art was cached, or the download at pick time failed.
+    // Downloading here would let any visitor make us fetch again on every request, so
+    // that is left to the next pick and the backfill.
+    Ok(match source_url(pool, album_id).await? {
+        Some(source_url) => AlbumArt::Remote(source_url),
+        None => AlbumArt::Missing,
+    })
+}
+
+async fn load_album_art(
+    pool: &SqlitePool,
+    album_id: &str,
+    size: ArtSize,
+) -> Result<Option<AlbumArt>, ApiError> {
+    let row =
+        sqlx::query("SELECT content_type, data FROM album_art WHERE album_id = ? AND size = ?")
+            .bind(album_id)
+            .bind(size.as_str())
+            .fetch_optional(pool)
+            .await
+            .server_err()?;
+
+    Ok(row.map(|row| AlbumArt::Cached {
+        content_type: row.get("content_type"),
+        data: row.get("data"),
+    }))
+}
+
+/// The catalog's largest cover for an album, falling back to the URL stored on its
+/// meetings for albums picked before metadata was stored.
+async fn source_url(pool: &SqlitePool, album_id: &str) -> Result<Option<String>, ApiError> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(
+            (SELECT url FROM album_images WHERE album_id = ?1 ORDER BY width DESC LIMIT 1),
+            (SELECT album_art_url FROM meetings WHERE album_id = ?1 AND album_art_url != ''
+             LIMIT 1)
+         )",
+    )
+    .bind(album_id)
+    .fetch_one(pool)
+    .await
+    .server_err()
+}
+
+/// Download `source_url`, render every [`ArtSize`] and replace the stored art.
+async fn cache_album_art(
+    pool: &SqlitePool,
+    album_id: &str,
+    source_url: &str,
+) -> Result<(), ApiError> {
+    tracing::info!("Downloading album art for \"{album_id}\" from {source_url}");
+
+    let source = download(source_url).await?;
+    let rendered = tokio::task::spawn_blocking(move || render_sizes(&source))
+        .await
+        .server_err()?
+        .map_err(|e| ApiError::UpstreamUnavailable(format!("Unreadable album art: {e}")))?;
+
+    let mut tx = pool.begin().await.server_err()?;
+
+    sqlx::query("DELETE FROM album_art WHERE album_id = ?")
+        .bind(album_id)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    for art in rendered {
+        sqlx::query(
+            "INSERT INTO album_art
+                (album_id, size, width, height, content_type, data, source_url)
+             VALUES (?, ?, ?, ?, 'image/jpeg', ?, ?)",
+        )
+        .bind(album_id)
+        .bind(art.size.as_str())
+        .bind(art.width as i64)
+        .bind(art.height as i64)
+        .bind(art.data)
+        .bind(source_url)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+    }
+
+    tx.commit().await.server_err()
+}
+
+async fn download(url: &str) -> Result<Vec<u8>, ApiError> {
+    let client = HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(DOWNLOAD_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    });
+    let unavailable = |e: reqwest::Error| {
+        ApiError::UpstreamUnavailable(format!("Album art download failed: {e}"))
+    };
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(unavailable)?;
+
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOWNLOAD_BYTES as u64)
+    {
+        return Err(ApiError::UpstreamUnavailable(
+            "Album art download is too large".to_string(),
+        ));
+    }
+
+    let bytes = response.bytes().await.map_err(unavailable)?;
+    if bytes.len() > MAX_DOWNLOAD_BYTES {
+        return Err(ApiError::UpstreamUnavailable(
+            "Album art download is too large".to_string(),
+        ));
+    }
+
+    Ok(bytes.to_vec())
+}
+
+struct RenderedArt {
+    size: ArtSize,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+/// Scale the source down to every [`ArtSize`] (never up) and encode each as JPEG.
+fn render_sizes(source: &[u8]) -> Result<Vec<RenderedArt>, image::ImageError> {
+    let image = image::load_from_memory(source)?;
+
+    ArtSize::ALL
+        .into_iter()
+        .map(|size| {
+            let pixels = size.pixels();
+            let resized = if image.width() > pixels || image.height() > pixels {
+                image.resize(pixels, pixels, FilterType::Lanczos3)
+            } else {
+                image.clone()
+            };
+
+            let mut data = Vec::new();
+            JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+                .encode_image(&resized.to_rgb8())?;
+
+            Ok(RenderedArt {
+                size,
+                width: resized.width(),
+                height: resized.height(),
+                data,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,188 @@
+//! Album metadata persistence.
+
+use std::collections::HashMap;
+
+use ::spotify::{AlbumDetails, SpotifyError};
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{AlbumImage, AlbumMetadata};
+use crate::error::ApiError;
+
+use super::{get_music_catalog, IntoServerError};
+
+/// Resolve an album ID, Spotify URL or `spotify:album:` URI to the canonical album
+/// from the music catalog. Input that isn't an album reference, or an album the
+/// catalog doesn't know, is rejected.
+pub async fn resolve_album(album: &str) -> Result<AlbumDetails, ApiError> {
+    let album_id = ::spotify::parse_album_id(album)
+        .ok_or_else(|| ApiError::Validation(format!("Not an album ID or link: \"{album}\"")))?;
+
+    let catalog = get_music_catalog().await?;
+    catalog.get_album(&album_id).await.map_err(|e| {
+        tracing::warn!("Failed to resolve album \"{album_id}\": {e}");
+        match e {
+            SpotifyError::NotFound(_) => {
+                ApiError::NotFound(format!("Unknown album \"{album_id}\""))
+            }
+            other => other.into(),
+        }
+    })
+}
+
+/// The cover URL stored on a meeting: the largest image, or empty if there is none.
+pub fn album_art_url(album: &AlbumDetails) -> String {
+    album
+        .images
+        .first()
+        .map(|image| image.url.clone())
+        .unwrap_or_default()
+}
+
+/// Insert or replace the stored metadata (and cover images) for an album.
+pub async fn store_album_metadata(
+    conn: &mut SqliteConnection,
+    details: &AlbumDetails,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        "INSERT INTO albums
+            (album_id, release_date, label, genres, total_tracks, duration_ms, artist_ids, upc)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(album_id) DO UPDATE SET
+            release_date = excluded.release_date,
+            label = excluded.label,
+            genres = excluded.genres,
+            total_tracks = excluded.total_tracks,
+            duration_ms = excluded.duration_ms,
+            artist_ids = excluded.artist_ids,
+            upc = excluded.upc,
+            fetched_at = datetime('now')",
+    )
+    .bind(&details.id)
+    .bind(&details.release_date)
+    .bind(&details.label)
+    .bind(serde_json::to_string(&details.genres).server_err()?)
+    .bind(details.total_tracks as i64)
+    .bind(details.total_duration_ms as i64)
+    .bind(serde_json::to_string(&details.artist_ids).server_err()?)
+    .bind(&details.upc)
+    .execute(&mut *conn)
+    .await
+    .server_err()?;
+
+    sqlx::query("DELETE FROM album_images WHERE album_id = ?")
+        .bind(&details.id)
+        .execute(&mut *conn)
+        .await
+        .server_err()?;
+
+    for image in &details.images {
+        sqlx::query(
+            "INSERT OR IGNORE INTO album_images (album_id, url, width, height)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(&details.id)
+        .bind(&image.url)
+        .bind(image.width.map(|w| w as i64))
+        .bind(image.height.map(|h| h as i64))
+        .execute(&mut *conn)
+        .await
+        .server_err()?;
+    }
+
+    Ok(())
+}
+
+/// Load the stored metadata for a single album, if any.
+pub async fn load_album_metadata(
+    pool: &SqlitePool,
+    album_id: &str,
+) -> Result<Option<AlbumMetadata>, ApiError> {
+    let row = sqlx::query(
+        "SELECT album_id, release_date, label, genres, total_tracks, duration_ms, artist_ids, upc
+         FROM albums WHERE album_id = ?",
+    )
+    .bind(album_id)
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let image_rows = sqlx::query(
+        "SELECT album_id, url, width, height FROM album_images
+         WHERE album_id = ? ORDER BY width DESC",
+    )
+    .bind(album_id)
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(Some(metadata_from_row(
+        &row,
+        image_rows.iter().map(image_from_row).collect(),
+    )))
+}
+
+/// Load the stored metadata for every album, keyed by album ID.
+pub async fn load_all_album_metadata(
+    pool: &SqlitePool,
+) -> Result<HashMap<String, AlbumMetadata>, ApiError> {
+    let rows = sqlx::query(
+        "SELECT album_id, release_date, label, genres, total_tracks, duration_ms, artist_ids, upc
+         FROM albums",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let image_rows = sqlx::query(
+        "SELECT album_id, url, width, height FROM album_images ORDER BY album_id, width DESC",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let mut images: HashMap<String, Vec<AlbumImage>> = HashMap::new();
+    for row in &image_rows {
+        images
+            .entry(row.get("album_id"))
+            .or_default()
+            .push(image_from_row(row));
+    }
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let album_id: String = row.get("album_id");
+            let album_images = images.remove(&album_id).unwrap_or_default();
+            (album_id, metadata_from_row(row, album_images))
+        })
+        .collect())
+}
+
+fn metadata_from_row(row: &SqliteRow, images: Vec<AlbumImage>) -> AlbumMetadata {
+    let json_list = |column: &str| -> Vec<String> {
+        serde_json::from_str(&row.get::<String, _>(column)).unwrap_or_default()
+    };
+
+    AlbumMetadata {
+        release_date: row.get("release_date"),
+        label: row.get("label"),
+        genres: json_list("genres"),
+        total_tracks: row.get::<Option<i64>, _>("total_tracks").map(|n| n as u32),
+        duration_ms: row.get("duration_ms"),
+        artist_ids: json_list("artist_ids"),
+        upc: row.get("upc"),
+        images,
+    }
+}
+
+fn image_from_row(row: &SqliteRow) -> AlbumImage {
+    AlbumImage {
+        url: row.get("url"),
+        width: row.get::<Option<i64>, _>("width").map(|w| w as u32),
+        height: row.get::<Option<i64>, _>("height").map(|h| h as u32),
+    }
+}
@@ -0,0 +1,285 @@
+//! The audit log: who changed what, recorded by every mutating server function.
+
+use serde_json::Value;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{
+    ActorKind, AdminSession, AuditAction, AuditActor, AuditActorId, AuditEntry, AuditLogFilter,
+    AuditLogPage, Member, MemberId,
+};
+use crate::error::ApiError;
+
+use super::{ensure_admin, get_db, IntoServerError};
+
+const AUDIT_PAGE_SIZE: i64 = 50;
+
+/// Who is making a change, as recorded in the log.
+pub struct Actor {
+    kind: ActorKind,
+    name: String,
+    member_id: Option<MemberId>,
+    credential_id: Option<i64>,
+}
+
+impl Actor {
+    pub fn admin(session: &AdminSession) -> Self {
+        Self::admin_by(
+            session.label.clone(),
+            session.credential_id,
+            session.member_id,
+        )
+    }
+
+    /// An admin known from what was stored about their session, e.g. who started a
+    /// Spotify authorisation.
+    pub fn admin_by(
+        label: String,
+        credential_id: Option<i64>,
+        member_id: Option<MemberId>,
+    ) -> Self {
+        Self {
+            kind: ActorKind::Admin,
+            name: label,
+            member_id,
+            credential_id,
+        }
+    }
+
+    pub fn member(member: &Member) -> Self {
+        Self {
+            kind: ActorKind::Member,
+            name: member.name.to_string(),
+            member_id: Some(member.id),
+            credential_id: None,
+        }
+    }
+}
+
+/// Record a change. Call this on the transaction making the change, so the entry is
+/// only kept if the change is.
+pub async fn record_audit(
+    conn: &mut SqliteConnection,
+    actor: &Actor,
+    action: AuditAction,
+    before: Option<Value>,
+    after: Option<Value>,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        "INSERT INTO audit_log
+            (actor_kind, actor, actor_member_id, actor_credential_id, action, before_json,
+             after_json)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(actor.kind.as_str())
+    .bind(&actor.name)
+    .bind(actor.member_id)
+    .bind(actor.credential_id)
+    .bind(action.as_str())
+    .bind(before.map(|v| v.to_string()))
+    .bind(after.map(|v| v.to_string()))
+    .execute(&mut *conn)
+    .await
+    .server_err()?;
+    Ok(())
+}
+
+pub async fn admin_audit_log_impl(
+    admin_token: String,
+    filter: AuditLogFilter,
+) -> Result<AuditLogPage, ApiError> {
+    ensure_admin(&admin_token).await?;
+    let pool = get_db().await?;
+    load_audit_log(pool, &filter).await
+}
+
+async fn load_audit_log(
+    pool: &SqlitePool,
+    filter: &AuditLogFilter,
+) -> Result<AuditLogPage, ApiError> {
+    let action = filter.action.map(AuditAction::as_str);
+    let (actor_member_id, actor_credential_id) = match filter.actor {
+        Some(AuditActorId::Member(id)) => (Some(id), None),
+        Some(AuditActorId::Credential(id)) => (None, Some(id)),
+        None => (None, None),
+    };
+
+    // One row more than a page, to tell whether there is another page.
+    let mut rows = sqlx::query(
+        "SELECT id, at, actor_kind, actor, action, before_json, after_json
+         FROM audit_log
+         WHERE (? IS NULL OR action = ?)
+           AND (? IS NULL OR actor_member_id = ?)
+           AND (? IS NULL OR actor_credential_id = ?)
+           AND (? IS NULL OR id < ?)
+         ORDER BY id DESC
+         LIMIT ?",
+    )
+    .bind(action)
+    .bind(action)
+    .bind(actor_member_id)
+    .bind(actor_member_id)
+    .bind(actor_credential_id)
+    .bind(actor_credential_id)
+    .bind(filter.before_id)
+    .bind(filter.before_id)
+    .bind(AUDIT_PAGE_SIZE + 1)
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let has_more = rows.len() as i64 > AUDIT_PAGE_SIZE;
+    rows.truncate(AUDIT_PAGE_SIZE as usize);
+
+    let entries = rows
+        .into_iter()
+        .map(|row| {
+            let actor_kind: String = row.get("actor_kind");
+            let action: String = row.get("action");
+            Ok(AuditEntry {
+                id: row.get("id"),
+                at: row.get("at"),
+                actor_kind: ActorKind::parse(&actor_kind).ok_or_else(|| {
+                    ApiError::Internal(format!("Unknown audit actor kind \"{actor_kind}\""))
+                })?,
+                actor: row.get("actor"),
+                action: AuditAction::parse(&action).ok_or_else(|| {
+                    ApiError::Internal(format!("Unknown audit action \"{action}\""))
+                })?,
+                before: row.get("before_json"),
+                after: row.get("after_json"),
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    // Listed under their current name, so a renamed member appears once.
+    let actors = sqlx::query(
+        "SELECT DISTINCT a.actor_member_id, a.actor_credential_id,
+                COALESCE(m.name, c.label) AS name
+         FROM audit_log a
+         LEFT JOIN members m ON m.id = a.actor_member_id
+         LEFT JOIN admin_credentials c ON c.id = a.actor_credential_id
+         WHERE a.actor_member_id IS NOT NULL OR a.actor_credential_id IS NOT NULL
+         ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?
+    .into_iter()
+    .map(|row| {
+        let id = match row.get::<Option<MemberId>, _>("actor_member_id") {
+            Some(member_id) => AuditActorId::Member(member_id),
+            None => AuditActorId::Credential(row.get("actor_credential_id")),
+        };
+        AuditActor {
+            id,
+            name: row.get("name"),
+        }
+    })
+    .collect();
+
+    Ok(AuditLogPage {
+        entries,
+        actors,
+        has_more,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::test_pool;
+
+    async fn add_member(pool: &SqlitePool, name: &str) -> Member {
+        let id = sqlx::query_scalar("INSERT INTO members (name) VALUES (?) RETURNING id")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        Member {
+            id,
+            name: name.into(),
+        }
+    }
+
+    async fn record(pool: &SqlitePool, actor: &Actor, action: AuditAction) {
+        let mut conn = pool.acquire().await.unwrap();
+        record_audit(&mut conn, actor, action, None, Some(json!({})))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn filters_by_actor_across_renames() {
+        let pool = test_pool().await;
+        let nox = add_member(&pool, "Nox").await;
+        let karro = add_member(&pool, "Karro").await;
+
+        record(&pool, &Actor::member(&nox), AuditAction::ReviewAlbum).await;
+        record(&pool, &Actor::member(&karro), AuditAction::ReviewAlbum).await;
+        sqlx::query("UPDATE members SET name = 'Nyx' WHERE id = ?")
+            .bind(nox.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let admin = Actor::admin_by("Nyx".to_string(), None, Some(nox.id));
+        record(&pool, &admin, AuditAction::AddMember).await;
+
+        let filter = AuditLogFilter {
+            actor: Some(AuditActorId::Member(nox.id)),
+            ..Default::default()
+        };
+        let page = load_audit_log(&pool, &filter).await.unwrap();
+
+        let entries: Vec<(ActorKind, &str)> = page
+            .entries
+            .iter()
+            .map(|entry| (entry.actor_kind, entry.actor.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [(ActorKind::Admin, "Nyx"), (ActorKind::Member, "Nox")]
+        );
+        assert_eq!(
+            page.actors,
+            [
+                AuditActor {
+                    id: AuditActorId::Member(karro.id),
+                    name: "Karro".to_string(),
+                },
+                AuditActor {
+                    id: AuditActorId::Member(nox.id),
+                    name: "Nyx".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn credentials_are_filtered_separately_from_members() {
+        let pool = test_pool().await;
+        let member = add_member(&pool, "Nox").await;
+        let credential_id: i64 = sqlx::query_scalar(
+            "INSERT INTO admin_credentials (label, token_hash) VALUES ('Laptop', 'hash')
+             RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let credential = Actor::admin_by("Laptop".to_string(), Some(credential_id), None);
+        record(&pool, &credential, AuditAction::AddMember).await;
+        record(&pool, &Actor::member(&member), AuditAction::ReviewAlbum).await;
+
+        let filter = AuditLogFilter {
+            actor: Some(AuditActorId::Credential(credential_id)),
+            ..Default::default()
+        };
+        let page = load_audit_log(&pool, &filter).await.unwrap();
+
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].actor, "Laptop");
+        assert_eq!(page.entries[0].action, AuditAction::AddMember);
+    }
+}
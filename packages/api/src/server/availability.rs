@@ -0,0 +1,260 @@
+//! Date ranges when members are away and can't pick.
+
+use serde_json::{json, Value};
+use sqlx::{Row, SqliteConnection};
+
+use crate::api_models::{AuditAction, MemberId, Unavailability, MAX_UNAVAILABILITY_NOTE_LEN};
+use crate::error::ApiError;
+
+use super::{
+    audit::{record_audit, Actor},
+    ensure_admin, get_db,
+    members::ensure_active_member,
+    sessions::session_member,
+    IntoServerError,
+};
+
+/// Ranges that haven't ended yet, for members in the rotation, soonest first.
+pub async fn get_unavailability_impl() -> Result<Vec<Unavailability>, ApiError> {
+    let pool = get_db().await?;
+    let mut conn = pool.acquire().await.server_err()?;
+    upcoming_unavailability(&mut conn).await
+}
+
+/// Register a range when the logged-in member is away.
+pub async fn add_unavailability_impl(
+    session_token: String,
+    start_date: String,
+    end_date: String,
+    note: Option<String>,
+) -> Result<Vec<Unavailability>, ApiError> {
+    let pool = get_db().await?;
+    let member = session_member(pool, &session_token).await?;
+    tracing::info!("POST /api/member/unavailability member={}", member.id);
+
+    let mut tx = pool.begin().await.server_err()?;
+    add_unavailability(
+        &mut tx,
+        &Actor::member(&member),
+        member.id,
+        &start_date,
+        &end_date,
+        note,
+    )
+    .await?;
+    let ranges = upcoming_unavailability(&mut tx).await?;
+    tx.commit().await.server_err()?;
+
+    Ok(ranges)
+}
+
+/// Remove one of the logged-in member's own ranges.
+pub async fn remove_unavailability_impl(
+    session_token: String,
+    id: i64,
+) -> Result<Vec<Unavailability>, ApiError> {
+    let pool = get_db().await?;
+    let member = session_member(pool, &session_token).await?;
+    tracing::info!(
+        "POST /api/member/unavailability/remove member={} id={id}",
+        member.id
+    );
+
+    let mut tx = pool.begin().await.server_err()?;
+    remove_unavailability(&mut tx, &Actor::member(&member), id, Some(member.id)).await?;
+    let ranges = upcoming_unavailability(&mut tx).await?;
+    tx.commit().await.server_err()?;
+
+    Ok(ranges)
+}
+
+/// Register a range when any member is away.
+pub async fn admin_add_unavailability_impl(
+    admin_token: String,
+    member_id: MemberId,
+    start_date: String,
+    end_date: String,
+    note: Option<String>,
+) -> Result<Vec<Unavailability>, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/unavailability member={member_id}");
+
+    let pool = get_db().await?;
+    ensure_active_member(pool, member_id).await?;
+
+    let mut tx = pool.begin().await.server_err()?;
+    add_unavailability(
+        &mut tx,
+        &Actor::admin(&admin),
+        member_id,
+        &start_date,
+        &end_date,
+        note,
+    )
+    .await?;
+    let ranges = upcoming_unavailability(&mut tx).await?;
+    tx.commit().await.server_err()?;
+
+    Ok(ranges)
+}
+
+pub async fn admin_remove_unavailability_impl(
+    admin_token: String,
+    id: i64,
+) -> Result<Vec<Unavailability>, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/unavailability/remove id={id}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+    remove_unavailability(&mut tx, &Actor::admin(&admin), id, None).await?;
+    let ranges = upcoming_unavailability(&mut tx).await?;
+    tx.commit().await.server_err()?;
+
+    Ok(ranges)
+}
+
+async fn add_unavailability(
+    conn: &mut SqliteConnection,
+    actor: &Actor,
+    member_id: MemberId,
+    start_date: &str,
+    end_date: &str,
+    note: Option<String>,
+) -> Result<(), ApiError> {
+    validate_date_range(conn, start_date, end_date).await?;
+    let note = normalize_note(note)?;
+
+    let id: i64 = sqlx::query_scalar(
+        "INSERT INTO member_unavailability (member_id, start_date, end_date, note)
+         VALUES (?, ?, ?, ?)
+         RETURNING id",
+    )
+    .bind(member_id)
+    .bind(start_date)
+    .bind(end_date)
+    .bind(&note)
+    .fetch_one(&mut *conn)
+    .await
+    .server_err()?;
+
+    let after = unavailability_snapshot(conn, id).await?;
+    record_audit(conn, actor, AuditAction::AddUnavailability, None, after).await
+}
+
+/// Remove a range, only if it belongs to `owner` when given.
+async fn remove_unavailability(
+    conn: &mut SqliteConnection,
+    actor: &Actor,
+    id: i64,
+    owner: Option<MemberId>,
+) -> Result<(), ApiError> {
+    let before = unavailability_snapshot(conn, id).await?;
+
+    let rows_affected = sqlx::query(
+        "DELETE FROM member_unavailability WHERE id = ? AND (? IS NULL OR member_id = ?)",
+    )
+    .bind(id)
+    .bind(owner)
+    .bind(owner)
+    .execute(&mut *conn)
+    .await
+    .server_err()?
+    .rows_affected();
+
+    if rows_affected == 0 {
+        return Err(ApiError::NotFound(format!("Unavailability {id} not found")));
+    }
+
+    record_audit(conn, actor, AuditAction::RemoveUnavailability, before, None).await
+}
+
+async fn upcoming_unavailability(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<Unavailability>, ApiError> {
+    let rows = sqlx::query(
+        "SELECT u.id, u.member_id, m.name, u.start_date, u.end_date, u.note
+         FROM member_unavailability u
+         JOIN members m ON m.id = u.member_id
+         WHERE u.end_date >= date('now', 'localtime') AND m.deleted_at IS NULL
+         ORDER BY u.start_date, m.sort_order",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Unavailability {
+            id: row.get("id"),
+            member_id: row.get("member_id"),
+            member_name: row.get("name"),
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            note: row.get("note"),
+        })
+        .collect())
+}
+
+/// Both dates must be real `YYYY-MM-DD` dates, and the range can't end before it starts.
+async fn validate_date_range(
+    conn: &mut SqliteConnection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<(), ApiError> {
+    let valid: bool = sqlx::query_scalar("SELECT date(?1) IS ?1 AND date(?2) IS ?2")
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_one(&mut *conn)
+        .await
+        .server_err()?;
+
+    if !valid {
+        return Err(ApiError::Validation(
+            "Dates must be given as YYYY-MM-DD".to_string(),
+        ));
+    }
+    if end_date < start_date {
+        return Err(ApiError::Validation(
+            "The range can't end before it starts".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn normalize_note(note: Option<String>) -> Result<Option<String>, ApiError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_UNAVAILABILITY_NOTE_LEN)
+    {
+        return Err(ApiError::Validation(format!(
+            "The note can be at most {MAX_UNAVAILABILITY_NOTE_LEN} characters"
+        )));
+    }
+    Ok(note)
+}
+
+/// A range as recorded in the audit log, or `None` if it doesn't exist.
+async fn unavailability_snapshot(
+    conn: &mut SqliteConnection,
+    id: i64,
+) -> Result<Option<Value>, ApiError> {
+    let row = sqlx::query(
+        "SELECT member_id, start_date, end_date, note FROM member_unavailability WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await
+    .server_err()?;
+
+    Ok(row.map(|row| {
+        json!({
+            "id": id,
+            "member_id": row.get::<MemberId, _>("member_id"),
+            "start_date": row.get::<String, _>("start_date"),
+            "end_date": row.get::<String, _>("end_date"),
+            "note": row.get::<Option<String>, _>("note"),
+        })
+    }))
+}
@@ -0,0 +1,160 @@
+//! Single-use invite links for members to set their own password.
+
+use serde_json::json;
+use sqlx::{Row, SqlitePool};
+
+use crate::api_models::{AuditAction, Member, MemberId, MemberInvite, MemberSession};
+use crate::error::ApiError;
+
+use super::{
+    audit::{record_audit, Actor},
+    ensure_admin, get_db,
+    members::{set_member_password, validate_password},
+    sessions::{hash_token, new_session_token, start_member_session},
+    IntoServerError,
+};
+
+/// How long an invite link can be used, as an SQLite `datetime` modifier.
+const INVITE_TTL: &str = "+7 days";
+
+/// Create an invite link token for a member. Earlier unused links for the member stop
+/// working, so only the latest link handed out is valid.
+pub async fn admin_create_member_invite_impl(
+    admin_token: String,
+    member_id: MemberId,
+) -> Result<MemberInvite, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/member/invite member={member_id}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let name: Option<String> =
+        sqlx::query_scalar("SELECT name FROM members WHERE id = ? AND deleted_at IS NULL")
+            .bind(member_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .server_err()?;
+    let name = name.ok_or_else(|| ApiError::NotFound(format!("Member {member_id} not found")))?;
+
+    sqlx::query(
+        "DELETE FROM member_invites
+         WHERE member_id = ? OR expires_at <= datetime('now') OR used_at IS NOT NULL",
+    )
+    .bind(member_id)
+    .execute(&mut *tx)
+    .await
+    .server_err()?;
+
+    let token = new_session_token();
+
+    let expires_at: String = sqlx::query_scalar(
+        "INSERT INTO member_invites (token_hash, member_id, expires_at)
+         VALUES (?, ?, datetime('now', ?))
+         RETURNING expires_at",
+    )
+    .bind(hash_token(&token))
+    .bind(member_id)
+    .bind(INVITE_TTL)
+    .fetch_one(&mut *tx)
+    .await
+    .server_err()?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::CreateMemberInvite,
+        None,
+        Some(json!({ "member_id": member_id, "name": name, "expires_at": expires_at })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/invite {member_id} → ok");
+    Ok(MemberInvite {
+        token,
+        member: Member {
+            id: member_id,
+            name: name.into(),
+        },
+        expires_at,
+    })
+}
+
+/// The member an invite link is for, so the page can greet them before they pick a password.
+pub async fn member_invite_impl(invite_token: String) -> Result<Member, ApiError> {
+    let pool = get_db().await?;
+    invited_member(pool, &invite_token).await
+}
+
+/// Set the invited member's password and log them in. The link can't be used again.
+pub async fn redeem_member_invite_impl(
+    invite_token: String,
+    new_password: String,
+) -> Result<MemberSession, ApiError> {
+    let pool = get_db().await?;
+    let member = invited_member(pool, &invite_token).await?;
+    tracing::info!("POST /api/member/invite/redeem member={}", member.id);
+    validate_password(&new_password)?;
+
+    let mut tx = pool.begin().await.server_err()?;
+
+    // Claim the link before using it, so two requests racing with the same link can't
+    // both set a password.
+    let claimed = sqlx::query(
+        "UPDATE member_invites SET used_at = datetime('now')
+         WHERE token_hash = ? AND used_at IS NULL",
+    )
+    .bind(hash_token(&invite_token))
+    .execute(&mut *tx)
+    .await
+    .server_err()?
+    .rows_affected();
+
+    if claimed == 0 {
+        return Err(invalid_invite());
+    }
+
+    set_member_password(&mut tx, member.id, &new_password).await?;
+    record_audit(
+        &mut tx,
+        &Actor::member(&member),
+        AuditAction::RedeemInvite,
+        None,
+        Some(json!({ "member_id": member.id })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/member/invite/redeem {} → ok", member.id);
+    start_member_session(pool, member.id).await
+}
+
+async fn invited_member(pool: &SqlitePool, invite_token: &str) -> Result<Member, ApiError> {
+    let row = sqlx::query(
+        "SELECT m.id, m.name
+         FROM member_invites i
+         JOIN members m ON m.id = i.member_id
+         WHERE i.token_hash = ?
+           AND i.used_at IS NULL
+           AND i.expires_at > datetime('now')
+           AND m.deleted_at IS NULL",
+    )
+    .bind(hash_token(invite_token))
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+
+    let row = row.ok_or_else(invalid_invite)?;
+
+    Ok(Member {
+        id: row.get("id"),
+        name: row.get::<String, _>("name").into(),
+    })
+}
+
+fn invalid_invite() -> ApiError {
+    ApiError::NotFound("Invite link is invalid, already used or expired".to_string())
+}
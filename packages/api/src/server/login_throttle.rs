@@ -0,0 +1,288 @@
+//! Throttling of failed logins per member and per client IP, with temporary lockouts.
+//!
+//! Failures are counted in memory, so a restart forgets them. That is enough to make
+//! guessing passwords and admin tokens impractically slow.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use dioxus::fullstack::{extract::ConnectInfo, FullstackContext, HeaderMap};
+
+use crate::api_models::MemberId;
+use crate::error::ApiError;
+
+/// Failures older than this are forgotten.
+const FAILURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+const LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Failed logins as one member, from anywhere, before the member is locked out.
+const MAX_MEMBER_FAILURES: u32 = 5;
+/// Failed logins from one IP, for any member or admin token, before the IP is locked out.
+const MAX_IP_FAILURES: u32 = 20;
+
+/// Names the header a reverse proxy puts the client's IP in, e.g. `X-Real-IP`.
+const CLIENT_IP_HEADER_ENV: &str = "CLIENT_IP_HEADER";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ThrottleKey {
+    Member(MemberId),
+    Ip(String),
+}
+
+impl ThrottleKey {
+    fn max_failures(&self) -> u32 {
+        match self {
+            Self::Member(_) => MAX_MEMBER_FAILURES,
+            Self::Ip(_) => MAX_IP_FAILURES,
+        }
+    }
+}
+
+impl std::fmt::Display for ThrottleKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member(id) => write!(f, "member {id}"),
+            Self::Ip(ip) => write!(f, "IP {ip}"),
+        }
+    }
+}
+
+struct Failures {
+    count: u32,
+    first_at: Instant,
+    locked_until: Option<Instant>,
+}
+
+static FAILURES: LazyLock<Mutex<HashMap<ThrottleKey, Failures>>> = LazyLock::new(Default::default);
+
+/// The keys a login attempt in the current request is counted under: the client's IP,
+/// and the member when logging in as one.
+pub async fn login_keys(member_id: Option<MemberId>) -> Vec<ThrottleKey> {
+    // Without a known IP every client shares one budget, which is stricter, not looser.
+    let ip = client_ip().await.unwrap_or_else(|| "unknown".to_string());
+    let mut keys = vec![ThrottleKey::Ip(ip)];
+    keys.extend(member_id.map(ThrottleKey::Member));
+    keys
+}
+
+/// Count a login attempt as failed before the password is checked, or refuse it outright
+/// while any of its keys is locked out or has no attempts left. Counting up front means
+/// parallel guesses can't all get past the limit while the first ones are still being
+/// checked; a successful attempt is taken back by [`record_success`].
+pub fn reserve_attempt(keys: &[ThrottleKey]) -> Result<(), ApiError> {
+    let mut failures = FAILURES.lock().unwrap_or_else(PoisonError::into_inner);
+    reserve(&mut failures, keys, Instant::now())
+}
+
+/// A successful login takes back its reserved attempt and clears the member's failures.
+/// The IP's other failures are kept, so logging in to one account doesn't buy more
+/// guesses at others.
+pub fn record_success(keys: &[ThrottleKey]) {
+    let mut failures = FAILURES.lock().unwrap_or_else(PoisonError::into_inner);
+    take_back(&mut failures, keys);
+}
+
+fn reserve(
+    failures: &mut HashMap<ThrottleKey, Failures>,
+    keys: &[ThrottleKey],
+    now: Instant,
+) -> Result<(), ApiError> {
+    failures.retain(|_, f| {
+        f.locked_until.is_some_and(|until| until > now)
+            || now.duration_since(f.first_at) < FAILURE_WINDOW
+    });
+
+    for key in keys {
+        let Some(f) = failures.get_mut(key) else {
+            continue;
+        };
+        let is_locked = f.locked_until.is_some_and(|until| until > now);
+        if !is_locked && f.count >= key.max_failures() {
+            tracing::warn!(
+                "Login lockout: {key} locked for {} min after {} failed attempts",
+                LOCKOUT.as_secs() / 60,
+                f.count
+            );
+            *f = Failures {
+                count: 0,
+                first_at: now,
+                locked_until: Some(now + LOCKOUT),
+            };
+        }
+    }
+
+    let locked_until = keys
+        .iter()
+        .filter_map(|key| failures.get(key)?.locked_until)
+        .filter(|until| *until > now)
+        .max();
+    if let Some(until) = locked_until {
+        return Err(ApiError::LockedOut {
+            retry_after_secs: (until - now).as_secs().max(1),
+        });
+    }
+
+    for key in keys {
+        failures
+            .entry(key.clone())
+            .or_insert(Failures {
+                count: 0,
+                first_at: now,
+                locked_until: None,
+            })
+            .count += 1;
+    }
+    Ok(())
+}
+
+fn take_back(failures: &mut HashMap<ThrottleKey, Failures>, keys: &[ThrottleKey]) {
+    for key in keys {
+        match key {
+            ThrottleKey::Member(_) => {
+                failures.remove(key);
+            }
+            ThrottleKey::Ip(_) => {
+                if let Some(f) = failures.get_mut(key) {
+                    f.count = f.count.saturating_sub(1);
+                }
+            }
+        }
+    }
+}
+
+async fn client_ip() -> Option<String> {
+    if let Ok(header) = std::env::var(CLIENT_IP_HEADER_ENV) {
+        let headers = FullstackContext::extract::<HeaderMap, _>().await.ok()?;
+        // Proxies append to `X-Forwarded-For`, so only the last entry is trustworthy.
+        let ip = headers
+            .get(header.as_str())?
+            .to_str()
+            .ok()?
+            .rsplit(',')
+            .next()?;
+        return Some(ip.trim().to_string());
+    }
+
+    let ConnectInfo(addr) = FullstackContext::current()?.extension::<ConnectInfo<SocketAddr>>()?;
+    Some(addr.ip().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(ip: &str) -> ThrottleKey {
+        ThrottleKey::Ip(ip.to_string())
+    }
+
+    fn count(failures: &HashMap<ThrottleKey, Failures>, key: &ThrottleKey) -> Option<u32> {
+        failures.get(key).map(|f| f.count)
+    }
+
+    #[test]
+    fn member_is_locked_out_after_max_failures_from_any_ip() {
+        let mut failures = HashMap::new();
+        let now = Instant::now();
+        let member = ThrottleKey::Member(1);
+
+        for _ in 0..MAX_MEMBER_FAILURES {
+            reserve(&mut failures, &[ip("10.0.0.1"), member.clone()], now).unwrap();
+        }
+
+        let locked = reserve(&mut failures, &[ip("10.0.0.2"), member.clone()], now);
+        assert!(matches!(
+            locked,
+            Err(ApiError::LockedOut { retry_after_secs }) if retry_after_secs == LOCKOUT.as_secs()
+        ));
+        // Refused attempts aren't counted against the other keys.
+        assert_eq!(count(&failures, &ip("10.0.0.2")), None);
+        assert!(reserve(
+            &mut failures,
+            &[ip("10.0.0.2"), ThrottleKey::Member(2)],
+            now
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn ip_is_locked_out_across_members() {
+        let mut failures = HashMap::new();
+        let now = Instant::now();
+
+        for member_id in 0..MAX_IP_FAILURES {
+            let keys = [ip("10.0.0.1"), ThrottleKey::Member(member_id as MemberId)];
+            reserve(&mut failures, &keys, now).unwrap();
+        }
+
+        assert!(matches!(
+            reserve(
+                &mut failures,
+                &[ip("10.0.0.1"), ThrottleKey::Member(100)],
+                now
+            ),
+            Err(ApiError::LockedOut { .. })
+        ));
+    }
+
+    #[test]
+    fn lockouts_and_failures_expire() {
+        let mut failures = HashMap::new();
+        let start = Instant::now();
+        let keys = [ip("10.0.0.1"), ThrottleKey::Member(1)];
+
+        for _ in 0..MAX_MEMBER_FAILURES {
+            reserve(&mut failures, &keys, start).unwrap();
+        }
+        assert!(reserve(&mut failures, &keys, start).is_err());
+        assert!(reserve(
+            &mut failures,
+            &keys,
+            start + LOCKOUT - Duration::from_secs(1)
+        )
+        .is_err());
+
+        // After the lockout the member starts over with a full budget.
+        let after_lockout = start + LOCKOUT;
+        for _ in 0..MAX_MEMBER_FAILURES {
+            reserve(&mut failures, &keys, after_lockout).unwrap();
+        }
+        assert!(reserve(&mut failures, &keys, after_lockout).is_err());
+
+        // Failures outside the window are forgotten rather than adding up.
+        let mut failures = HashMap::new();
+        for _ in 0..MAX_MEMBER_FAILURES - 1 {
+            reserve(&mut failures, &keys, start).unwrap();
+        }
+        let later = start + FAILURE_WINDOW;
+        for _ in 0..MAX_MEMBER_FAILURES {
+            reserve(&mut failures, &keys, later).unwrap();
+        }
+        assert_eq!(count(&failures, &keys[1]), Some(MAX_MEMBER_FAILURES));
+    }
+
+    #[test]
+    fn success_clears_the_member_but_only_takes_back_one_ip_attempt() {
+        let mut failures = HashMap::new();
+        let now = Instant::now();
+        let keys = [ip("10.0.0.1"), ThrottleKey::Member(1)];
+
+        for _ in 0..3 {
+            reserve(&mut failures, &keys, now).unwrap();
+        }
+        take_back(&mut failures, &keys);
+
+        assert_eq!(count(&failures, &keys[0]), Some(2));
+        assert_eq!(count(&failures, &keys[1]), None);
+
+        // Admin token logins have no member key, and taking back never goes below zero.
+        let admin = [ip("10.0.0.2")];
+        take_back(&mut failures, &admin);
+        assert_eq!(count(&failures, &admin[0]), None);
+        reserve(&mut failures, &admin, now).unwrap();
+        take_back(&mut failures, &admin);
+        take_back(&mut failures, &admin);
+        assert_eq!(count(&failures, &admin[0]), Some(0));
+    }
+}
@@ -1,102 +1,200 @@
 //! Meeting-related server function implementations.
 
-use dioxus::prelude::ServerFnError;
-use sqlx::Row;
+use serde_json::{json, Value};
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
 use uuid::Uuid;
 
-use crate::api_models::{Data, HistoryEntry, SetCurrentRequest};
-
-use super::{ensure_admin_token, get_db, IntoServerError};
-
-pub async fn get_current_impl() -> Result<Data, ServerFnError> {
+use crate::api_models::{
+    Album, AuditAction, Data, HistoryEntry, Meeting, MeetingDetails, Member, MemberId,
+    RevealPolicy, SetCurrentRequest,
+};
+use crate::error::ApiError;
+
+use super::album_art::{ensure_album_art, served_album_art};
+use super::albums::{
+    album_art_url, load_album_metadata, load_all_album_metadata, resolve_album,
+    store_album_metadata,
+};
+use super::audit::{record_audit, Actor};
+use super::members::{active_members, ensure_active_member};
+use super::rotation::{
+    next_meeting_date, next_picker, record_skips, unavailable_on, upcoming_rotation,
+};
+use super::spotify::cache_album_tracks;
+use super::{ensure_admin, get_db, IntoServerError};
+
+const MAX_REVIEW_CLOSE_DAYS: u32 = 365;
+
+/// The columns [`meeting_from_row`] reads, from `meetings m JOIN members p`.
+const MEETING_COLUMNS: &str = "m.id, m.is_current, m.album_id, m.album_name, m.album_artist,
+    m.album_art_url, m.album_spotify_url, m.picker_id, p.name AS picker, m.meeting_date,
+    m.meeting_time, m.meeting_location, m.reveal_policy, m.reviews_revealed_at,
+    m.review_close_days, m.reviews_locked_at";
+
+pub async fn get_current_impl() -> Result<Data, ApiError> {
     tracing::debug!("GET /api/info");
 
     let pool = get_db().await?;
 
-    let members: Vec<String> =
-        sqlx::query_scalar("SELECT name FROM members WHERE deleted_at IS NULL ORDER BY sort_order")
-            .fetch_all(pool)
-            .await
-            .server_err()?;
-
-    let row = sqlx::query(
-        "SELECT id, album_id, album_name, album_artist, album_art_url, album_spotify_url,
-                picker, meeting_date, meeting_time, meeting_location
-         FROM meetings WHERE is_current = 1",
-    )
+    let (members, rotation, unavailable) = {
+        let mut conn = pool.acquire().await.server_err()?;
+        let next_date = next_meeting_date(&mut conn).await?;
+        (
+            active_members(&mut conn).await?,
+            upcoming_rotation(&mut conn).await?,
+            unavailable_on(&mut conn, &next_date).await?,
+        )
+    };
+    let next_person = next_picker(&rotation, &unavailable);
+
+    let row = sqlx::query(&format!(
+        "SELECT {MEETING_COLUMNS}
+         FROM meetings m
+         JOIN members p ON p.id = m.picker_id
+         WHERE m.is_current = 1"
+    ))
     .fetch_optional(pool)
     .await
     .server_err()?;
 
-    let member_names: Vec<crate::api_models::Name> =
-        members.into_iter().map(|m| m.into()).collect();
-
     match row {
         None => Ok(Data {
             current_meeting_id: None,
             current_album: None,
             next_meeting: None,
             current_person: None,
-            members: member_names,
+            next_person,
+            members,
+            rotation,
+            unavailable,
         }),
         Some(row) => {
-            let meeting_date: String = row.get("meeting_date");
-            let meeting_time: Option<String> = row.get("meeting_time");
-            let meeting_location: Option<String> = row.get("meeting_location");
-
-            let next_meeting = Some(crate::api_models::Meeting {
-                date: meeting_date,
-                time: meeting_time,
-                location: meeting_location,
-            });
+            let (album, picker, meeting) = meeting_from_row(pool, &row).await?;
 
             Ok(Data {
                 current_meeting_id: Some(row.get("id")),
-                current_album: Some(crate::api_models::Album {
-                    id: row.get("album_id"),
-                    name: row.get("album_name"),
-                    artist: row.get("album_artist"),
-                    album_art: row.get("album_art_url"),
-                    spotify_url: row.get("album_spotify_url"),
-                }),
-                next_meeting,
-                current_person: Some(row.get::<String, _>("picker").into()),
-                members: member_names,
+                current_album: Some(album),
+                next_meeting: Some(meeting),
+                current_person: Some(picker),
+                next_person,
+                members,
+                rotation,
+                unavailable,
             })
         }
     }
 }
 
-pub async fn get_history_impl() -> Result<Vec<HistoryEntry>, ServerFnError> {
+/// Any meeting, current or past, for its own review page.
+pub async fn get_meeting_impl(meeting_id: String) -> Result<MeetingDetails, ApiError> {
+    tracing::debug!("get_meeting id=\"{meeting_id}\"");
+
+    let pool = get_db().await?;
+
+    let row = sqlx::query(&format!(
+        "SELECT {MEETING_COLUMNS}
+         FROM meetings m
+         JOIN members p ON p.id = m.picker_id
+         WHERE m.id = ?"
+    ))
+    .bind(&meeting_id)
+    .fetch_optional(pool)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("Meeting {meeting_id} not found")))?;
+
+    let (album, picker, meeting) = meeting_from_row(pool, &row).await?;
+    let mut conn = pool.acquire().await.server_err()?;
+    let members = active_members(&mut conn).await?;
+
+    Ok(MeetingDetails {
+        id: meeting_id,
+        is_current: row.get("is_current"),
+        album,
+        picker,
+        meeting,
+        members,
+    })
+}
+
+async fn meeting_from_row(
+    pool: &SqlitePool,
+    row: &SqliteRow,
+) -> Result<(Album, Member, Meeting), ApiError> {
+    let reviews_revealed_at: Option<String> = row.get("reviews_revealed_at");
+    let reviews_locked_at: Option<String> = row.get("reviews_locked_at");
+
+    let meeting = Meeting {
+        date: row.get("meeting_date"),
+        time: row.get("meeting_time"),
+        location: row.get("meeting_location"),
+        reveal_policy: RevealPolicy::parse(row.get("reveal_policy")).unwrap_or_default(),
+        reviews_revealed: reviews_revealed_at.is_some(),
+        review_close_days: row.get::<i64, _>("review_close_days") as u32,
+        reviews_locked: reviews_locked_at.is_some(),
+    };
+
+    let album_id: String = row.get("album_id");
+    let metadata = load_album_metadata(pool, &album_id).await?;
+    let album_art = served_album_art(&album_id, row.get("album_art_url"));
+
+    let album = Album {
+        id: album_id,
+        name: row.get("album_name"),
+        artist: row.get("album_artist"),
+        album_art,
+        spotify_url: row.get("album_spotify_url"),
+        metadata,
+    };
+
+    let picker = Member {
+        id: row.get("picker_id"),
+        name: row.get::<String, _>("picker").into(),
+    };
+
+    Ok((album, picker, meeting))
+}
+
+pub async fn get_history_impl() -> Result<Vec<HistoryEntry>, ApiError> {
     tracing::debug!("GET /api/history");
 
     let pool = get_db().await?;
 
     let rows = sqlx::query(
-        "SELECT id, album_name, album_artist, album_art_url, album_spotify_url, picker, recorded_at,
-                meeting_date, meeting_time, meeting_location
-         FROM meetings
-         WHERE is_current = 0
-         ORDER BY meeting_date ASC",
+        "SELECT m.id, m.album_id, m.album_name, m.album_artist, m.album_art_url,
+                m.album_spotify_url, m.picker_id, p.name AS picker, m.recorded_at,
+                m.meeting_date, m.meeting_time, m.meeting_location
+         FROM meetings m
+         JOIN members p ON p.id = m.picker_id
+         WHERE m.is_current = 0
+         ORDER BY m.meeting_date ASC",
     )
     .fetch_all(pool)
     .await
     .server_err()?;
 
+    let metadata = load_all_album_metadata(pool).await?;
+
     tracing::debug!("GET /api/history → {} entries", rows.len());
     Ok(rows
         .into_iter()
-        .map(|row| HistoryEntry {
-            id: row.get("id"),
-            album_name: row.get("album_name"),
-            album_artist: row.get("album_artist"),
-            album_art: row.get("album_art_url"),
-            spotify_url: row.get("album_spotify_url"),
-            picker: row.get("picker"),
-            recorded_at: row.get("recorded_at"),
-            meeting_date: row.get("meeting_date"),
-            meeting_time: row.get("meeting_time"),
-            meeting_location: row.get("meeting_location"),
+        .map(|row| {
+            let album_id: String = row.get("album_id");
+            HistoryEntry {
+                id: row.get("id"),
+                album_name: row.get("album_name"),
+                album_artist: row.get("album_artist"),
+                album_art: served_album_art(&album_id, row.get("album_art_url")),
+                spotify_url: row.get("album_spotify_url"),
+                picker_id: row.get("picker_id"),
+                picker: row.get("picker"),
+                recorded_at: row.get("recorded_at"),
+                meeting_date: row.get("meeting_date"),
+                meeting_time: row.get("meeting_time"),
+                meeting_location: row.get("meeting_location"),
+                album_metadata: metadata.get(&album_id).cloned(),
+                album_id,
+            }
         })
         .collect())
 }
@@ -104,45 +202,72 @@ pub async fn get_history_impl() -> Result<Vec<HistoryEntry>, ServerFnError> {
 pub async fn admin_set_current_impl(
     admin_token: String,
     req: SetCurrentRequest,
-) -> Result<(), ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
     tracing::info!(
-        "POST /api/admin/set-current album=\"{}\" picker=\"{}\" date=\"{}\"",
-        req.album_name,
-        req.picker,
-        req.meeting_date
+        "POST /api/admin/set-current album=\"{}\" picker={} date=\"{}\" reveal={}",
+        req.album,
+        req.picker_id,
+        req.meeting_date,
+        req.reveal_policy.as_str()
     );
+    validate_review_close_days(req.review_close_days)?;
+
+    let album = resolve_album(&req.album).await?;
 
     let pool = get_db().await?;
+    ensure_active_member(pool, req.picker_id).await?;
     let mut tx = pool.begin().await.server_err()?;
 
+    store_album_metadata(&mut tx, &album).await?;
+    cache_album_tracks(&mut tx, &album.id, &album.tracks).await?;
+
     sqlx::query("UPDATE meetings SET is_current = 0 WHERE is_current = 1")
         .execute(&mut *tx)
         .await
         .server_err()?;
 
+    let meeting_id = Uuid::new_v4().to_string();
+
     sqlx::query(
         "INSERT INTO meetings
             (id, is_current, album_id, album_name, album_artist, album_art_url,
-             album_spotify_url, picker, meeting_date, meeting_time, meeting_location)
-         VALUES (?, 1, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+             album_spotify_url, picker_id, meeting_date, meeting_time, meeting_location,
+             reveal_policy, review_close_days)
+         VALUES (?, 1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
-    .bind(Uuid::new_v4().to_string())
-    .bind(req.album_id)
-    .bind(req.album_name)
-    .bind(req.album_artist)
-    .bind(req.album_art_url)
-    .bind(req.album_spotify_url)
-    .bind(req.picker)
-    .bind(req.meeting_date)
+    .bind(&meeting_id)
+    .bind(&album.id)
+    .bind(&album.name)
+    .bind(&album.artists)
+    .bind(album_art_url(&album))
+    .bind(&album.spotify_url)
+    .bind(req.picker_id)
+    .bind(&req.meeting_date)
     .bind(req.meeting_time)
     .bind(req.meeting_location)
+    .bind(req.reveal_policy.as_str())
+    .bind(req.review_close_days as i64)
     .execute(&mut *tx)
     .await
     .server_err()?;
 
+    record_skips(&mut tx, &meeting_id, req.picker_id, &req.meeting_date).await?;
+
+    let after = meeting_snapshot(&mut tx, &meeting_id).await?;
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::SetCurrent,
+        None,
+        after,
+    )
+    .await?;
+
     tx.commit().await.server_err()?;
 
+    cache_picked_album_art(pool, &album.id, &album_art_url(&album)).await;
+
     tracing::info!("POST /api/admin/set-current → ok");
     Ok(())
 }
@@ -150,55 +275,117 @@ pub async fn admin_set_current_impl(
 pub async fn admin_update_current_impl(
     admin_token: String,
     req: SetCurrentRequest,
-) -> Result<(), ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
     tracing::info!(
-        "POST /api/admin/update-current album=\"{}\" picker=\"{}\" date=\"{}\"",
-        req.album_name,
-        req.picker,
+        "POST /api/admin/update-current album=\"{}\" picker={} date=\"{}\"",
+        req.album,
+        req.picker_id,
         req.meeting_date
     );
+    validate_review_close_days(req.review_close_days)?;
+
+    let album = resolve_album(&req.album).await?;
 
     let pool = get_db().await?;
+    ensure_active_member(pool, req.picker_id).await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let meeting_id: String = sqlx::query_scalar("SELECT id FROM meetings WHERE is_current = 1")
+        .fetch_optional(&mut *tx)
+        .await
+        .server_err()?
+        .ok_or_else(|| ApiError::NotFound("No current meeting to update".to_string()))?;
+    let before = meeting_snapshot(&mut tx, &meeting_id).await?;
+
+    store_album_metadata(&mut tx, &album).await?;
+    cache_album_tracks(&mut tx, &album.id, &album.tracks).await?;
 
     sqlx::query(
         "UPDATE meetings
          SET album_id = ?, album_name = ?, album_artist = ?, album_art_url = ?,
-             album_spotify_url = ?, picker = ?, meeting_date = ?, meeting_time = ?,
-             meeting_location = ?
-         WHERE is_current = 1",
+             album_spotify_url = ?, picker_id = ?, meeting_date = ?, meeting_time = ?,
+             meeting_location = ?, reveal_policy = ?, review_close_days = ?
+         WHERE id = ?",
     )
-    .bind(req.album_id)
-    .bind(req.album_name)
-    .bind(req.album_artist)
-    .bind(req.album_art_url)
-    .bind(req.album_spotify_url)
-    .bind(req.picker)
-    .bind(req.meeting_date)
+    .bind(&album.id)
+    .bind(&album.name)
+    .bind(&album.artists)
+    .bind(album_art_url(&album))
+    .bind(&album.spotify_url)
+    .bind(req.picker_id)
+    .bind(&req.meeting_date)
     .bind(req.meeting_time)
     .bind(req.meeting_location)
-    .execute(pool)
+    .bind(req.reveal_policy.as_str())
+    .bind(req.review_close_days as i64)
+    .bind(&meeting_id)
+    .execute(&mut *tx)
     .await
     .server_err()?;
 
+    record_skips(&mut tx, &meeting_id, req.picker_id, &req.meeting_date).await?;
+
+    let after = meeting_snapshot(&mut tx, &meeting_id).await?;
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::UpdateCurrent,
+        before,
+        after,
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    cache_picked_album_art(pool, &album.id, &album_art_url(&album)).await;
+
     tracing::info!("POST /api/admin/update-current → ok");
     Ok(())
 }
 
+/// Download the art for a newly picked album. A failure doesn't fail the pick: the art
+/// route retries on the first request and falls back to the catalog's URL.
+async fn cache_picked_album_art(pool: &sqlx::SqlitePool, album_id: &str, source_url: &str) {
+    if source_url.is_empty() {
+        return;
+    }
+    if let Err(e) = ensure_album_art(pool, album_id, source_url).await {
+        tracing::warn!("Failed to cache album art for \"{album_id}\": {e}");
+    }
+}
+
 pub async fn admin_delete_history_entry_impl(
     admin_token: String,
     id: String,
-) -> Result<(), ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
     tracing::info!("POST /api/admin/history/delete id=\"{id}\"");
 
     let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let before = meeting_snapshot(&mut tx, &id).await?;
 
-    sqlx::query("DELETE FROM meetings WHERE id = ? AND is_current = 0")
+    let rows_affected = sqlx::query("DELETE FROM meetings WHERE id = ? AND is_current = 0")
         .bind(&id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
-        .server_err()?;
+        .server_err()?
+        .rows_affected();
+
+    if rows_affected > 0 {
+        record_audit(
+            &mut tx,
+            &Actor::admin(&admin),
+            AuditAction::DeleteHistoryEntry,
+            before,
+            None,
+        )
+        .await?;
+    }
+
+    tx.commit().await.server_err()?;
 
     tracing::info!("POST /api/admin/history/delete id=\"{id}\" → ok");
     Ok(())
@@ -206,28 +393,96 @@ pub async fn admin_delete_history_entry_impl(
 
 pub async fn admin_reorder_members_impl(
     admin_token: String,
-    ordered_names: Vec<String>,
-) -> Result<(), ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+    ordered_ids: Vec<MemberId>,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
     tracing::info!(
         "POST /api/admin/reorder-members {} members",
-        ordered_names.len()
+        ordered_ids.len()
     );
 
     let pool = get_db().await?;
     let mut tx = pool.begin().await.server_err()?;
 
-    for (i, name) in ordered_names.iter().enumerate() {
-        sqlx::query("UPDATE members SET sort_order = ? WHERE name = ?")
+    let before: Vec<MemberId> = active_members(&mut tx)
+        .await?
+        .iter()
+        .map(|member| member.id)
+        .collect();
+
+    for (i, id) in ordered_ids.iter().enumerate() {
+        sqlx::query("UPDATE members SET sort_order = ? WHERE id = ?")
             .bind(i as i64)
-            .bind(name)
+            .bind(id)
             .execute(&mut *tx)
             .await
             .server_err()?;
     }
 
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::ReorderMembers,
+        Some(json!(before)),
+        Some(json!(ordered_ids)),
+    )
+    .await?;
+
     tx.commit().await.server_err()?;
 
     tracing::info!("POST /api/admin/reorder-members → ok");
     Ok(())
 }
+
+/// Reviewing can be kept open for at most a year after the meeting.
+fn validate_review_close_days(days: u32) -> Result<(), ApiError> {
+    if days > MAX_REVIEW_CLOSE_DAYS {
+        return Err(ApiError::Validation(format!(
+            "Reviews can close at most {MAX_REVIEW_CLOSE_DAYS} days after the meeting"
+        )));
+    }
+    Ok(())
+}
+
+/// A meeting as recorded in the audit log, or `None` if it doesn't exist.
+async fn meeting_snapshot(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+) -> Result<Option<Value>, ApiError> {
+    let row = sqlx::query(
+        "SELECT album_id, album_name, album_artist, picker_id, meeting_date, meeting_time,
+                meeting_location, reveal_policy, review_close_days
+         FROM meetings
+         WHERE id = ?",
+    )
+    .bind(meeting_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .server_err()?;
+
+    let skipped: Vec<(MemberId, String)> =
+        sqlx::query_as("SELECT member_id, reason FROM rotation_skips WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_all(&mut *conn)
+            .await
+            .server_err()?;
+
+    Ok(row.map(|row| {
+        json!({
+            "id": meeting_id,
+            "album_id": row.get::<String, _>("album_id"),
+            "album_name": row.get::<String, _>("album_name"),
+            "album_artist": row.get::<String, _>("album_artist"),
+            "picker_id": row.get::<MemberId, _>("picker_id"),
+            "meeting_date": row.get::<String, _>("meeting_date"),
+            "meeting_time": row.get::<Option<String>, _>("meeting_time"),
+            "meeting_location": row.get::<Option<String>, _>("meeting_location"),
+            "reveal_policy": row.get::<String, _>("reveal_policy"),
+            "review_close_days": row.get::<i64, _>("review_close_days"),
+            "skipped": skipped
+                .iter()
+                .map(|(member_id, reason)| json!({ "member_id": member_id, "reason": reason }))
+                .collect::<Vec<_>>(),
+        })
+    }))
+}
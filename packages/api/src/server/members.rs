@@ -1,93 +1,436 @@
 //! Member-related server function implementations.
 
-use argon2::{
-    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
-    Argon2, PasswordHash, PasswordVerifier,
+use std::sync::LazyLock;
+
+use serde_json::json;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{
+    AuditAction, Member, MemberId, MemberSession, RemovedMember, MIN_PASSWORD_LEN,
+};
+use crate::error::ApiError;
+
+use super::{
+    admin_auth::ensure_admin_access_remains,
+    audit::{record_audit, Actor},
+    ensure_admin, get_db, hash_secret,
+    login_throttle::{login_keys, record_success, reserve_attempt},
+    secret_matches,
+    sessions::{revoke_member_sessions, session_member, start_member_session},
+    IntoServerError,
 };
-use dioxus::prelude::ServerFnError;
-use rand::distributions::{Alphanumeric, DistString};
-use sqlx::Row;
 
-use super::{ensure_admin_token, get_db, IntoServerError};
+const MAX_MEMBER_NAME_LEN: usize = 40;
+const MAX_PASSWORD_LEN: usize = 128;
+
+/// Checked against when the member has no password, so the answer takes as long as for
+/// a wrong password.
+static DUMMY_PASSWORD_HASH: LazyLock<Result<String, ApiError>> =
+    LazyLock::new(|| hash_secret("no password set"));
 
+/// Check a member's password, throttled per member and per client IP. Unknown members,
+/// members without a password and wrong passwords all fail the same way, so the answer
+/// doesn't tell which names exist or have a password.
 pub async fn verify_member_password_internal(
-    member_name: &str,
+    member_id: MemberId,
+    password: &str,
+) -> Result<(), ApiError> {
+    verify_member_login(member_id, password, false).await
+}
+
+/// Like [`verify_member_password_internal`], but with `admin_only` a member without admin
+/// rights fails the same way as a wrong password, and counts as a failed attempt.
+pub async fn verify_member_login(
+    member_id: MemberId,
     password: &str,
-) -> Result<(), ServerFnError> {
+    admin_only: bool,
+) -> Result<(), ApiError> {
+    let keys = login_keys(Some(member_id)).await;
+    reserve_attempt(&keys)?;
+
     let pool = get_db().await?;
 
-    let row = sqlx::query("SELECT password_hash FROM members WHERE name = ?")
-        .bind(member_name)
-        .fetch_optional(pool)
-        .await
-        .server_err()?;
+    let row = sqlx::query(
+        "SELECT password_hash, is_admin FROM members WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(member_id)
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+    let hash: Option<String> = row.as_ref().and_then(|row| row.get("password_hash"));
+    let is_admin = row.is_some_and(|row| row.get::<bool, _>("is_admin"));
 
-    let row = row.ok_or_else(|| ServerFnError::new("Unknown member"))?;
-    let hash: Option<String> = row.get("password_hash");
+    let matches = match hash {
+        Some(hash) => secret_matches(password, &hash)?,
+        None => {
+            let dummy = DUMMY_PASSWORD_HASH.as_ref().map_err(Clone::clone)?;
+            secret_matches(password, dummy)?;
+            false
+        }
+    };
 
-    let hash = hash.ok_or_else(|| {
-        ServerFnError::new("No password set for this member — ask an admin to generate one")
-    })?;
+    if !matches || (admin_only && !is_admin) {
+        return Err(invalid_login());
+    }
 
-    let parsed =
-        PasswordHash::new(&hash).map_err(|_| ServerFnError::new("Stored hash is invalid"))?;
+    record_success(&keys);
+    Ok(())
+}
 
-    Argon2::default()
-        .verify_password(password.as_bytes(), &parsed)
-        .map_err(|_| ServerFnError::new("Incorrect password"))
+pub fn invalid_login() -> ApiError {
+    ApiError::Unauthorized("Incorrect member or password".to_string())
 }
 
-pub async fn admin_set_member_password_impl(
-    admin_token: String,
-    member_name: String,
-) -> Result<String, ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+/// Change the logged-in member's own password. Every session, including the one used
+/// here, is ended; the member is logged in again with the returned session.
+pub async fn change_member_password_impl(
+    session_token: String,
+    current_password: String,
+    new_password: String,
+) -> Result<MemberSession, ApiError> {
+    let pool = get_db().await?;
+    let member = session_member(pool, &session_token).await?;
+    let member_id = member.id;
+    tracing::info!("POST /api/member/password member={member_id}");
 
-    tracing::info!("POST /api/admin/member/set-password member=\"{member_name}\"");
+    verify_member_password_internal(member_id, &current_password).await?;
 
-    let plain: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+    let mut tx = pool.begin().await.server_err()?;
+    set_member_password(&mut tx, member_id, &new_password).await?;
+    record_audit(
+        &mut tx,
+        &Actor::member(&member),
+        AuditAction::ChangePassword,
+        None,
+        Some(json!({ "member_id": member_id })),
+    )
+    .await?;
+    tx.commit().await.server_err()?;
 
-    let salt = SaltString::generate(&mut OsRng);
-    let hash = Argon2::default()
-        .hash_password(plain.as_bytes(), &salt)
-        .map_err(|e| ServerFnError::new(format!("Failed to hash password: {e}")))?
-        .to_string();
+    tracing::info!("POST /api/member/password {member_id} → ok");
+    start_member_session(pool, member_id).await
+}
 
-    let pool = get_db().await?;
+/// Store a new password for an active member and log them out everywhere, since the
+/// old password no longer works and neither should logins made with it.
+pub async fn set_member_password(
+    conn: &mut SqliteConnection,
+    member_id: MemberId,
+    password: &str,
+) -> Result<(), ApiError> {
+    validate_password(password)?;
+    let hash = hash_secret(password)?;
 
-    let rows_affected = sqlx::query("UPDATE members SET password_hash = ? WHERE name = ?")
-        .bind(&hash)
-        .bind(&member_name)
-        .execute(pool)
-        .await
-        .server_err()?
-        .rows_affected();
+    let rows_affected =
+        sqlx::query("UPDATE members SET password_hash = ? WHERE id = ? AND deleted_at IS NULL")
+            .bind(&hash)
+            .bind(member_id)
+            .execute(&mut *conn)
+            .await
+            .server_err()?
+            .rows_affected();
 
     if rows_affected == 0 {
-        return Err(ServerFnError::new(format!(
-            "Member \"{member_name}\" not found"
-        )));
+        return Err(ApiError::NotFound(format!("Member {member_id} not found")));
     }
 
-    tracing::info!("POST /api/admin/member/set-password \"{member_name}\" → ok");
-    Ok(plain)
+    revoke_member_sessions(conn, member_id).await
 }
 
 pub async fn admin_delete_member_impl(
+    admin_token: String,
+    member_id: MemberId,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/member/delete member={member_id}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let name: String = sqlx::query_scalar(
+        "UPDATE members SET deleted_at = datetime('now')
+         WHERE id = ? AND deleted_at IS NULL
+         RETURNING name",
+    )
+    .bind(member_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("Member {member_id} not found")))?;
+
+    ensure_admin_access_remains(&mut tx).await?;
+    revoke_member_sessions(&mut tx, member_id).await?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::DeleteMember,
+        Some(json!({ "id": member_id, "name": name })),
+        None,
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/delete {member_id} → ok");
+    Ok(())
+}
+
+pub async fn admin_add_member_impl(
     admin_token: String,
     member_name: String,
-) -> Result<(), ServerFnError> {
-    ensure_admin_token(&admin_token)?;
-    tracing::info!("POST /api/admin/member/delete member=\"{member_name}\"");
+    position: u32,
+) -> Result<Vec<Member>, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    let member_name = validate_member_name(&member_name)?;
+    tracing::info!("POST /api/admin/member/add member=\"{member_name}\" position={position}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    ensure_name_available(&mut tx, &member_name).await?;
+
+    let id: MemberId = sqlx::query_scalar(
+        "INSERT INTO members (name, joined_at) VALUES (?, datetime('now')) RETURNING id",
+    )
+    .bind(&member_name)
+    .fetch_one(&mut *tx)
+    .await
+    .server_err()?;
+
+    let mut members = active_members(&mut tx).await?;
+    let index = members
+        .iter()
+        .position(|member| member.id == id)
+        .ok_or_else(|| ApiError::Internal("New member was not stored".to_string()))?;
+    let member = members.remove(index);
+    members.insert((position as usize).min(members.len()), member);
+    write_sort_order(&mut tx, &members).await?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::AddMember,
+        None,
+        Some(json!({ "id": id, "name": member_name, "position": position })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/add \"{member_name}\" → {id}");
+    Ok(members)
+}
+
+/// Change a member's display name. Meetings and reviews reference the member by ID,
+/// so nothing else needs updating.
+pub async fn admin_rename_member_impl(
+    admin_token: String,
+    member_id: MemberId,
+    new_name: String,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    let new_name = validate_member_name(&new_name)?;
+    tracing::info!("POST /api/admin/member/rename member={member_id} new=\"{new_name}\"");
 
     let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
 
-    sqlx::query("UPDATE members SET deleted_at = datetime('now') WHERE name = ?")
-        .bind(&member_name)
-        .execute(pool)
+    let current_name: Option<String> = sqlx::query_scalar("SELECT name FROM members WHERE id = ?")
+        .bind(member_id)
+        .fetch_optional(&mut *tx)
         .await
         .server_err()?;
 
-    tracing::info!("POST /api/admin/member/delete \"{member_name}\" → ok");
+    let current_name = match current_name {
+        None => return Err(ApiError::NotFound(format!("Member {member_id} not found"))),
+        Some(name) if name == new_name => return Ok(()),
+        Some(name) => name,
+    };
+
+    ensure_name_available(&mut tx, &new_name).await?;
+
+    sqlx::query("UPDATE members SET name = ? WHERE id = ?")
+        .bind(&new_name)
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::RenameMember,
+        Some(json!({ "id": member_id, "name": current_name })),
+        Some(json!({ "id": member_id, "name": new_name })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/rename {member_id} → \"{new_name}\"");
+    Ok(())
+}
+
+pub async fn admin_removed_members_impl(
+    admin_token: String,
+) -> Result<Vec<RemovedMember>, ApiError> {
+    ensure_admin(&admin_token).await?;
+
+    let pool = get_db().await?;
+
+    let rows = sqlx::query(
+        "SELECT id, name, deleted_at FROM members
+         WHERE deleted_at IS NOT NULL
+         ORDER BY deleted_at DESC, name",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RemovedMember {
+            id: row.get("id"),
+            name: row.get("name"),
+            deleted_at: row.get("deleted_at"),
+        })
+        .collect())
+}
+
+/// Undo a soft delete. The member rejoins at the end of the rotation.
+pub async fn admin_restore_member_impl(
+    admin_token: String,
+    member_id: MemberId,
+) -> Result<Vec<Member>, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/member/restore member={member_id}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let name: String = sqlx::query_scalar(
+        "UPDATE members
+         SET deleted_at = NULL,
+             joined_at = datetime('now'),
+             sort_order = (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM members
+                           WHERE deleted_at IS NULL)
+         WHERE id = ? AND deleted_at IS NOT NULL
+         RETURNING name",
+    )
+    .bind(member_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("No removed member {member_id}")))?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::RestoreMember,
+        None,
+        Some(json!({ "id": member_id, "name": name })),
+    )
+    .await?;
+
+    let members = active_members(&mut tx).await?;
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/member/restore {member_id} → ok");
+    Ok(members)
+}
+
+/// Members in the rotation, in rotation order.
+pub async fn active_members(conn: &mut SqliteConnection) -> Result<Vec<Member>, ApiError> {
+    let rows = sqlx::query(
+        "SELECT id, name FROM members WHERE deleted_at IS NULL ORDER BY sort_order, id",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Member {
+            id: row.get("id"),
+            name: row.get::<String, _>("name").into(),
+        })
+        .collect())
+}
+
+/// Reject IDs that don't belong to a member in the rotation, e.g. when picking.
+pub async fn ensure_active_member(pool: &SqlitePool, member_id: MemberId) -> Result<(), ApiError> {
+    let exists: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM members WHERE id = ? AND deleted_at IS NULL")
+            .bind(member_id)
+            .fetch_one(pool)
+            .await
+            .server_err()?;
+
+    if exists == 0 {
+        return Err(ApiError::NotFound(format!("Member {member_id} not found")));
+    }
+    Ok(())
+}
+
+fn validate_member_name(name: &str) -> Result<String, ApiError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::Validation(
+            "Member name must not be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_MEMBER_NAME_LEN {
+        return Err(ApiError::Validation(format!(
+            "Member name must be at most {MAX_MEMBER_NAME_LEN} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+pub fn validate_password(password: &str) -> Result<(), ApiError> {
+    let len = password.chars().count();
+    if len < MIN_PASSWORD_LEN {
+        return Err(ApiError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LEN} characters"
+        )));
+    }
+    if len > MAX_PASSWORD_LEN {
+        return Err(ApiError::Validation(format!(
+            "Password must be at most {MAX_PASSWORD_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+/// Names stay unique, removed members included, so members can tell each other apart.
+async fn ensure_name_available(conn: &mut SqliteConnection, name: &str) -> Result<(), ApiError> {
+    let deleted_at: Option<Option<String>> =
+        sqlx::query_scalar("SELECT deleted_at FROM members WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await
+            .server_err()?;
+
+    match deleted_at {
+        None => Ok(()),
+        Some(Some(_)) => Err(ApiError::Validation(format!(
+            "\"{name}\" is a removed member, restore them instead"
+        ))),
+        Some(None) => Err(ApiError::Validation(format!(
+            "Member \"{name}\" already exists"
+        ))),
+    }
+}
+
+async fn write_sort_order(conn: &mut SqliteConnection, members: &[Member]) -> Result<(), ApiError> {
+    for (i, member) in members.iter().enumerate() {
+        sqlx::query("UPDATE members SET sort_order = ? WHERE id = ?")
+            .bind(i as i64)
+            .bind(member.id)
+            .execute(&mut *conn)
+            .await
+            .server_err()?;
+    }
     Ok(())
 }
@@ -1,41 +1,100 @@
 #![cfg(feature = "server")]
 //! Server-only infrastructure shared across all endpoint modules.
 
-use std::sync::OnceLock;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    Argon2, PasswordHash, PasswordVerifier,
+};
+use ::spotify::{MusicCatalog, SpotifyClient, SpotifyError};
 
-use argon2::{Argon2, PasswordHash, PasswordVerifier};
-use dioxus::prelude::ServerFnError;
-use ::spotify::SpotifyClient;
+use crate::error::ApiError;
 
+pub mod admin_auth;
+pub mod album_art;
+pub mod albums;
+pub mod audit;
+pub mod availability;
+pub mod invites;
+pub mod login_throttle;
 pub mod meetings;
 pub mod members;
 pub mod reviews;
+pub mod rotation;
+pub mod sessions;
 pub mod spotify;
+pub mod spotify_account;
+pub mod stats;
 
 // Re-export the impl fns so lib.rs can reach them via `server::*`.
+pub use admin_auth::{
+    admin_access_impl, admin_create_credential_impl, admin_login_impl, admin_login_member_impl,
+    admin_logout_impl, admin_revoke_credential_impl, admin_session_impl,
+    admin_set_member_admin_impl, ensure_admin,
+};
+pub use audit::admin_audit_log_impl;
+pub use availability::{
+    add_unavailability_impl, admin_add_unavailability_impl, admin_remove_unavailability_impl,
+    get_unavailability_impl, remove_unavailability_impl,
+};
 pub use meetings::{
     admin_delete_history_entry_impl, admin_reorder_members_impl, admin_set_current_impl,
-    admin_update_current_impl, get_current_impl, get_history_impl,
+    admin_update_current_impl, get_current_impl, get_history_impl, get_meeting_impl,
+};
+pub use invites::{
+    admin_create_member_invite_impl, member_invite_impl, redeem_member_invite_impl,
+};
+pub use members::{
+    admin_add_member_impl, admin_delete_member_impl, admin_removed_members_impl,
+    admin_rename_member_impl, admin_restore_member_impl, change_member_password_impl,
+};
+pub use reviews::{
+    admin_reveal_reviews_impl, admin_set_reviews_locked_impl, get_reviews_impl,
+    submit_album_review_impl, submit_track_review_impl,
 };
-pub use members::{admin_delete_member_impl, admin_set_member_password_impl};
-pub use reviews::{get_reviews_impl, submit_album_review_impl, submit_track_review_impl};
+pub use rotation::{
+    admin_fairness_report_impl, admin_rotation_skips_impl, get_next_picker_impl,
+};
+pub use sessions::{member_logout_impl, member_session_impl, verify_member_impl};
 pub use spotify::{admin_spotify_album_search_impl, get_album_tracks_impl};
+pub use stats::{get_compatibility_impl, get_stats_impl};
+pub use spotify_account::{
+    admin_spotify_account_impl, admin_spotify_authorize_impl, admin_spotify_disconnect_impl,
+    admin_spotify_export_playlist_impl, spotify_complete_authorization_impl,
+};
 
-// Also re-export verify so lib.rs can call it directly for the verify_member endpoint.
-pub use self::members::verify_member_password_internal;
+// The art route and backfill are used outside server functions, by `web` and the backfill bin.
+pub use album_art::{album_art_router, backfill_album_art, BackfillReport};
 
 // ---------------------------------------------------------------------------
 // Extension trait
 // ---------------------------------------------------------------------------
 
-/// Converts any `Display` error into a `ServerFnError` via `.server_err()`.
+/// Converts any `Display` error into an internal `ApiError` via `.server_err()`.
 pub trait IntoServerError<T> {
-    fn server_err(self) -> Result<T, ServerFnError>;
+    fn server_err(self) -> Result<T, ApiError>;
 }
 
 impl<T, E: std::fmt::Display> IntoServerError<T> for Result<T, E> {
-    fn server_err(self) -> Result<T, ServerFnError> {
-        self.map_err(|e| ServerFnError::new(e.to_string()))
+    fn server_err(self) -> Result<T, ApiError> {
+        self.map_err(|e| ApiError::Internal(e.to_string()))
+    }
+}
+
+impl From<SpotifyError> for ApiError {
+    fn from(error: SpotifyError) -> Self {
+        match error {
+            SpotifyError::NotFound(message) => ApiError::NotFound(message),
+            SpotifyError::RateLimited { retry_after } => ApiError::RateLimited {
+                retry_after_secs: retry_after.map(|delay| delay.as_secs()),
+            },
+            SpotifyError::Unavailable(message) | SpotifyError::Unexpected(message) => {
+                ApiError::UpstreamUnavailable(message)
+            }
+            // Bad credentials or configuration are our problem, not Spotify's.
+            SpotifyError::Config(message) | SpotifyError::Auth(message) => {
+                ApiError::Internal(message)
+            }
+        }
     }
 }
 
@@ -43,18 +102,20 @@ impl<T, E: std::fmt::Display> IntoServerError<T> for Result<T, E> {
 // Statics
 // ---------------------------------------------------------------------------
 
-pub static SPOTIFY_CLIENT: OnceLock<tokio::sync::Mutex<Option<SpotifyClient>>> =
-    OnceLock::new();
+/// The catalog is shared by all requests without a lock; it synchronises its own token cache.
+static MUSIC_CATALOG: tokio::sync::OnceCell<Box<dyn MusicCatalog>> =
+    tokio::sync::OnceCell::const_new();
 
-static DB: tokio::sync::OnceCell<sqlx::SqlitePool> = tokio::sync::OnceCell::const_new();
+/// Used directly (not through the catalog) for calls on behalf of the connected Spotify account.
+static SPOTIFY_CLIENT: tokio::sync::OnceCell<SpotifyClient> = tokio::sync::OnceCell::const_new();
 
-const ADMIN_TOKEN_ENV: &str = "ADMIN_TOKEN";
+static DB: tokio::sync::OnceCell<sqlx::SqlitePool> = tokio::sync::OnceCell::const_new();
 
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
 
-pub async fn get_db() -> Result<&'static sqlx::SqlitePool, ServerFnError> {
+pub async fn get_db() -> Result<&'static sqlx::SqlitePool, ApiError> {
     DB.get_or_try_init(|| async {
         let db_url =
             std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:database.db".to_string());
@@ -71,34 +132,44 @@ pub async fn get_db() -> Result<&'static sqlx::SqlitePool, ServerFnError> {
 }
 
 /// Eagerly initialise the database pool. Call this on startup to surface errors early.
-pub async fn init_db() -> Result<(), ServerFnError> {
+pub async fn init_db() -> Result<(), ApiError> {
     get_db().await?;
     Ok(())
 }
 
-pub fn ensure_admin_token(admin_token: &str) -> Result<(), ServerFnError> {
-    let expected_hash = std::env::var(ADMIN_TOKEN_ENV)
-        .map_err(|_| ServerFnError::new("ADMIN_TOKEN is not configured on the server"))?;
-
-    let parsed_hash = PasswordHash::new(&expected_hash)
-        .map_err(|_| ServerFnError::new("ADMIN_TOKEN must be a valid Argon2 PHC hash"))?;
-
+/// Argon2 PHC hash of a password or admin token.
+pub fn hash_secret(plain: &str) -> Result<String, ApiError> {
+    let salt = SaltString::generate(&mut OsRng);
     Argon2::default()
-        .verify_password(admin_token.as_bytes(), &parsed_hash)
-        .map_err(|_| ServerFnError::new("Unauthorized"))?;
+        .hash_password(plain.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ApiError::Internal(format!("Failed to hash secret: {e}")))
+}
 
-    Ok(())
+/// Whether `plain` matches an Argon2 PHC hash made by [`hash_secret`].
+pub fn secret_matches(plain: &str, hash: &str) -> Result<bool, ApiError> {
+    let parsed = PasswordHash::new(hash)
+        .map_err(|_| ApiError::Internal("Stored hash is invalid".to_string()))?;
+
+    Ok(Argon2::default()
+        .verify_password(plain.as_bytes(), &parsed)
+        .is_ok())
 }
 
-/// Acquire the Spotify client, lazily initialising it from environment variables.
-pub async fn get_spotify_client(
-) -> Result<tokio::sync::MutexGuard<'static, Option<SpotifyClient>>, ServerFnError> {
-    let mutex = SPOTIFY_CLIENT.get_or_init(|| tokio::sync::Mutex::new(None));
-    let mut guard = mutex.lock().await;
+/// Get the music catalog, lazily initialising it from environment variables.
+/// `MUSIC_CATALOG` selects between the Spotify Web API and a local JSON fixture.
+pub async fn get_music_catalog() -> Result<&'static dyn MusicCatalog, ApiError> {
+    let catalog = MUSIC_CATALOG
+        .get_or_try_init(|| async { ::spotify::catalog_from_env() })
+        .await?;
 
-    if guard.is_none() {
-        *guard = Some(SpotifyClient::from_env().server_err()?);
-    }
+    Ok(catalog.as_ref())
+}
 
-    Ok(guard)
+/// Get the Spotify Web API client, configured from `SPOTIFY_CLIENT_ID` and friends. Unlike
+/// [`get_music_catalog`] this is always the real API (or its configured stand-in).
+pub async fn get_spotify_client() -> Result<&'static SpotifyClient, ApiError> {
+    Ok(SPOTIFY_CLIENT
+        .get_or_try_init(|| async { SpotifyClient::from_env() })
+        .await?)
 }
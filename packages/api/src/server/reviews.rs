@@ -1,114 +1,419 @@
 //! Review-related server function implementations.
 
-use dioxus::prelude::ServerFnError;
-use sqlx::Row;
+use serde_json::json;
+use sqlx::{Row, SqliteConnection};
 use uuid::Uuid;
 
-use crate::api_models::{AlbumReview, Reviews, TrackReview};
+use crate::api_models::{
+    AlbumReview, AuditAction, MemberId, RevealPolicy, ReviewWindow, Reviews, TrackReview,
+    MAX_REVIEW_TEXT_LEN, MAX_TRACK_COMMENT_LEN,
+};
+use crate::error::ApiError;
 
-use super::{get_db, members::verify_member_password_internal, IntoServerError};
+use super::{
+    audit::{record_audit, Actor},
+    ensure_admin, get_db,
+    sessions::session_member,
+    IntoServerError,
+};
 
-pub async fn get_reviews_impl(meeting_id: String) -> Result<Reviews, ServerFnError> {
+/// The reviews of a meeting as seen by the logged in member, or by anyone if there's no
+/// (valid) session: until the meeting's reviews are revealed, only the member's own.
+pub async fn get_reviews_impl(
+    meeting_id: String,
+    session_token: Option<String>,
+) -> Result<Reviews, ApiError> {
     tracing::debug!("get_reviews meeting_id=\"{meeting_id}\"");
     let pool = get_db().await?;
 
-    let album_rows =
-        sqlx::query("SELECT member_name, score FROM album_reviews WHERE meeting_id = ?")
-            .bind(&meeting_id)
-            .fetch_all(pool)
+    let viewer = match session_token {
+        Some(token) => session_member(pool, &token).await.ok().map(|member| member.id),
+        None => None,
+    };
+
+    let mut conn = pool.acquire().await.server_err()?;
+    reviews_for(&mut conn, &meeting_id, viewer).await
+}
+
+async fn reviews_for(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    viewer: Option<MemberId>,
+) -> Result<Reviews, ApiError> {
+    let meeting = sqlx::query(
+        "SELECT is_current, reveal_policy, reviews_revealed_at IS NOT NULL AS revealed_by_admin,
+                datetime(meeting_date || ' ' || COALESCE(meeting_time, '00:00'))
+                    <= datetime('now', 'localtime') AS has_started
+         FROM meetings
+         WHERE id = ?",
+    )
+    .bind(meeting_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("Meeting {meeting_id} not found")))?;
+
+    let reveal_policy = RevealPolicy::parse(meeting.get("reveal_policy")).unwrap_or_default();
+    let window = review_window(conn, meeting_id).await?;
+
+    let reviewers: Vec<MemberId> =
+        sqlx::query_scalar("SELECT member_id FROM album_reviews WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_all(&mut *conn)
             .await
             .server_err()?;
 
+    // A past meeting is revealed once it can't be reviewed any more; while late reviews are
+    // still open its policy applies. `has_started` is NULL if the date doesn't parse.
+    let revealed = (!meeting.get::<bool, _>("is_current") && !window.open)
+        || meeting.get::<bool, _>("revealed_by_admin")
+        || match reveal_policy {
+            RevealPolicy::AfterOwnReview => viewer.is_some_and(|id| reviewers.contains(&id)),
+            RevealPolicy::AtMeetingTime => {
+                meeting.get::<Option<bool>, _>("has_started").unwrap_or(false)
+            }
+            RevealPolicy::AdminReveal => false,
+        };
+
+    let album_rows = sqlx::query(
+        "SELECT r.member_id, m.name AS member_name, r.score, r.review_text
+         FROM album_reviews r
+         JOIN members m ON m.id = r.member_id
+         WHERE r.meeting_id = ? AND (? OR r.member_id = ?)",
+    )
+    .bind(meeting_id)
+    .bind(revealed)
+    .bind(viewer)
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?;
+
     let album_reviews = album_rows
         .into_iter()
         .map(|r| AlbumReview {
+            member_id: r.get("member_id"),
             member_name: r.get("member_name"),
             score: r.get::<i64, _>("score") as u8,
+            text: r.get("review_text"),
         })
         .collect();
 
     let track_rows = sqlx::query(
-        "SELECT member_name, track_id, score FROM track_reviews WHERE meeting_id = ?",
+        "SELECT r.member_id, m.name AS member_name, r.track_id, r.score, r.comment
+         FROM track_reviews r
+         JOIN members m ON m.id = r.member_id
+         WHERE r.meeting_id = ? AND (? OR r.member_id = ?)",
     )
-    .bind(&meeting_id)
-    .fetch_all(pool)
+    .bind(meeting_id)
+    .bind(revealed)
+    .bind(viewer)
+    .fetch_all(&mut *conn)
     .await
     .server_err()?;
 
     let track_reviews = track_rows
         .into_iter()
         .map(|r| TrackReview {
+            member_id: r.get("member_id"),
             member_name: r.get("member_name"),
             track_id: r.get("track_id"),
             score: r.get::<i64, _>("score") as u8,
+            comment: r.get("comment"),
         })
         .collect();
 
     Ok(Reviews {
         album_reviews,
         track_reviews,
+        revealed,
+        reveal_policy,
+        reviewer_count: reviewers.len() as u32,
+        window,
     })
 }
 
+/// Whether the meeting can still be reviewed. Meeting times are the server's local time.
+async fn review_window(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+) -> Result<ReviewWindow, ApiError> {
+    let row = sqlx::query(
+        "SELECT locked, closes_at, closes_at > datetime('now', 'localtime') AS before_close
+         FROM (SELECT reviews_locked_at IS NOT NULL AS locked,
+                      datetime(meeting_date || ' ' || COALESCE(meeting_time, '00:00'),
+                               '+' || review_close_days || ' days') AS closes_at
+               FROM meetings
+               WHERE id = ?)",
+    )
+    .bind(meeting_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("Meeting {meeting_id} not found")))?;
+
+    let locked: bool = row.get("locked");
+    // A date that doesn't parse gives no closing time; then only a lock closes reviewing.
+    let before_close = row.get::<Option<bool>, _>("before_close").unwrap_or(true);
+
+    Ok(ReviewWindow {
+        open: !locked && before_close,
+        closes_at: row.get("closes_at"),
+        locked,
+    })
+}
+
+async fn ensure_review_window_open(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+) -> Result<(), ApiError> {
+    if review_window(conn, meeting_id).await?.open {
+        Ok(())
+    } else {
+        Err(ApiError::ReviewsClosed(format!("Reviewing of meeting {meeting_id} is closed")))
+    }
+}
+
+/// Close reviewing of a meeting ahead of time, or open it again. Reopening doesn't extend
+/// the window past its closing time.
+pub async fn admin_set_reviews_locked_impl(
+    admin_token: String,
+    meeting_id: String,
+    locked: bool,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/reviews-locked meeting=\"{meeting_id}\" locked={locked}");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let was_locked: bool = sqlx::query_scalar(
+        "SELECT reviews_locked_at IS NOT NULL FROM meetings WHERE id = ?",
+    )
+    .bind(&meeting_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?
+    .ok_or_else(|| ApiError::NotFound(format!("Meeting {meeting_id} not found")))?;
+
+    if was_locked != locked {
+        sqlx::query(
+            "UPDATE meetings
+             SET reviews_locked_at = CASE WHEN ? THEN datetime('now') ELSE NULL END
+             WHERE id = ?",
+        )
+        .bind(locked)
+        .bind(&meeting_id)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+        record_audit(
+            &mut tx,
+            &Actor::admin(&admin),
+            AuditAction::SetReviewsLocked,
+            Some(json!({ "meeting_id": meeting_id, "locked": was_locked })),
+            Some(json!({ "meeting_id": meeting_id, "locked": locked })),
+        )
+        .await?;
+    }
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/reviews-locked meeting=\"{meeting_id}\" → ok");
+    Ok(())
+}
+
+/// Show everyone's reviews of a meeting now, whatever its reveal policy.
+pub async fn admin_reveal_reviews_impl(
+    admin_token: String,
+    meeting_id: String,
+) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/reveal-reviews meeting=\"{meeting_id}\"");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let revealed_at: Option<String> = sqlx::query_scalar(
+        "UPDATE meetings SET reviews_revealed_at = datetime('now')
+         WHERE id = ? AND reviews_revealed_at IS NULL
+         RETURNING reviews_revealed_at",
+    )
+    .bind(&meeting_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?;
+
+    if let Some(revealed_at) = revealed_at {
+        record_audit(
+            &mut tx,
+            &Actor::admin(&admin),
+            AuditAction::RevealReviews,
+            None,
+            Some(json!({ "meeting_id": meeting_id, "revealed_at": revealed_at })),
+        )
+        .await?;
+    }
+
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/admin/reveal-reviews meeting=\"{meeting_id}\" → ok");
+    Ok(())
+}
+
 pub async fn submit_album_review_impl(
-    member_name: String,
-    password: String,
+    session_token: String,
     meeting_id: String,
     score: u8,
-) -> Result<Reviews, ServerFnError> {
+    text: Option<String>,
+) -> Result<Reviews, ApiError> {
+    let text = normalize_text(text, MAX_REVIEW_TEXT_LEN, "Review")?;
+    let pool = get_db().await?;
+    let member = session_member(pool, &session_token).await?;
+    let member_id = member.id;
     tracing::info!(
-        "submit_album_review member=\"{member_name}\" meeting=\"{meeting_id}\" score={score}"
+        "submit_album_review member={member_id} meeting=\"{meeting_id}\" score={score}"
     );
-    verify_member_password_internal(&member_name, &password).await?;
 
-    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+    ensure_review_window_open(&mut tx, &meeting_id).await?;
 
-    sqlx::query(
-        "INSERT INTO album_reviews (id, meeting_id, member_name, score)
-         VALUES (?, ?, ?, ?)
-         ON CONFLICT(meeting_id, member_name)
-         DO UPDATE SET score = excluded.score, updated_at = datetime('now')",
+    let previous: Option<(i64, Option<String>)> = sqlx::query_as(
+        "SELECT score, review_text FROM album_reviews WHERE meeting_id = ? AND member_id = ?",
+    )
+    .bind(&meeting_id)
+    .bind(member_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?;
+
+    let (score, text): (i64, Option<String>) = sqlx::query_as(
+        "INSERT INTO album_reviews (id, meeting_id, member_id, score, review_text)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(meeting_id, member_id)
+         DO UPDATE SET score = excluded.score,
+                       review_text = CASE WHEN ? THEN excluded.review_text
+                                          ELSE album_reviews.review_text END,
+                       updated_at = datetime('now')
+         RETURNING score, review_text",
     )
     .bind(Uuid::new_v4().to_string())
     .bind(&meeting_id)
-    .bind(&member_name)
+    .bind(member_id)
     .bind(score as i64)
-    .execute(pool)
+    .bind(text.clone().flatten())
+    .bind(text.is_some())
+    .fetch_one(&mut *tx)
     .await
     .server_err()?;
 
+    let snapshot = |score, text| json!({ "meeting_id": meeting_id, "score": score, "text": text });
+    record_audit(
+        &mut tx,
+        &Actor::member(&member),
+        AuditAction::ReviewAlbum,
+        previous.map(|(score, text)| snapshot(score, text)),
+        Some(snapshot(score, text)),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
     tracing::info!("submit_album_review → ok");
-    get_reviews_impl(meeting_id).await
+    let mut conn = pool.acquire().await.server_err()?;
+    reviews_for(&mut conn, &meeting_id, Some(member_id)).await
 }
 
 pub async fn submit_track_review_impl(
-    member_name: String,
-    password: String,
+    session_token: String,
     meeting_id: String,
     track_id: String,
     score: u8,
-) -> Result<Reviews, ServerFnError> {
-    tracing::info!("Reviewing track by {member_name} :: {track_id} :: {score}");
-    tracing::info!("submit_track_review member=\"{member_name}\" track=\"{track_id}\" meeting=\"{meeting_id}\" score={score}");
-    verify_member_password_internal(&member_name, &password).await?;
-
+    comment: Option<String>,
+) -> Result<Reviews, ApiError> {
+    let comment = normalize_text(comment, MAX_TRACK_COMMENT_LEN, "Comment")?;
     let pool = get_db().await?;
+    let member = session_member(pool, &session_token).await?;
+    let member_id = member.id;
+    tracing::info!(
+        "submit_track_review member={member_id} track=\"{track_id}\" meeting=\"{meeting_id}\" \
+         score={score}"
+    );
 
-    sqlx::query(
-        "INSERT INTO track_reviews (id, meeting_id, member_name, track_id, score)
-         VALUES (?, ?, ?, ?, ?)
-         ON CONFLICT(meeting_id, member_name, track_id)
-         DO UPDATE SET score = excluded.score, updated_at = datetime('now')",
+    let mut tx = pool.begin().await.server_err()?;
+    ensure_review_window_open(&mut tx, &meeting_id).await?;
+
+    let previous: Option<(i64, Option<String>)> = sqlx::query_as(
+        "SELECT score, comment FROM track_reviews
+         WHERE meeting_id = ? AND member_id = ? AND track_id = ?",
+    )
+    .bind(&meeting_id)
+    .bind(member_id)
+    .bind(&track_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .server_err()?;
+
+    let (score, comment): (i64, Option<String>) = sqlx::query_as(
+        "INSERT INTO track_reviews (id, meeting_id, member_id, track_id, score, comment)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(meeting_id, member_id, track_id)
+         DO UPDATE SET score = excluded.score,
+                       comment = CASE WHEN ? THEN excluded.comment
+                                      ELSE track_reviews.comment END,
+                       updated_at = datetime('now')
+         RETURNING score, comment",
     )
     .bind(Uuid::new_v4().to_string())
     .bind(&meeting_id)
-    .bind(&member_name)
+    .bind(member_id)
     .bind(&track_id)
     .bind(score as i64)
-    .execute(pool)
+    .bind(comment.clone().flatten())
+    .bind(comment.is_some())
+    .fetch_one(&mut *tx)
     .await
     .server_err()?;
 
+    let snapshot = |score, comment| {
+        json!({
+            "meeting_id": meeting_id,
+            "track_id": track_id,
+            "score": score,
+            "comment": comment,
+        })
+    };
+    record_audit(
+        &mut tx,
+        &Actor::member(&member),
+        AuditAction::ReviewTrack,
+        previous.map(|(score, comment)| snapshot(score, comment)),
+        Some(snapshot(score, comment)),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+
     tracing::info!("submit_track_review → ok");
-    get_reviews_impl(meeting_id).await
+    let mut conn = pool.acquire().await.server_err()?;
+    reviews_for(&mut conn, &meeting_id, Some(member_id)).await
+}
+
+/// Trim a submitted review text. `None` means "leave the stored text alone" and is kept
+/// as is; an empty text becomes `Some(None)`, which clears it.
+fn normalize_text(
+    text: Option<String>,
+    max_len: usize,
+    what: &str,
+) -> Result<Option<Option<String>>, ApiError> {
+    let Some(text) = text else {
+        return Ok(None);
+    };
+    let text = text.trim();
+    if text.chars().count() > max_len {
+        return Err(ApiError::Validation(format!(
+            "{what} can be at most {max_len} characters"
+        )));
+    }
+    Ok(Some((!text.is_empty()).then(|| text.to_string())))
 }
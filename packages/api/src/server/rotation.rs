@@ -0,0 +1,508 @@
+//! Whose turn it is to pick an album.
+
+use std::collections::HashMap;
+
+use sqlx::{Row, SqliteConnection};
+
+use crate::api_models::{
+    FairnessReport, Member, MemberFairness, MemberId, PickImbalance, RotationSkip, SkipReason,
+    PICK_IMBALANCE_THRESHOLD,
+};
+use crate::error::ApiError;
+
+use super::{ensure_admin, get_db, members::active_members, IntoServerError};
+
+const SKIP_LOG_LIMIT: i64 = 50;
+
+/// Who picks a meeting on `meeting_date` (`YYYY-MM-DD`), or on the next meeting if `None`.
+pub async fn get_next_picker_impl(
+    meeting_date: Option<String>,
+) -> Result<Option<Member>, ApiError> {
+    let pool = get_db().await?;
+    let mut conn = pool.acquire().await.server_err()?;
+
+    let date = match meeting_date {
+        Some(date) => date,
+        None => next_meeting_date(&mut conn).await?,
+    };
+    let rotation = upcoming_rotation(&mut conn).await?;
+    let unavailable = unavailable_on(&mut conn, &date).await?;
+
+    Ok(next_picker(&rotation, &unavailable))
+}
+
+/// Recently skipped turns, newest meeting first.
+pub async fn admin_rotation_skips_impl(admin_token: String) -> Result<Vec<RotationSkip>, ApiError> {
+    ensure_admin(&admin_token).await?;
+
+    let pool = get_db().await?;
+
+    let rows = sqlx::query(
+        "SELECT s.meeting_id, m.meeting_date, m.album_name, s.member_id, p.name, s.reason
+         FROM rotation_skips s
+         JOIN meetings m ON m.id = s.meeting_id
+         JOIN members p ON p.id = s.member_id
+         ORDER BY m.meeting_date DESC, m.recorded_at DESC, p.sort_order
+         LIMIT ?",
+    )
+    .bind(SKIP_LOG_LIMIT)
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RotationSkip {
+            meeting_id: row.get("meeting_id"),
+            meeting_date: row.get("meeting_date"),
+            album_name: row.get("album_name"),
+            member_id: row.get("member_id"),
+            member_name: row.get("name"),
+            reason: SkipReason::parse(row.get("reason")).unwrap_or(SkipReason::Passed),
+        })
+        .collect())
+}
+
+/// Picks per member since they joined, against their share of the meetings since then, so
+/// an admin can see whether the rotation needs correcting.
+pub async fn admin_fairness_report_impl(admin_token: String) -> Result<FairnessReport, ApiError> {
+    ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/rotation/fairness");
+
+    let pool = get_db().await?;
+    let mut conn = pool.acquire().await.server_err()?;
+
+    let members = active_members(&mut conn).await?;
+
+    // Join dates and meeting times are both `datetime('now')`, so they compare as text.
+    let joined: HashMap<MemberId, Option<String>> =
+        sqlx::query_as("SELECT id, joined_at FROM members WHERE deleted_at IS NULL")
+            .fetch_all(&mut *conn)
+            .await
+            .server_err()?
+            .into_iter()
+            .collect();
+
+    let meetings: Vec<(MemberId, String)> =
+        sqlx::query_as("SELECT picker_id, recorded_at FROM meetings ORDER BY recorded_at")
+            .fetch_all(&mut *conn)
+            .await
+            .server_err()?;
+
+    let skips: Vec<(MemberId, String)> = sqlx::query_as(
+        "SELECT s.member_id, m.recorded_at
+         FROM rotation_skips s
+         JOIN meetings m ON m.id = s.meeting_id",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?;
+
+    // A meeting planned for later counts as zero days ago.
+    let last_picks: HashMap<MemberId, (String, Option<i64>)> = sqlx::query(
+        "SELECT picker_id, MAX(meeting_date) AS last_date,
+                MAX(0, CAST(julianday(date('now', 'localtime')) - julianday(MAX(meeting_date))
+                            AS INTEGER)) AS days
+         FROM meetings
+         GROUP BY picker_id",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?
+    .into_iter()
+    .map(|row| {
+        (
+            row.get("picker_id"),
+            (row.get("last_date"), row.get("days")),
+        )
+    })
+    .collect();
+
+    let first_meeting = meetings.first().map(|(_, at)| at.as_str());
+    let active = members.len().max(1) as f32;
+
+    let members = members
+        .into_iter()
+        .map(|member| {
+            let joined_at = joined.get(&member.id).cloned().flatten();
+            let since_joined = |at: &str| joined_at.as_deref().is_none_or(|joined| at >= joined);
+
+            let meetings_since = meetings.iter().filter(|(_, at)| since_joined(at)).count();
+            let picks = meetings
+                .iter()
+                .filter(|(picker, at)| *picker == member.id && since_joined(at))
+                .count() as u32;
+            let skipped_turns = skips
+                .iter()
+                .filter(|(skipped, at)| *skipped == member.id && since_joined(at))
+                .count() as u32;
+
+            let expected_picks = meetings_since as f32 / active;
+            let balance = picks as f32 - expected_picks;
+            let imbalance = if balance <= -PICK_IMBALANCE_THRESHOLD {
+                Some(PickImbalance::Behind)
+            } else if balance >= PICK_IMBALANCE_THRESHOLD {
+                Some(PickImbalance::Ahead)
+            } else {
+                None
+            };
+
+            let (last_pick_date, days_since_last_pick) = match last_picks.get(&member.id) {
+                Some((date, days)) => (Some(date.clone()), *days),
+                None => (None, None),
+            };
+            let joined_mid_cycle = joined_at
+                .as_deref()
+                .zip(first_meeting)
+                .is_some_and(|(joined, first)| joined > first);
+
+            MemberFairness {
+                member,
+                picks,
+                expected_picks,
+                balance,
+                imbalance,
+                last_pick_date,
+                days_since_last_pick,
+                joined_at,
+                joined_mid_cycle,
+                skipped_turns,
+            }
+        })
+        .collect();
+
+    Ok(FairnessReport {
+        total_meetings: meetings.len() as u32,
+        members,
+    })
+}
+
+/// Members in the rotation in the order they pick from now on, starting with whoever
+/// follows the latest picker. Members who are away keep their place here.
+///
+/// The turn passes to whoever follows the latest picker in rotation order. If the latest
+/// picker has since been removed, the one before them counts, and so on back through
+/// the history, so removing a member doesn't reset the rotation.
+pub async fn upcoming_rotation(conn: &mut SqliteConnection) -> Result<Vec<Member>, ApiError> {
+    rotation_before(conn, None).await
+}
+
+/// The rotation as it stood before `meeting_id` was set, or as it stands now for `None`.
+async fn rotation_before(
+    conn: &mut SqliteConnection,
+    meeting_id: Option<&str>,
+) -> Result<Vec<Member>, ApiError> {
+    let mut members = active_members(conn).await?;
+
+    let pickers: Vec<MemberId> = sqlx::query_scalar(
+        "SELECT picker_id FROM meetings
+         WHERE id IS NOT ?
+         ORDER BY is_current DESC, meeting_date DESC, recorded_at DESC",
+    )
+    .bind(meeting_id)
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()?;
+
+    let next = pickers
+        .iter()
+        .find_map(|picker| members.iter().position(|m| m.id == *picker))
+        .map_or(0, |latest| latest + 1);
+
+    let len = members.len();
+    if len > 0 {
+        members.rotate_left(next % len);
+    }
+    Ok(members)
+}
+
+/// Whose turn it is: the first member in the rotation who isn't away. Skipped members
+/// keep their place and pick as usual the next cycle. If everyone is away the rotation
+/// goes on as if nobody were.
+pub fn next_picker(rotation: &[Member], unavailable: &[MemberId]) -> Option<Member> {
+    rotation
+        .iter()
+        .find(|m| !unavailable.contains(&m.id))
+        .or(rotation.first())
+        .cloned()
+}
+
+/// Members who are away on `date` (`YYYY-MM-DD`).
+pub async fn unavailable_on(
+    conn: &mut SqliteConnection,
+    date: &str,
+) -> Result<Vec<MemberId>, ApiError> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT member_id FROM member_unavailability
+         WHERE start_date <= ?1 AND end_date >= ?1",
+    )
+    .bind(date)
+    .fetch_all(&mut *conn)
+    .await
+    .server_err()
+}
+
+/// The date to check availability for when the next meeting hasn't been planned: the day
+/// after the current meeting, or today once that has passed.
+pub async fn next_meeting_date(conn: &mut SqliteConnection) -> Result<String, ApiError> {
+    sqlx::query_scalar(
+        "SELECT MAX(date('now', 'localtime'),
+                    COALESCE((SELECT date(meeting_date, '+1 day') FROM meetings
+                              WHERE is_current = 1), ''))",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .server_err()
+}
+
+/// Record whose turns were skipped when `picker_id` was set to pick `meeting_id`: everyone
+/// ahead of them in the rotation, as it stood before the meeting. Replaces what was recorded
+/// before, so it can be called again when the meeting is edited.
+pub async fn record_skips(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    picker_id: MemberId,
+    meeting_date: &str,
+) -> Result<(), ApiError> {
+    let rotation = rotation_before(conn, Some(meeting_id)).await?;
+    let unavailable = unavailable_on(conn, meeting_date).await?;
+
+    sqlx::query("DELETE FROM rotation_skips WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *conn)
+        .await
+        .server_err()?;
+
+    let Some(turn) = rotation.iter().position(|m| m.id == picker_id) else {
+        return Ok(());
+    };
+
+    for member in &rotation[..turn] {
+        let reason = if unavailable.contains(&member.id) {
+            SkipReason::Unavailable
+        } else {
+            SkipReason::Passed
+        };
+        sqlx::query("INSERT INTO rotation_skips (meeting_id, member_id, reason) VALUES (?, ?, ?)")
+            .bind(meeting_id)
+            .bind(member.id)
+            .bind(reason.as_str())
+            .execute(&mut *conn)
+            .await
+            .server_err()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::SqlitePool;
+
+    use super::*;
+    use crate::db::test_pool;
+
+    async fn add_members(pool: &SqlitePool, names: &[&str]) -> Vec<MemberId> {
+        let mut ids = Vec::new();
+        for (sort_order, name) in names.iter().enumerate() {
+            let id = sqlx::query_scalar(
+                "INSERT INTO members (name, sort_order) VALUES (?, ?) RETURNING id",
+            )
+            .bind(name)
+            .bind(sort_order as i64)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+        ids
+    }
+
+    async fn add_meeting(pool: &SqlitePool, picker_id: MemberId, meeting_date: &str) {
+        sqlx::query(
+            "INSERT INTO meetings
+                (id, album_id, album_name, album_artist, album_art_url, album_spotify_url,
+                 picker_id, meeting_date)
+             VALUES (?, 'album', 'Album', 'Artist', '', '', ?, ?)",
+        )
+        .bind(format!("meeting-{meeting_date}"))
+        .bind(picker_id)
+        .bind(meeting_date)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn member(id: MemberId) -> Member {
+        Member {
+            id,
+            name: format!("Member {id}").into(),
+        }
+    }
+
+    fn names(members: Vec<Member>) -> Vec<String> {
+        members
+            .into_iter()
+            .map(|member| member.name.to_string())
+            .collect()
+    }
+
+    async fn skips(pool: &SqlitePool, meeting_id: &str) -> Vec<(MemberId, String)> {
+        sqlx::query_as(
+            "SELECT member_id, reason FROM rotation_skips WHERE meeting_id = ? ORDER BY member_id",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn rotation(pool: &SqlitePool) -> Vec<String> {
+        let mut conn = pool.acquire().await.unwrap();
+        names(upcoming_rotation(&mut conn).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn starts_from_the_top_without_history() {
+        let pool = test_pool().await;
+        assert!(rotation(&pool).await.is_empty());
+
+        add_members(&pool, &["Nox", "Karro", "Vidde"]).await;
+        assert_eq!(rotation(&pool).await, ["Nox", "Karro", "Vidde"]);
+    }
+
+    #[tokio::test]
+    async fn turn_passes_to_whoever_follows_the_latest_picker() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde"]).await;
+
+        add_meeting(&pool, ids[0], "2026-01-10").await;
+        assert_eq!(rotation(&pool).await, ["Karro", "Vidde", "Nox"]);
+
+        // The last member picking wraps around to the first.
+        add_meeting(&pool, ids[2], "2026-02-10").await;
+        assert_eq!(rotation(&pool).await, ["Nox", "Karro", "Vidde"]);
+    }
+
+    #[tokio::test]
+    async fn removed_latest_picker_walks_back_through_history() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde", "Dino"]).await;
+        add_meeting(&pool, ids[0], "2026-01-10").await;
+        add_meeting(&pool, ids[1], "2026-02-10").await;
+
+        sqlx::query("UPDATE members SET deleted_at = datetime('now') WHERE id = ?")
+            .bind(ids[1])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(rotation(&pool).await, ["Vidde", "Dino", "Nox"]);
+    }
+
+    #[test]
+    fn next_picker_skips_members_who_are_away() {
+        let rotation = [member(1), member(2), member(3)];
+
+        assert_eq!(next_picker(&rotation, &[]), Some(member(1)));
+        assert_eq!(next_picker(&rotation, &[1]), Some(member(2)));
+        assert_eq!(next_picker(&rotation, &[1, 2]), Some(member(3)));
+        assert_eq!(next_picker(&rotation, &[2]), Some(member(1)));
+    }
+
+    #[test]
+    fn next_picker_falls_back_to_the_rotation_when_everyone_is_away() {
+        let rotation = [member(1), member(2)];
+
+        assert_eq!(next_picker(&rotation, &[1, 2]), Some(member(1)));
+        assert_eq!(next_picker(&[], &[1]), None);
+    }
+
+    #[tokio::test]
+    async fn rotation_before_leaves_out_the_edited_meeting() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde"]).await;
+        add_meeting(&pool, ids[0], "2026-01-10").await;
+        add_meeting(&pool, ids[2], "2026-02-10").await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        let before_latest = rotation_before(&mut conn, Some("meeting-2026-02-10"))
+            .await
+            .unwrap();
+        assert_eq!(names(before_latest), ["Karro", "Vidde", "Nox"]);
+
+        // Without an edited meeting, Vidde picking last wraps around to the top.
+        let now = rotation_before(&mut conn, None).await.unwrap();
+        assert_eq!(names(now), ["Nox", "Karro", "Vidde"]);
+    }
+
+    #[tokio::test]
+    async fn rotation_before_walks_back_past_removed_pickers() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde", "Dino"]).await;
+        add_meeting(&pool, ids[0], "2026-01-10").await;
+        add_meeting(&pool, ids[1], "2026-02-10").await;
+        add_meeting(&pool, ids[3], "2026-03-10").await;
+
+        sqlx::query("UPDATE members SET deleted_at = datetime('now') WHERE id = ?")
+            .bind(ids[1])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        let before_latest = rotation_before(&mut conn, Some("meeting-2026-03-10"))
+            .await
+            .unwrap();
+        assert_eq!(names(before_latest), ["Vidde", "Dino", "Nox"]);
+    }
+
+    #[tokio::test]
+    async fn record_skips_tells_away_members_from_passed_ones() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde"]).await;
+        add_meeting(&pool, ids[2], "2026-03-10").await;
+
+        sqlx::query(
+            "INSERT INTO member_unavailability (member_id, start_date, end_date)
+             VALUES (?, '2026-03-01', '2026-03-15')",
+        )
+        .bind(ids[0])
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        record_skips(&mut conn, "meeting-2026-03-10", ids[2], "2026-03-10")
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert_eq!(
+            skips(&pool, "meeting-2026-03-10").await,
+            [
+                (ids[0], "unavailable".to_string()),
+                (ids[1], "passed".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn record_skips_replaces_what_was_recorded_before() {
+        let pool = test_pool().await;
+        let ids = add_members(&pool, &["Nox", "Karro", "Vidde"]).await;
+        add_meeting(&pool, ids[2], "2026-03-10").await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        record_skips(&mut conn, "meeting-2026-03-10", ids[2], "2026-03-10")
+            .await
+            .unwrap();
+        // The meeting is edited to have Nox pick, whose turn it was.
+        record_skips(&mut conn, "meeting-2026-03-10", ids[0], "2026-03-10")
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert!(skips(&pool, "meeting-2026-03-10").await.is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+//! Member login sessions for reviewing.
+
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{Member, MemberId, MemberSession};
+use crate::error::ApiError;
+
+use super::{get_db, members::verify_member_password_internal, IntoServerError};
+
+/// How long a login lasts, as an SQLite `datetime` modifier.
+const SESSION_TTL: &str = "+30 days";
+
+const SESSION_TOKEN_LEN: usize = 43;
+
+/// Check the member's password and start a session, so the password only has to be
+/// sent (and hashed) once.
+pub async fn verify_member_impl(
+    member_id: MemberId,
+    password: String,
+) -> Result<MemberSession, ApiError> {
+    tracing::info!("POST /api/member/verify member={member_id}");
+    verify_member_password_internal(member_id, &password).await?;
+
+    let pool = get_db().await?;
+    let session = start_member_session(pool, member_id).await?;
+
+    tracing::info!("POST /api/member/verify {member_id} → ok");
+    Ok(session)
+}
+
+pub async fn member_session_impl(session_token: String) -> Result<Member, ApiError> {
+    let pool = get_db().await?;
+    session_member(pool, &session_token).await
+}
+
+pub async fn member_logout_impl(session_token: String) -> Result<(), ApiError> {
+    let pool = get_db().await?;
+
+    sqlx::query("DELETE FROM member_sessions WHERE token_hash = ?")
+        .bind(hash_token(&session_token))
+        .execute(pool)
+        .await
+        .server_err()?;
+
+    Ok(())
+}
+
+/// Log a member in, e.g. after checking their password.
+pub async fn start_member_session(
+    pool: &SqlitePool,
+    member_id: MemberId,
+) -> Result<MemberSession, ApiError> {
+    sqlx::query("DELETE FROM member_sessions WHERE expires_at <= datetime('now')")
+        .execute(pool)
+        .await
+        .server_err()?;
+
+    let token = new_session_token();
+
+    let expires_at: String = sqlx::query_scalar(
+        "INSERT INTO member_sessions (token_hash, member_id, expires_at)
+         VALUES (?, ?, datetime('now', ?))
+         RETURNING expires_at",
+    )
+    .bind(hash_token(&token))
+    .bind(member_id)
+    .bind(SESSION_TTL)
+    .fetch_one(pool)
+    .await
+    .server_err()?;
+
+    let member = session_member(pool, &token).await?;
+
+    Ok(MemberSession {
+        token,
+        member,
+        expires_at,
+    })
+}
+
+/// The member a session belongs to. Expired sessions and sessions of removed members
+/// are rejected as unauthorized so the client knows to log in again.
+pub async fn session_member(pool: &SqlitePool, session_token: &str) -> Result<Member, ApiError> {
+    let row = sqlx::query(
+        "SELECT m.id, m.name
+         FROM member_sessions s
+         JOIN members m ON m.id = s.member_id
+         WHERE s.token_hash = ?
+           AND s.expires_at > datetime('now')
+           AND m.deleted_at IS NULL",
+    )
+    .bind(hash_token(session_token))
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+
+    let row = row.ok_or_else(|| ApiError::Unauthorized("Session expired".to_string()))?;
+
+    Ok(Member {
+        id: row.get("id"),
+        name: row.get::<String, _>("name").into(),
+    })
+}
+
+/// Log the member out everywhere, admin page included, e.g. when their password changes.
+pub async fn revoke_member_sessions(
+    conn: &mut SqliteConnection,
+    member_id: MemberId,
+) -> Result<(), ApiError> {
+    sqlx::query("DELETE FROM member_sessions WHERE member_id = ?")
+        .bind(member_id)
+        .execute(&mut *conn)
+        .await
+        .server_err()?;
+    sqlx::query("DELETE FROM admin_sessions WHERE member_id = ?")
+        .bind(member_id)
+        .execute(&mut *conn)
+        .await
+        .server_err()?;
+    Ok(())
+}
+
+/// A random session token. Long enough that storing a plain SHA-256 of it is safe.
+pub fn new_session_token() -> String {
+    Alphanumeric.sample_string(&mut rand::thread_rng(), SESSION_TOKEN_LEN)
+}
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
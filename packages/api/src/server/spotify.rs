@@ -1,100 +1,119 @@
 //! Spotify-related server function implementations.
 
-use dioxus::prelude::ServerFnError;
+use ::spotify::{AlbumSearchItem, AlbumTrackItem, SpotifyLink};
+use sqlx::SqliteConnection;
 
 use crate::api_models::{AlbumTrack, SpotifyAlbumSearchItem};
-
-use super::{ensure_admin_token, get_db, get_spotify_client, IntoServerError};
+use crate::error::ApiError;
+
+use super::{ensure_admin, get_db, get_music_catalog, IntoServerError};
+
+/// A row of `album_tracks`.
+#[derive(sqlx::FromRow)]
+struct CachedTrack {
+    track_id: String,
+    disc_number: i64,
+    track_number: i64,
+    track_name: String,
+    duration_ms: Option<i64>,
+    spotify_url: Option<String>,
+}
 
 pub async fn get_album_tracks_impl(
     album_id: String,
-) -> Result<Vec<AlbumTrack>, ServerFnError> {
+) -> Result<Vec<AlbumTrack>, ApiError> {
     tracing::debug!("get_album_tracks album_id=\"{album_id}\"");
     let pool = get_db().await?;
 
-    let cached: Vec<(String, i64, String, Option<i64>, Option<String>)> = sqlx::query_as(
-        "SELECT track_id, track_number, track_name, duration_ms, spotify_url
-         FROM album_tracks WHERE album_id = ? ORDER BY track_number",
+    let cached: Vec<CachedTrack> = sqlx::query_as(
+        "SELECT track_id, disc_number, track_number, track_name, duration_ms, spotify_url
+         FROM album_tracks WHERE album_id = ? ORDER BY disc_number, track_number",
     )
     .bind(&album_id)
     .fetch_all(pool)
     .await
     .server_err()?;
 
-    if !cached.is_empty() && cached.iter().all(|(_, _, _, _, url)| url.is_some()) {
+    if !cached.is_empty() && cached.iter().all(|t| t.spotify_url.is_some()) {
         return Ok(cached
             .into_iter()
-            .map(
-                |(track_id, track_number, track_name, duration_ms, spotify_url)| AlbumTrack {
-                    track_id,
-                    track_number: track_number as u32,
-                    track_name,
-                    duration_ms,
-                    spotify_url,
-                },
-            )
+            .map(|t| AlbumTrack {
+                track_id: t.track_id,
+                disc_number: t.disc_number as u32,
+                track_number: t.track_number as u32,
+                track_name: t.track_name,
+                duration_ms: t.duration_ms,
+                spotify_url: t.spotify_url,
+            })
             .collect());
     }
 
-    let mut guard = get_spotify_client().await?;
-    let client = guard
-        .as_mut()
-        .ok_or_else(|| ServerFnError::new("Failed to initialize Spotify client"))?;
+    let catalog = get_music_catalog().await?;
 
-    let tracks = client
-        .get_album_tracks(&album_id)
-        .await
-        .server_err()?;
+    let tracks = catalog.get_album_tracks(&album_id).await?;
 
     let mut tx = pool.begin().await.server_err()?;
+    cache_album_tracks(&mut tx, &album_id, &tracks).await?;
+    tx.commit().await.server_err()?;
 
-    for t in &tracks {
+    Ok(tracks
+        .into_iter()
+        .map(|t| AlbumTrack {
+            track_id: t.id,
+            disc_number: t.disc_number,
+            track_number: t.track_number,
+            track_name: t.name,
+            duration_ms: t.duration_ms.map(|d| d as i64),
+            spotify_url: t.spotify_url,
+        })
+        .collect())
+}
+
+/// Store an album's track listing in `album_tracks`.
+pub async fn cache_album_tracks(
+    conn: &mut SqliteConnection,
+    album_id: &str,
+    tracks: &[AlbumTrackItem],
+) -> Result<(), ApiError> {
+    for t in tracks {
         sqlx::query(
             "INSERT OR REPLACE INTO album_tracks
-                (album_id, track_number, track_id, track_name, duration_ms, spotify_url)
-             VALUES (?, ?, ?, ?, ?, ?)",
+                (album_id, disc_number, track_number, track_id, track_name, duration_ms, spotify_url)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
         )
-        .bind(&album_id)
+        .bind(album_id)
+        .bind(t.disc_number as i64)
         .bind(t.track_number as i64)
         .bind(&t.id)
         .bind(&t.name)
         .bind(t.duration_ms.map(|d| d as i64))
         .bind(&t.spotify_url)
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await
         .server_err()?;
     }
 
-    tx.commit().await.server_err()?;
-
-    Ok(tracks
-        .into_iter()
-        .map(|t| AlbumTrack {
-            track_id: t.id,
-            track_number: t.track_number,
-            track_name: t.name,
-            duration_ms: t.duration_ms.map(|d| d as i64),
-            spotify_url: t.spotify_url,
-        })
-        .collect())
+    Ok(())
 }
 
 pub async fn admin_spotify_album_search_impl(
     admin_token: String,
     search_term: &str,
-) -> Result<Vec<SpotifyAlbumSearchItem>, ServerFnError> {
-    ensure_admin_token(&admin_token)?;
+) -> Result<Vec<SpotifyAlbumSearchItem>, ApiError> {
+    ensure_admin(&admin_token).await?;
     tracing::debug!("POST /api/admin/spotify/search query=\"{search_term}\"");
 
-    let mut guard = get_spotify_client().await?;
-    let client = guard
-        .as_mut()
-        .ok_or_else(|| ServerFnError::new("Failed to initialize Spotify client"))?;
+    let catalog = get_music_catalog().await?;
 
-    let albums = client
-        .search_albums(search_term)
-        .await
-        .server_err()?
+    let albums = match ::spotify::parse_link(search_term) {
+        Some(SpotifyLink::Album(album_id)) => {
+            vec![AlbumSearchItem::from(catalog.get_album(&album_id).await?)]
+        }
+        Some(SpotifyLink::Track(track_id)) => vec![catalog.get_track_album(&track_id).await?],
+        None => catalog.search_albums(search_term).await?,
+    };
+
+    let albums = albums
         .into_iter()
         .map(|album| SpotifyAlbumSearchItem {
             id: album.id,
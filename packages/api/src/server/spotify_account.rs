@@ -0,0 +1,456 @@
+//! Connecting a Spotify account (authorization code + PKCE) and exporting the club playlist.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ::spotify::{PkceChallenge, Playlist, SpotifyClient, SpotifyError, UserToken, PLAYLIST_SCOPES};
+use serde_json::{json, Value};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::api_models::{AuditAction, PlaylistExport, SpotifyAccount};
+use crate::error::ApiError;
+
+use super::audit::{record_audit, Actor};
+use super::{ensure_admin, get_db, get_spotify_client, IntoServerError};
+
+const SPOTIFY_REDIRECT_URI_ENV: &str = "SPOTIFY_REDIRECT_URI";
+
+/// How long an authorisation started from the admin page can be completed.
+const AUTH_REQUEST_TTL: &str = "-10 minutes";
+
+/// Refresh the access token when it has less than this many seconds left.
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+
+const PLAYLIST_NAME: &str = "Albumklubben";
+const PLAYLIST_DESCRIPTION: &str =
+    "Den högst betygsatta låten från varje album vi har lyssnat på i Albumklubben.";
+
+pub async fn admin_spotify_authorize_impl(admin_token: String) -> Result<String, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/spotify/authorize");
+
+    let client = get_spotify_client().await?;
+    let redirect_uri = redirect_uri()?;
+    let pkce = PkceChallenge::generate();
+    let state = PkceChallenge::generate_state();
+
+    let pool = get_db().await?;
+
+    sqlx::query("DELETE FROM spotify_auth_requests WHERE created_at < datetime('now', ?)")
+        .bind(AUTH_REQUEST_TTL)
+        .execute(pool)
+        .await
+        .server_err()?;
+
+    sqlx::query(
+        "INSERT INTO spotify_auth_requests
+            (state, code_verifier, started_by, started_by_credential_id, started_by_member_id)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&state)
+    .bind(&pkce.verifier)
+    .bind(&admin.label)
+    .bind(admin.credential_id)
+    .bind(admin.member_id)
+    .execute(pool)
+    .await
+    .server_err()?;
+
+    Ok(client.authorize_url(&redirect_uri, &state, &pkce.challenge, PLAYLIST_SCOPES)?)
+}
+
+/// Complete an authorisation Spotify redirected back with. The single-use `state` is what
+/// ties the callback to an admin who started the flow, so no admin token is needed here.
+pub async fn spotify_complete_authorization_impl(
+    code: String,
+    state: String,
+) -> Result<SpotifyAccount, ApiError> {
+    tracing::info!("POST /api/spotify/callback");
+
+    let pool = get_db().await?;
+    let request = take_auth_request(pool, &state).await?;
+
+    let client = get_spotify_client().await?;
+    let token = client
+        .exchange_authorization_code(&code, &redirect_uri()?, &request.code_verifier)
+        .await?;
+    let refresh_token = token.refresh_token.clone().ok_or_else(|| {
+        ApiError::UpstreamUnavailable("Spotify did not return a refresh token".to_string())
+    })?;
+    let user = client.current_user(&token.access_token).await?;
+
+    let mut tx = pool.begin().await.server_err()?;
+    let before = account_snapshot(&mut tx).await?;
+
+    // Reconnecting the same account keeps its playlist; a different account starts over.
+    sqlx::query(
+        "INSERT INTO spotify_account
+            (id, spotify_user_id, display_name, access_token, refresh_token, expires_at, scope)
+         VALUES (1, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+            playlist_id = CASE WHEN spotify_user_id = excluded.spotify_user_id
+                               THEN playlist_id END,
+            playlist_url = CASE WHEN spotify_user_id = excluded.spotify_user_id
+                                THEN playlist_url END,
+            spotify_user_id = excluded.spotify_user_id,
+            display_name = excluded.display_name,
+            access_token = excluded.access_token,
+            refresh_token = excluded.refresh_token,
+            expires_at = excluded.expires_at,
+            scope = excluded.scope,
+            connected_at = datetime('now')",
+    )
+    .bind(&user.id)
+    .bind(&user.display_name)
+    .bind(&token.access_token)
+    .bind(&refresh_token)
+    .bind(expires_at(&token))
+    .bind(&token.scope)
+    .execute(&mut *tx)
+    .await
+    .server_err()?;
+
+    let after = account_snapshot(&mut tx).await?;
+    record_audit(
+        &mut tx,
+        &request.started_by,
+        AuditAction::ConnectSpotify,
+        before,
+        after,
+    )
+    .await?;
+
+    let account = load_account(&mut tx)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Spotify account was not stored".to_string()))?;
+    tx.commit().await.server_err()?;
+
+    tracing::info!("POST /api/spotify/callback → connected \"{}\"", user.id);
+    Ok(account)
+}
+
+pub async fn admin_spotify_account_impl(
+    admin_token: String,
+) -> Result<Option<SpotifyAccount>, ApiError> {
+    ensure_admin(&admin_token).await?;
+    let pool = get_db().await?;
+    let mut conn = pool.acquire().await.server_err()?;
+    load_account(&mut conn).await
+}
+
+pub async fn admin_spotify_disconnect_impl(admin_token: String) -> Result<(), ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/spotify/disconnect");
+
+    let pool = get_db().await?;
+    let mut tx = pool.begin().await.server_err()?;
+
+    let before = account_snapshot(&mut tx).await?;
+
+    sqlx::query("DELETE FROM spotify_account")
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    if before.is_some() {
+        record_audit(
+            &mut tx,
+            &Actor::admin(&admin),
+            AuditAction::DisconnectSpotify,
+            before,
+            None,
+        )
+        .await?;
+    }
+
+    tx.commit().await.server_err()?;
+    Ok(())
+}
+
+/// Create or update the club playlist: the highest-rated track of every past album, in
+/// meeting order. Ties go to the track with more reviews.
+pub async fn admin_spotify_export_playlist_impl(
+    admin_token: String,
+) -> Result<PlaylistExport, ApiError> {
+    let admin = ensure_admin(&admin_token).await?;
+    tracing::info!("POST /api/admin/spotify/export-playlist");
+
+    let pool = get_db().await?;
+    let client = get_spotify_client().await?;
+    let access_token = user_access_token(pool, client).await?;
+
+    let meeting_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM meetings WHERE is_current = 0")
+            .fetch_one(pool)
+            .await
+            .server_err()?;
+
+    // A score of 0 is a cleared rating, not a vote.
+    let rows = sqlx::query(
+        "SELECT m.id AS meeting_id, tr.track_id, AVG(tr.score) AS avg_score, COUNT(*) AS votes
+         FROM meetings m
+         JOIN track_reviews tr ON tr.meeting_id = m.id
+         WHERE m.is_current = 0 AND tr.score > 0
+         GROUP BY m.id, tr.track_id
+         ORDER BY m.meeting_date ASC, m.id, avg_score DESC, votes DESC, tr.track_id",
+    )
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let mut track_uris: Vec<String> = Vec::new();
+    let mut last_meeting: Option<String> = None;
+    for row in rows {
+        let meeting_id: String = row.get("meeting_id");
+        if last_meeting.as_ref() == Some(&meeting_id) {
+            continue;
+        }
+        track_uris.push(format!(
+            "spotify:track:{}",
+            row.get::<String, _>("track_id")
+        ));
+        last_meeting = Some(meeting_id);
+    }
+
+    let account_row =
+        sqlx::query("SELECT spotify_user_id, playlist_id, playlist_url FROM spotify_account")
+            .fetch_one(pool)
+            .await
+            .server_err()?;
+    let user_id: String = account_row.get("spotify_user_id");
+    let existing_playlist = account_row
+        .get::<Option<String>, _>("playlist_id")
+        .map(|id| Playlist {
+            id,
+            url: account_row
+                .get::<Option<String>, _>("playlist_url")
+                .unwrap_or_default(),
+        });
+
+    let mut playlist = None;
+    if let Some(existing) = existing_playlist {
+        match client
+            .update_playlist_details(
+                &access_token,
+                &existing.id,
+                PLAYLIST_NAME,
+                PLAYLIST_DESCRIPTION,
+            )
+            .await
+        {
+            Ok(()) => playlist = Some(existing),
+            // Deleted on Spotify's side since the last export: make a new one.
+            Err(SpotifyError::NotFound(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let playlist = match playlist {
+        Some(playlist) => playlist,
+        None => {
+            client
+                .create_playlist(&access_token, &user_id, PLAYLIST_NAME, PLAYLIST_DESCRIPTION)
+                .await?
+        }
+    };
+
+    client
+        .replace_playlist_tracks(&access_token, &playlist.id, &track_uris)
+        .await?;
+
+    let track_count = track_uris.len() as u32;
+
+    let mut tx = pool.begin().await.server_err()?;
+
+    sqlx::query("UPDATE spotify_account SET playlist_id = ?, playlist_url = ?")
+        .bind(&playlist.id)
+        .bind(&playlist.url)
+        .execute(&mut *tx)
+        .await
+        .server_err()?;
+
+    record_audit(
+        &mut tx,
+        &Actor::admin(&admin),
+        AuditAction::ExportPlaylist,
+        None,
+        Some(json!({ "playlist_url": playlist.url, "track_count": track_count })),
+    )
+    .await?;
+
+    tx.commit().await.server_err()?;
+    tracing::info!("POST /api/admin/spotify/export-playlist → {track_count} tracks");
+
+    Ok(PlaylistExport {
+        playlist_url: playlist.url,
+        track_count,
+        skipped_albums: (meeting_count as u32).saturating_sub(track_count),
+    })
+}
+
+/// A valid access token for the connected account, refreshing (and storing) it when it is
+/// about to expire. A revoked authorisation disconnects the account.
+async fn user_access_token(pool: &SqlitePool, client: &SpotifyClient) -> Result<String, ApiError> {
+    let row = sqlx::query("SELECT access_token, refresh_token, expires_at FROM spotify_account")
+        .fetch_optional(pool)
+        .await
+        .server_err()?
+        .ok_or_else(|| ApiError::NotFound("No Spotify account is connected".to_string()))?;
+
+    let stored_expires_at: i64 = row.get("expires_at");
+    if stored_expires_at - TOKEN_REFRESH_MARGIN_SECS > unix_now() {
+        return Ok(row.get("access_token"));
+    }
+
+    let refresh_token: String = row.get("refresh_token");
+    let token = match client.refresh_user_token(&refresh_token).await {
+        Ok(token) => token,
+        Err(SpotifyError::Auth(e)) => {
+            tracing::warn!("Spotify refresh token rejected, disconnecting account: {e}");
+            sqlx::query("DELETE FROM spotify_account")
+                .execute(pool)
+                .await
+                .server_err()?;
+            return Err(ApiError::NotFound(
+                "The Spotify authorisation was revoked, connect the account again".to_string(),
+            ));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    sqlx::query(
+        "UPDATE spotify_account
+         SET access_token = ?, refresh_token = COALESCE(?, refresh_token), expires_at = ?,
+             scope = COALESCE(?, scope)",
+    )
+    .bind(&token.access_token)
+    .bind(&token.refresh_token)
+    .bind(expires_at(&token))
+    .bind(&token.scope)
+    .execute(pool)
+    .await
+    .server_err()?;
+
+    Ok(token.access_token)
+}
+
+/// An authorisation started from the admin page, waiting for Spotify's callback.
+struct AuthRequest {
+    code_verifier: String,
+    started_by: Actor,
+}
+
+/// Use up the authorisation request `state` was issued for. Each state works once, and
+/// only within [`AUTH_REQUEST_TTL`] of being issued.
+async fn take_auth_request(pool: &SqlitePool, state: &str) -> Result<AuthRequest, ApiError> {
+    let row = sqlx::query(
+        "DELETE FROM spotify_auth_requests
+         WHERE state = ? AND created_at >= datetime('now', ?)
+         RETURNING code_verifier, started_by, started_by_credential_id, started_by_member_id",
+    )
+    .bind(state)
+    .bind(AUTH_REQUEST_TTL)
+    .fetch_optional(pool)
+    .await
+    .server_err()?;
+
+    let row = row.ok_or_else(|| {
+        ApiError::Unauthorized("Unknown or expired Spotify authorisation, start again".to_string())
+    })?;
+
+    Ok(AuthRequest {
+        code_verifier: row.get("code_verifier"),
+        // Requests started before authorisations were attributed have no `started_by`.
+        started_by: Actor::admin_by(
+            row.get::<Option<String>, _>("started_by")
+                .unwrap_or_else(|| "unknown".to_string()),
+            row.get("started_by_credential_id"),
+            row.get("started_by_member_id"),
+        ),
+    })
+}
+
+async fn load_account(conn: &mut SqliteConnection) -> Result<Option<SpotifyAccount>, ApiError> {
+    let row = sqlx::query(
+        "SELECT spotify_user_id, display_name, playlist_url, connected_at FROM spotify_account",
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .server_err()?;
+
+    Ok(row.map(|row| SpotifyAccount {
+        user_id: row.get("spotify_user_id"),
+        display_name: row.get("display_name"),
+        playlist_url: row.get("playlist_url"),
+        connected_at: row.get("connected_at"),
+    }))
+}
+
+/// The connected account as recorded in the audit log. Tokens are left out.
+async fn account_snapshot(conn: &mut SqliteConnection) -> Result<Option<Value>, ApiError> {
+    Ok(load_account(conn).await?.map(|account| json!(account)))
+}
+
+fn redirect_uri() -> Result<String, ApiError> {
+    std::env::var(SPOTIFY_REDIRECT_URI_ENV).map_err(|_| {
+        ApiError::Internal("SPOTIFY_REDIRECT_URI is not configured on the server".to_string())
+    })
+}
+
+fn expires_at(token: &UserToken) -> i64 {
+    unix_now() + token.expires_in as i64
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_pool;
+
+    async fn insert_auth_request(pool: &SqlitePool, state: &str, age: &str) {
+        sqlx::query(
+            "INSERT INTO spotify_auth_requests (state, code_verifier, created_at)
+             VALUES (?, ?, datetime('now', ?))",
+        )
+        .bind(state)
+        .bind(format!("verifier-{state}"))
+        .bind(age)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn state_is_used_once() {
+        let pool = test_pool().await;
+        insert_auth_request(&pool, "state", "-1 minutes").await;
+
+        let request = take_auth_request(&pool, "state").await.unwrap();
+        assert_eq!(request.code_verifier, "verifier-state");
+        assert!(matches!(
+            take_auth_request(&pool, "state").await,
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unknown_or_expired_state_is_rejected() {
+        let pool = test_pool().await;
+        insert_auth_request(&pool, "state", "-1 minutes").await;
+        insert_auth_request(&pool, "old", "-11 minutes").await;
+
+        assert!(matches!(
+            take_auth_request(&pool, "other").await,
+            Err(ApiError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            take_auth_request(&pool, "old").await,
+            Err(ApiError::Unauthorized(_))
+        ));
+        assert!(take_auth_request(&pool, "state").await.is_ok());
+    }
+}
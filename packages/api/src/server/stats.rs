@@ -0,0 +1,366 @@
+//! Club-wide statistics, computed with SQL aggregates.
+
+use std::collections::HashMap;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::api_models::{
+    AlbumStat, ClubStats, Compatibility, CompatibilityBasis, Member, MemberId, MemberPair,
+    PickerStat, ReviewerStat, TrackStat, MIN_COMPATIBILITY_OVERLAP,
+};
+use crate::error::ApiError;
+
+use super::{get_db, members::active_members, IntoServerError};
+
+/// Meetings (`m`) whose reviews everyone may see: ones an admin has revealed, and past ones
+/// whose review window has closed, as `reviews_for` decides. Keeps the stats from leaking
+/// reviews that are still hidden.
+const REVEALED_MEETING: &str = "(m.reviews_revealed_at IS NOT NULL
+    OR (m.is_current = 0
+        AND (m.reviews_locked_at IS NOT NULL
+             OR datetime(m.meeting_date || ' ' || COALESCE(m.meeting_time, '00:00'),
+                         '+' || m.review_close_days || ' days')
+                <= datetime('now', 'localtime'))))";
+
+/// How many tracks to list as best and worst.
+const TRACK_LIST_LEN: i64 = 10;
+/// Tracks scored by fewer members than this aren't listed as best or worst.
+const MIN_TRACK_REVIEWS: i64 = 2;
+
+pub async fn get_stats_impl() -> Result<ClubStats, ApiError> {
+    tracing::debug!("GET /api/stats");
+
+    let pool = get_db().await?;
+
+    Ok(ClubStats {
+        albums: album_stats(pool).await?,
+        pickers: picker_stats(pool).await?,
+        reviewers: reviewer_stats(pool).await?,
+        best_tracks: track_stats(pool, "DESC").await?,
+        worst_tracks: track_stats(pool, "ASC").await?,
+        album_score_distribution: score_distribution(pool, "album_reviews").await?,
+        track_score_distribution: score_distribution(pool, "track_reviews").await?,
+    })
+}
+
+async fn album_stats(pool: &SqlitePool) -> Result<Vec<AlbumStat>, ApiError> {
+    let rows = sqlx::query(&format!(
+        "SELECT m.id, m.album_id, m.album_name, m.album_artist, p.name AS picker,
+                m.meeting_date, AVG(r.score) AS average, COUNT(*) AS review_count
+         FROM meetings m
+         JOIN members p ON p.id = m.picker_id
+         JOIN album_reviews r ON r.meeting_id = m.id
+         WHERE r.score > 0 AND {REVEALED_MEETING}
+         GROUP BY m.id
+         ORDER BY average DESC, review_count DESC, m.meeting_date DESC"
+    ))
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| AlbumStat {
+            meeting_id: row.get("id"),
+            album_id: row.get("album_id"),
+            album_name: row.get("album_name"),
+            album_artist: row.get("album_artist"),
+            picker: row.get("picker"),
+            meeting_date: row.get("meeting_date"),
+            average: row.get::<f64, _>("average") as f32,
+            review_count: row.get::<i64, _>("review_count") as u32,
+        })
+        .collect())
+}
+
+async fn picker_stats(pool: &SqlitePool) -> Result<Vec<PickerStat>, ApiError> {
+    let rows = sqlx::query(&format!(
+        "SELECT p.id, p.name, COUNT(DISTINCT m.id) AS albums_picked,
+                AVG(r.score) AS average_received, COUNT(r.score) AS review_count
+         FROM meetings m
+         JOIN members p ON p.id = m.picker_id
+         LEFT JOIN album_reviews r ON r.meeting_id = m.id AND r.score > 0
+         WHERE {REVEALED_MEETING}
+         GROUP BY p.id
+         ORDER BY average_received IS NULL, average_received DESC, albums_picked DESC"
+    ))
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PickerStat {
+            member: Member {
+                id: row.get("id"),
+                name: row.get::<String, _>("name").into(),
+            },
+            albums_picked: row.get::<i64, _>("albums_picked") as u32,
+            average_received: row
+                .get::<Option<f64>, _>("average_received")
+                .map(|avg| avg as f32),
+            review_count: row.get::<i64, _>("review_count") as u32,
+        })
+        .collect())
+}
+
+async fn reviewer_stats(pool: &SqlitePool) -> Result<Vec<ReviewerStat>, ApiError> {
+    let track_counts: HashMap<MemberId, i64> = sqlx::query_as(&format!(
+        "SELECT r.member_id, COUNT(*)
+         FROM track_reviews r
+         JOIN meetings m ON m.id = r.meeting_id
+         WHERE r.score > 0 AND {REVEALED_MEETING}
+         GROUP BY r.member_id"
+    ))
+    .fetch_all(pool)
+    .await
+    .server_err()?
+    .into_iter()
+    .collect();
+
+    let rows = sqlx::query(&format!(
+        "SELECT r.member_id, p.name, AVG(r.score) AS average_given, COUNT(*) AS album_reviews
+         FROM album_reviews r
+         JOIN meetings m ON m.id = r.meeting_id
+         JOIN members p ON p.id = r.member_id
+         WHERE r.score > 0 AND {REVEALED_MEETING}
+         GROUP BY r.member_id
+         ORDER BY album_reviews DESC, p.name"
+    ))
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let member_id: MemberId = row.get("member_id");
+            ReviewerStat {
+                member: Member {
+                    id: member_id,
+                    name: row.get::<String, _>("name").into(),
+                },
+                average_given: row.get::<f64, _>("average_given") as f32,
+                album_reviews: row.get::<i64, _>("album_reviews") as u32,
+                track_reviews: track_counts.get(&member_id).copied().unwrap_or(0) as u32,
+            }
+        })
+        .collect())
+}
+
+/// The best (`order` "DESC") or worst ("ASC") tracks by average score.
+async fn track_stats(pool: &SqlitePool, order: &str) -> Result<Vec<TrackStat>, ApiError> {
+    let rows = sqlx::query(&format!(
+        "SELECT r.meeting_id, t.track_name, m.album_name, m.album_artist,
+                AVG(r.score) AS average, COUNT(*) AS review_count
+         FROM track_reviews r
+         JOIN meetings m ON m.id = r.meeting_id
+         JOIN album_tracks t ON t.album_id = m.album_id AND t.track_id = r.track_id
+         WHERE r.score > 0 AND {REVEALED_MEETING}
+         GROUP BY r.meeting_id, r.track_id
+         HAVING COUNT(*) >= ?
+         ORDER BY average {order}, review_count DESC
+         LIMIT ?"
+    ))
+    .bind(MIN_TRACK_REVIEWS)
+    .bind(TRACK_LIST_LEN)
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TrackStat {
+            meeting_id: row.get("meeting_id"),
+            track_name: row.get("track_name"),
+            album_name: row.get("album_name"),
+            album_artist: row.get("album_artist"),
+            average: row.get::<f64, _>("average") as f32,
+            review_count: row.get::<i64, _>("review_count") as u32,
+        })
+        .collect())
+}
+
+/// How often each score from 1 to 10 was given in `table` (`album_reviews` or
+/// `track_reviews`).
+async fn score_distribution(pool: &SqlitePool, table: &str) -> Result<Vec<u32>, ApiError> {
+    let counts: Vec<(i64, i64)> = sqlx::query_as(&format!(
+        "SELECT r.score, COUNT(*)
+         FROM {table} r
+         JOIN meetings m ON m.id = r.meeting_id
+         WHERE r.score > 0 AND {REVEALED_MEETING}
+         GROUP BY r.score"
+    ))
+    .fetch_all(pool)
+    .await
+    .server_err()?;
+
+    let mut distribution = vec![0; 10];
+    for (score, count) in counts {
+        if let Some(slot) = distribution.get_mut(score as usize - 1) {
+            *slot = count as u32;
+        }
+    }
+    Ok(distribution)
+}
+
+/// Pairwise agreement between active members over the albums or tracks both have scored.
+pub async fn get_compatibility_impl(
+    basis: CompatibilityBasis,
+    min_overlap: u32,
+) -> Result<Compatibility, ApiError> {
+    tracing::debug!("get_compatibility basis={basis:?} min_overlap={min_overlap}");
+    if min_overlap < MIN_COMPATIBILITY_OVERLAP {
+        return Err(ApiError::Validation(format!(
+            "The overlap threshold must be at least {MIN_COMPATIBILITY_OVERLAP}"
+        )));
+    }
+
+    let pool = get_db().await?;
+    let mut conn = pool.acquire().await.server_err()?;
+    let members = active_members(&mut conn).await?;
+
+    // Every pair of scores two members gave the same album or track.
+    let query = match basis {
+        CompatibilityBasis::Albums => format!(
+            "SELECT x.member_id, y.member_id, x.score, y.score
+             FROM album_reviews x
+             JOIN album_reviews y ON y.meeting_id = x.meeting_id AND y.member_id > x.member_id
+             JOIN meetings m ON m.id = x.meeting_id
+             WHERE x.score > 0 AND y.score > 0 AND {REVEALED_MEETING}"
+        ),
+        CompatibilityBasis::Tracks => format!(
+            "SELECT x.member_id, y.member_id, x.score, y.score
+             FROM track_reviews x
+             JOIN track_reviews y ON y.meeting_id = x.meeting_id AND y.track_id = x.track_id
+                                 AND y.member_id > x.member_id
+             JOIN meetings m ON m.id = x.meeting_id
+             WHERE x.score > 0 AND y.score > 0 AND {REVEALED_MEETING}"
+        ),
+    };
+    let rows: Vec<(MemberId, MemberId, i64, i64)> = sqlx::query_as(&query)
+        .fetch_all(&mut *conn)
+        .await
+        .server_err()?;
+
+    let mut scores: HashMap<(MemberId, MemberId), Vec<(f64, f64)>> = HashMap::new();
+    for (a, b, score_a, score_b) in rows {
+        scores
+            .entry((a, b))
+            .or_default()
+            .push((score_a as f64, score_b as f64));
+    }
+
+    let is_active = |id: MemberId| members.iter().any(|m| m.id == id);
+    let mut pairs: Vec<MemberPair> = scores
+        .into_iter()
+        .filter(|((a, b), scored)| {
+            scored.len() >= min_overlap as usize && is_active(*a) && is_active(*b)
+        })
+        .map(|((a, b), scored)| {
+            let (xs, ys): (Vec<f64>, Vec<f64>) = scored.into_iter().unzip();
+            let mean_abs_diff =
+                xs.iter().zip(&ys).map(|(x, y)| (x - y).abs()).sum::<f64>() / xs.len() as f64;
+            MemberPair {
+                a,
+                b,
+                overlap: xs.len() as u32,
+                pearson: pearson(&xs, &ys).map(|r| r as f32),
+                spearman: pearson(&ranks(&xs), &ranks(&ys)).map(|r| r as f32),
+                mean_abs_diff: mean_abs_diff as f32,
+            }
+        })
+        .collect();
+    pairs.sort_by_key(|pair| (pair.a, pair.b));
+
+    Ok(Compatibility {
+        basis,
+        min_overlap,
+        members,
+        pairs,
+    })
+}
+
+/// Pearson correlation, or `None` if either side has no variance.
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x * var_y).sqrt())
+}
+
+/// 1-based ranks, ties sharing the average of their ranks, for Spearman's correlation.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Positions start..end hold ties; their ranks are start+1 ..= end.
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("a correlation");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn pearson_of_linear_scores() {
+        assert_close(pearson(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]), 1.0);
+        assert_close(pearson(&[1.0, 2.0, 3.0], &[5.0, 3.0, 1.0]), -1.0);
+        assert_close(pearson(&[1.0, 2.0, 3.0, 4.0], &[1.0, 3.0, 2.0, 4.0]), 0.8);
+    }
+
+    #[test]
+    fn pearson_without_variance() {
+        assert_eq!(pearson(&[3.0, 3.0, 3.0], &[1.0, 2.0, 5.0]), None);
+        assert_eq!(pearson(&[1.0, 2.0, 5.0], &[4.0, 4.0, 4.0]), None);
+        assert_eq!(pearson(&[2.0], &[4.0]), None);
+        assert_eq!(pearson(&[], &[]), None);
+    }
+
+    #[test]
+    fn ranks_share_ties() {
+        assert_eq!(ranks(&[30.0, 10.0, 20.0]), vec![3.0, 1.0, 2.0]);
+        assert_eq!(ranks(&[2.0, 1.0, 2.0, 3.0]), vec![2.5, 1.0, 2.5, 4.0]);
+        assert_eq!(ranks(&[4.0, 4.0, 4.0]), vec![2.0, 2.0, 2.0]);
+        assert_eq!(ranks(&[]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn spearman_ignores_scale() {
+        let xs = [1.0, 2.0, 3.0, 5.0];
+        let ys = [1.0, 4.0, 9.0, 25.0];
+        assert_close(pearson(&ranks(&xs), &ranks(&ys)), 1.0);
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+
+use crate::{
+    AlbumDetails, AlbumSearchItem, AlbumTrackItem, FixtureCatalog, SpotifyClient, SpotifyError,
+};
+
+const MUSIC_CATALOG_ENV: &str = "MUSIC_CATALOG";
+const MUSIC_CATALOG_FIXTURE_ENV: &str = "MUSIC_CATALOG_FIXTURE";
+
+/// A source of album and track metadata.
+///
+/// Implemented by [`SpotifyClient`] for production and by [`FixtureCatalog`] for
+/// offline development and CI.
+#[async_trait]
+pub trait MusicCatalog: Send + Sync {
+    /// Free-text album search. An empty (or whitespace-only) query returns no results.
+    async fn search_albums(&self, query: &str) -> Result<Vec<AlbumSearchItem>, SpotifyError>;
+
+    /// The track listing of an album.
+    async fn get_album_tracks(&self, album_id: &str) -> Result<Vec<AlbumTrackItem>, SpotifyError>;
+
+    /// Full metadata for a single album by its Spotify ID.
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetails, SpotifyError>;
+
+    /// The album a track belongs to, by the track's Spotify ID.
+    async fn get_track_album(&self, track_id: &str) -> Result<AlbumSearchItem, SpotifyError>;
+}
+
+/// Build the catalog selected by `MUSIC_CATALOG`.
+///
+/// - `spotify` (default): the real Web API, configured through `SpotifyClient::from_env`.
+/// - `fixture`: a JSON file at `MUSIC_CATALOG_FIXTURE`, see [`FixtureCatalog`].
+pub fn catalog_from_env() -> Result<Box<dyn MusicCatalog>, SpotifyError> {
+    let kind = std::env::var(MUSIC_CATALOG_ENV).unwrap_or_else(|_| "spotify".to_string());
+
+    match kind.trim().to_ascii_lowercase().as_str() {
+        "" | "spotify" => Ok(Box::new(SpotifyClient::from_env()?)),
+        "fixture" => {
+            let path = std::env::var(MUSIC_CATALOG_FIXTURE_ENV).map_err(|_| {
+                SpotifyError::Config(
+                    "MUSIC_CATALOG_FIXTURE must be set when MUSIC_CATALOG=fixture".to_string(),
+                )
+            })?;
+            Ok(Box::new(FixtureCatalog::from_file(&path)?))
+        }
+        other => Err(SpotifyError::Config(format!(
+            "Unknown MUSIC_CATALOG \"{other}\" (expected \"spotify\" or \"fixture\")"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The only test touching these variables, so it doesn't race with others.
+    #[test]
+    fn fixture_backend_needs_a_path() {
+        std::env::set_var(MUSIC_CATALOG_ENV, "fixture");
+        std::env::remove_var(MUSIC_CATALOG_FIXTURE_ENV);
+        let missing_path = catalog_from_env().err().map(|e| e.to_string());
+
+        std::env::set_var(MUSIC_CATALOG_ENV, "nonsense");
+        let unknown_kind = catalog_from_env().err().map(|e| e.to_string());
+
+        std::env::set_var(
+            MUSIC_CATALOG_FIXTURE_ENV,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/catalog.json"),
+        );
+        std::env::set_var(MUSIC_CATALOG_ENV, " Fixture ");
+        let with_path = catalog_from_env();
+
+        std::env::remove_var(MUSIC_CATALOG_ENV);
+        std::env::remove_var(MUSIC_CATALOG_FIXTURE_ENV);
+
+        assert!(missing_path.is_some_and(|e| e.contains("MUSIC_CATALOG_FIXTURE")));
+        assert!(unknown_kind.is_some_and(|e| e.contains("nonsense")));
+        assert!(with_path.is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// Everything that can go wrong talking to a [`MusicCatalog`](crate::MusicCatalog).
+#[derive(Debug)]
+pub enum SpotifyError {
+    /// The catalog is misconfigured: missing credentials, an unreadable fixture or an
+    /// unknown `MUSIC_CATALOG`.
+    Config(String),
+    /// Spotify rejected our client credentials or access token.
+    Auth(String),
+    /// The requested album or track doesn't exist, or the ID is malformed.
+    NotFound(String),
+    /// Spotify was still rate limiting us after retrying. `retry_after` is the last
+    /// `Retry-After` it sent, if any.
+    RateLimited { retry_after: Option<Duration> },
+    /// Spotify couldn't be reached or kept answering with server errors.
+    Unavailable(String),
+    /// Any other unexpected response, e.g. a body that doesn't parse.
+    Unexpected(String),
+}
+
+impl SpotifyError {
+    /// Classify a failed response by its status code.
+    pub(crate) fn from_status(
+        status: reqwest::StatusCode,
+        retry_after: Option<Duration>,
+        message: String,
+    ) -> Self {
+        match status {
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { retry_after },
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::NOT_FOUND => {
+                Self::NotFound(message)
+            }
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                Self::Auth(message)
+            }
+            status if status.is_server_error() => Self::Unavailable(message),
+            _ => Self::Unexpected(message),
+        }
+    }
+}
+
+impl std::fmt::Display for SpotifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config(message)
+            | Self::Auth(message)
+            | Self::NotFound(message)
+            | Self::Unavailable(message)
+            | Self::Unexpected(message) => f.write_str(message),
+            Self::RateLimited {
+                retry_after: Some(delay),
+            } => write!(
+                f,
+                "Rate limited by Spotify, retry after {}s",
+                delay.as_secs()
+            ),
+            Self::RateLimited { retry_after: None } => f.write_str("Rate limited by Spotify"),
+        }
+    }
+}
+
+impl std::error::Error for SpotifyError {}
@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::{
+    AlbumDetails, AlbumImage, AlbumSearchItem, AlbumTrackItem, MusicCatalog, SpotifyError,
+};
+
+const SEARCH_LIMIT: usize = 10;
+
+/// A catalog backed by a JSON file, for running without Spotify credentials.
+///
+/// The file holds a list of albums with their tracks:
+///
+/// ```json
+/// {
+///   "albums": [
+///     {
+///       "id": "fixture-album-1",
+///       "name": "Album",
+///       "artists": "Artist",
+///       "image_url": null,
+///       "spotify_url": "https://open.spotify.com/album/fixture-album-1",
+///       "release_date": "1999-01-01",
+///       "label": "Label",
+///       "genres": ["jazz"],
+///       "tracks": [
+///         { "id": "fixture-track-1", "name": "Track", "disc_number": 1, "track_number": 1, "duration_ms": 180000 }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// All metadata beyond id, name, artists and `spotify_url` is optional.
+pub struct FixtureCatalog {
+    albums: Vec<FixtureAlbum>,
+}
+
+impl FixtureCatalog {
+    pub fn from_file(path: &str) -> Result<Self, SpotifyError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            SpotifyError::Config(format!("Failed to read catalog fixture {path}: {e}"))
+        })?;
+
+        Self::from_json(&contents)
+            .map_err(|e| SpotifyError::Config(format!("Invalid catalog fixture {path}: {e}")))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SpotifyError> {
+        let file: FixtureFile =
+            serde_json::from_str(json).map_err(|e| SpotifyError::Config(e.to_string()))?;
+
+        Ok(Self {
+            albums: file.albums,
+        })
+    }
+
+    fn find_album(&self, album_id: &str) -> Result<&FixtureAlbum, SpotifyError> {
+        self.albums
+            .iter()
+            .find(|album| album.id == album_id)
+            .ok_or_else(|| {
+                SpotifyError::NotFound(format!("Album \"{album_id}\" not found in fixture"))
+            })
+    }
+}
+
+#[async_trait]
+impl MusicCatalog for FixtureCatalog {
+    async fn search_albums(&self, query: &str) -> Result<Vec<AlbumSearchItem>, SpotifyError> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(self
+            .albums
+            .iter()
+            .filter(|album| {
+                let haystack = format!("{} {}", album.name, album.artists).to_lowercase();
+                terms.iter().all(|term| haystack.contains(term))
+            })
+            .take(SEARCH_LIMIT)
+            .map(FixtureAlbum::to_search_item)
+            .collect())
+    }
+
+    async fn get_album_tracks(&self, album_id: &str) -> Result<Vec<AlbumTrackItem>, SpotifyError> {
+        Ok(self.find_album(album_id)?.track_items())
+    }
+
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetails, SpotifyError> {
+        let album = self.find_album(album_id)?;
+
+        Ok(AlbumDetails {
+            id: album.id.clone(),
+            name: album.name.clone(),
+            artists: album.artists.clone(),
+            artist_ids: album.artist_ids.clone(),
+            release_date: album.release_date.clone(),
+            label: album.label.clone(),
+            genres: album.genres.clone(),
+            total_tracks: album.tracks.len() as u32,
+            total_duration_ms: album.tracks.iter().filter_map(|t| t.duration_ms).sum(),
+            upc: album.upc.clone(),
+            images: album
+                .image_url
+                .iter()
+                .map(|url| AlbumImage {
+                    url: url.clone(),
+                    width: None,
+                    height: None,
+                })
+                .collect(),
+            spotify_url: album.spotify_url.clone(),
+            tracks: album.track_items(),
+        })
+    }
+
+    async fn get_track_album(&self, track_id: &str) -> Result<AlbumSearchItem, SpotifyError> {
+        self.albums
+            .iter()
+            .find(|album| album.tracks.iter().any(|track| track.id == track_id))
+            .map(FixtureAlbum::to_search_item)
+            .ok_or_else(|| {
+                SpotifyError::NotFound(format!("Track \"{track_id}\" not found in fixture"))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureFile {
+    albums: Vec<FixtureAlbum>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureAlbum {
+    id: String,
+    name: String,
+    artists: String,
+    #[serde(default)]
+    image_url: Option<String>,
+    spotify_url: String,
+    #[serde(default)]
+    artist_ids: Vec<String>,
+    #[serde(default)]
+    release_date: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    genres: Vec<String>,
+    #[serde(default)]
+    upc: Option<String>,
+    #[serde(default)]
+    tracks: Vec<FixtureTrack>,
+}
+
+impl FixtureAlbum {
+    fn to_search_item(&self) -> AlbumSearchItem {
+        AlbumSearchItem {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            artists: self.artists.clone(),
+            image_url: self.image_url.clone(),
+            spotify_url: self.spotify_url.clone(),
+        }
+    }
+
+    fn track_items(&self) -> Vec<AlbumTrackItem> {
+        self.tracks
+            .iter()
+            .map(|t| AlbumTrackItem {
+                id: t.id.clone(),
+                name: t.name.clone(),
+                disc_number: t.disc_number,
+                track_number: t.track_number,
+                duration_ms: t.duration_ms,
+                spotify_url: t.spotify_url.clone(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureTrack {
+    id: String,
+    name: String,
+    #[serde(default = "crate::default_disc_number")]
+    disc_number: u32,
+    track_number: u32,
+    #[serde(default)]
+    duration_ms: Option<u64>,
+    #[serde(default)]
+    spotify_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"{
+        "albums": [
+            {
+                "id": "album-1",
+                "name": "Kind of Blue",
+                "artists": "Miles Davis",
+                "spotify_url": "https://open.spotify.com/album/album-1",
+                "tracks": [
+                    { "id": "track-1", "name": "So What", "track_number": 1, "duration_ms": 562000 },
+                    { "id": "track-2", "name": "Freddie Freeloader", "track_number": 2 }
+                ]
+            },
+            {
+                "id": "album-2",
+                "name": "Blue Train",
+                "artists": "John Coltrane",
+                "image_url": "https://example.com/blue-train.jpg",
+                "spotify_url": "https://open.spotify.com/album/album-2"
+            }
+        ]
+    }"#;
+
+    fn catalog() -> FixtureCatalog {
+        FixtureCatalog::from_json(FIXTURE).expect("valid fixture")
+    }
+
+    fn ids(albums: &[AlbumSearchItem]) -> Vec<&str> {
+        albums.iter().map(|album| album.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn search_matches_every_term_in_name_or_artist() {
+        let catalog = catalog();
+
+        assert_eq!(
+            ids(&catalog.search_albums("blue").await.unwrap()),
+            ["album-1", "album-2"]
+        );
+        assert_eq!(
+            ids(&catalog.search_albums("BLUE miles").await.unwrap()),
+            ["album-1"]
+        );
+        assert_eq!(
+            ids(&catalog.search_albums("coltrane train").await.unwrap()),
+            ["album-2"]
+        );
+        assert!(catalog
+            .search_albums("blue bayou")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(catalog.search_albums("   ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn album_tracks_in_fixture_order() {
+        let catalog = catalog();
+
+        let tracks = catalog.get_album_tracks("album-1").await.unwrap();
+        let names: Vec<&str> = tracks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["So What", "Freddie Freeloader"]);
+        assert_eq!(tracks[0].duration_ms, Some(562000));
+        assert_eq!(tracks[1].duration_ms, None);
+
+        assert!(catalog
+            .get_album_tracks("album-2")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            catalog.get_album_tracks("missing").await,
+            Err(SpotifyError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn album_by_id() {
+        let catalog = catalog();
+
+        let album = catalog.get_album("album-2").await.unwrap();
+        assert_eq!(album.name, "Blue Train");
+        assert_eq!(album.artists, "John Coltrane");
+        let images: Vec<&str> = album.images.iter().map(|i| i.url.as_str()).collect();
+        assert_eq!(images, ["https://example.com/blue-train.jpg"]);
+
+        let album = catalog.get_album("album-1").await.unwrap();
+        assert_eq!(album.total_tracks, 2);
+        assert_eq!(album.total_duration_ms, 562000);
+        assert!(album.images.is_empty());
+
+        assert!(matches!(
+            catalog.get_album("missing").await,
+            Err(SpotifyError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn bundled_fixture_loads() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/catalog.json");
+        assert!(FixtureCatalog::from_file(path).is_ok());
+        assert!(FixtureCatalog::from_file("does/not/exist.json").is_err());
+        assert!(FixtureCatalog::from_json("{ \"albums\": 1 }").is_err());
+    }
+}
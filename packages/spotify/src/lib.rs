@@ -1,13 +1,47 @@
-use serde::Deserialize;
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize};
 use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+mod catalog;
+mod error;
+mod fixture;
+mod link;
+mod pkce;
+#[cfg(test)]
+mod stand_in;
+mod user;
+
+pub use catalog::{catalog_from_env, MusicCatalog};
+pub use error::SpotifyError;
+pub use fixture::FixtureCatalog;
+pub use link::{parse_album_id, parse_link, SpotifyLink};
+pub use pkce::PkceChallenge;
+pub use user::{Playlist, SpotifyUser, UserToken, PLAYLIST_SCOPES};
 
 const SPOTIFY_CLIENT_ID_ENV: &str = "SPOTIFY_CLIENT_ID";
 const SPOTIFY_CLIENT_SECRET_ENV: &str = "SPOTIFY_CLIENT_SECRET";
+const SPOTIFY_API_BASE_URL_ENV: &str = "SPOTIFY_API_BASE_URL";
+const SPOTIFY_ACCOUNTS_BASE_URL_ENV: &str = "SPOTIFY_ACCOUNTS_BASE_URL";
+
+const DEFAULT_API_BASE_URL: &str = "https://api.spotify.com";
+const DEFAULT_ACCOUNTS_BASE_URL: &str = "https://accounts.spotify.com";
+
+/// Largest page size the album tracks endpoint accepts.
+const ALBUM_TRACKS_PAGE_SIZE: usize = 50;
+
+/// Total attempts per Web API call, including the first one.
+const MAX_ATTEMPTS: u32 = 4;
+/// Base delay for exponential backoff when Spotify doesn't send `Retry-After`.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longest `Retry-After` we are willing to wait out inside a single request.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlbumTrackItem {
     pub id: String,
     pub name: String,
+    pub disc_number: u32,
     pub track_number: u32,
     pub duration_ms: Option<u64>,
     pub spotify_url: Option<String>,
@@ -22,248 +56,375 @@ pub struct AlbumSearchItem {
     pub spotify_url: String,
 }
 
-#[derive(Debug)]
-pub struct SpotifyError(String);
-
-impl std::fmt::Display for SpotifyError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
-    }
+/// Full album metadata as returned by the album endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumDetails {
+    pub id: String,
+    pub name: String,
+    /// Artist names joined with ", ", as in [`AlbumSearchItem::artists`].
+    pub artists: String,
+    pub artist_ids: Vec<String>,
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD` depending on how precisely Spotify knows it.
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub genres: Vec<String>,
+    pub total_tracks: u32,
+    /// Sum of all track durations.
+    pub total_duration_ms: u64,
+    pub upc: Option<String>,
+    /// All available cover sizes, largest first.
+    pub images: Vec<AlbumImage>,
+    pub spotify_url: String,
+    /// The full track listing, as returned by [`SpotifyClient::get_album_tracks`].
+    pub tracks: Vec<AlbumTrackItem>,
 }
 
-impl std::error::Error for SpotifyError {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
 
 pub struct SpotifyClient {
     http_client: reqwest::Client,
     client_id: String,
     client_secret: String,
-    access_token: Option<String>,
-    access_token_expires_at: Option<Instant>,
+    api_base_url: String,
+    accounts_base_url: String,
+    /// Shared by all in-flight requests; held while refreshing so concurrent callers
+    /// wait for a single token request instead of each starting their own.
+    access_token: Mutex<Option<AccessToken>>,
+}
+
+struct AccessToken {
+    value: String,
+    expires_at: Instant,
 }
 
 impl SpotifyClient {
+    pub fn new(client_id: String, client_secret: String) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            client_id,
+            client_secret,
+            api_base_url: DEFAULT_API_BASE_URL.to_string(),
+            accounts_base_url: DEFAULT_ACCOUNTS_BASE_URL.to_string(),
+            access_token: Mutex::new(None),
+        }
+    }
+
+    /// Point the client at a different Web API and accounts host, e.g. a local stand-in.
+    pub fn with_base_urls(mut self, api_base_url: &str, accounts_base_url: &str) -> Self {
+        self.api_base_url = api_base_url.trim_end_matches('/').to_string();
+        self.accounts_base_url = accounts_base_url.trim_end_matches('/').to_string();
+        self
+    }
+
     pub fn from_env() -> Result<Self, SpotifyError> {
         let client_id = std::env::var(SPOTIFY_CLIENT_ID_ENV).map_err(|_| {
-            SpotifyError("SPOTIFY_CLIENT_ID is not configured on the server".to_string())
+            SpotifyError::Config("SPOTIFY_CLIENT_ID is not configured on the server".to_string())
         })?;
         let client_secret = std::env::var(SPOTIFY_CLIENT_SECRET_ENV).map_err(|_| {
-            SpotifyError("SPOTIFY_CLIENT_SECRET is not configured on the server".to_string())
+            SpotifyError::Config(
+                "SPOTIFY_CLIENT_SECRET is not configured on the server".to_string(),
+            )
         })?;
 
-        Ok(Self {
-            http_client: reqwest::Client::new(),
-            client_id,
-            client_secret,
-            access_token: None,
-            access_token_expires_at: None,
-        })
+        let api_base_url = std::env::var(SPOTIFY_API_BASE_URL_ENV)
+            .unwrap_or_else(|_| DEFAULT_API_BASE_URL.to_string());
+        let accounts_base_url = std::env::var(SPOTIFY_ACCOUNTS_BASE_URL_ENV)
+            .unwrap_or_else(|_| DEFAULT_ACCOUNTS_BASE_URL.to_string());
+
+        Ok(Self::new(client_id, client_secret).with_base_urls(&api_base_url, &accounts_base_url))
     }
 
+    /// Fetch every track on an album, following pagination until Spotify reports no next page.
     pub async fn get_album_tracks(
-        &mut self,
+        &self,
         album_id: &str,
     ) -> Result<Vec<AlbumTrackItem>, SpotifyError> {
-        self.ensure_access_token().await?;
-
-        let response = self.get_album_tracks_request(album_id).await?;
-        let status = response.status();
-
-        if status.is_success() {
-            return Self::parse_album_tracks_response(response).await;
-        }
-
-        let error_body = response.text().await.unwrap_or_default();
+        let path = format!("/v1/albums/{album_id}/tracks");
+        let limit = ALBUM_TRACKS_PAGE_SIZE.to_string();
+        let mut tracks = Vec::new();
+
+        loop {
+            let offset = tracks.len().to_string();
+            let page: SpotifyTracksResponse = self
+                .get_json(
+                    &path,
+                    &[("limit", limit.as_str()), ("offset", offset.as_str())],
+                    "album tracks",
+                )
+                .await?;
+
+            let page_len = page.items.len();
+            tracks.extend(page.items.into_iter().map(|t| AlbumTrackItem {
+                id: t.id,
+                name: t.name,
+                disc_number: t.disc_number,
+                track_number: t.track_number,
+                duration_ms: Some(t.duration_ms),
+                spotify_url: t.external_urls.map(|u| u.spotify),
+            }));
 
-        if Self::is_expired_token_response(status, &error_body) {
-            self.refresh_access_token().await?;
-            let retried = self.get_album_tracks_request(album_id).await?;
-            if retried.status().is_success() {
-                return Self::parse_album_tracks_response(retried).await;
+            if page.next.is_none() || page_len == 0 {
+                break;
             }
-            let s = retried.status();
-            let b = retried.text().await.unwrap_or_default();
-            return Err(SpotifyError(format!(
-                "Spotify album tracks failed after token refresh (status {s}): {b}"
-            )));
         }
 
-        Err(SpotifyError(format!(
-            "Spotify album tracks failed with status {}: {}",
-            status, error_body
-        )))
-    }
-
-    async fn get_album_tracks_request(
-        &self,
-        album_id: &str,
-    ) -> Result<reqwest::Response, SpotifyError> {
-        let access_token = self
-            .access_token
-            .as_deref()
-            .ok_or_else(|| SpotifyError("Spotify access token is not available".to_string()))?;
-
-        self.http_client
-            .get(format!(
-                "https://api.spotify.com/v1/albums/{album_id}/tracks"
-            ))
-            .query(&[("limit", "50")])
-            .bearer_auth(access_token)
-            .send()
-            .await
-            .map_err(|e| SpotifyError(format!("Spotify request failed: {e}")))
+        Ok(tracks)
     }
 
-    async fn parse_album_tracks_response(
-        response: reqwest::Response,
-    ) -> Result<Vec<AlbumTrackItem>, SpotifyError> {
-        let body: SpotifyTracksResponse = response
-            .json()
-            .await
-            .map_err(|e| SpotifyError(format!("Failed to parse Spotify tracks response: {e}")))?;
+    /// Fetch full album metadata. The total duration is summed from the complete
+    /// (paginated) track listing.
+    pub async fn get_album(&self, album_id: &str) -> Result<AlbumDetails, SpotifyError> {
+        let album: SpotifyAlbumDetails = self
+            .get_json(&format!("/v1/albums/{album_id}"), &[], "album lookup")
+            .await?;
+        let tracks = self.get_album_tracks(album_id).await?;
 
-        Ok(body
-            .items
+        let mut images: Vec<AlbumImage> = album
+            .images
             .into_iter()
-            .map(|t| AlbumTrackItem {
-                id: t.id,
-                name: t.name,
-                track_number: t.track_number,
-                duration_ms: Some(t.duration_ms),
-                spotify_url: t.external_urls.map(|u| u.spotify),
+            .map(|image| AlbumImage {
+                url: image.url,
+                width: image.width,
+                height: image.height,
             })
-            .collect())
+            .collect();
+        images.sort_by_key(|image| std::cmp::Reverse(image.width.unwrap_or(0)));
+
+        Ok(AlbumDetails {
+            id: album.id,
+            name: album.name,
+            artists: album
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            artist_ids: album
+                .artists
+                .into_iter()
+                .filter_map(|artist| artist.id)
+                .collect(),
+            release_date: album.release_date,
+            label: album.label,
+            genres: album.genres,
+            total_tracks: album.total_tracks,
+            total_duration_ms: tracks.iter().filter_map(|t| t.duration_ms).sum(),
+            upc: album.external_ids.and_then(|ids| ids.upc),
+            images,
+            spotify_url: album.external_urls.spotify,
+            tracks,
+        })
+    }
+
+    /// The album a track belongs to.
+    pub async fn get_track_album(&self, track_id: &str) -> Result<AlbumSearchItem, SpotifyError> {
+        let track: SpotifyTrackWithAlbum = self
+            .get_json(&format!("/v1/tracks/{track_id}"), &[], "track lookup")
+            .await?;
+
+        Ok(track.album.into())
     }
 
-    pub async fn search_albums(
-        &mut self,
-        query: &str,
-    ) -> Result<Vec<AlbumSearchItem>, SpotifyError> {
+    pub async fn search_albums(&self, query: &str) -> Result<Vec<AlbumSearchItem>, SpotifyError> {
         let search_term = query.trim();
         if search_term.is_empty() {
             return Ok(Vec::new());
         }
 
-        self.ensure_access_token().await?;
+        let body: SpotifySearchResponse = self
+            .get_json(
+                "/v1/search",
+                &[("q", search_term), ("type", "album"), ("limit", "10")],
+                "search",
+            )
+            .await?;
 
-        let response = self.search_albums_request(search_term).await?;
-        let status = response.status();
+        Ok(body
+            .albums
+            .items
+            .into_iter()
+            .map(AlbumSearchItem::from)
+            .collect())
+    }
 
-        if status.is_success() {
-            return Self::parse_album_search_response(response).await;
-        }
+    /// GET a Web API path and parse the JSON body. `context` is only used in error messages.
+    ///
+    /// Rate limiting (429) and server errors (5xx) are retried, honouring `Retry-After`
+    /// when Spotify sends it and backing off exponentially otherwise. An expired token
+    /// is refreshed once.
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        context: &str,
+    ) -> Result<T, SpotifyError> {
+        let url = format!("{}{path}", self.api_base_url);
+        let mut refreshed_token = false;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let access_token = self.access_token().await?;
+
+            let response = match self
+                .http_client
+                .get(&url)
+                .query(query)
+                .bearer_auth(&access_token)
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(_) if attempt < MAX_ATTEMPTS => {
+                    tokio::time::sleep(Self::backoff_delay(attempt)).await;
+                    continue;
+                }
+                Err(e) => {
+                    return Err(SpotifyError::Unavailable(format!(
+                        "Spotify request failed: {e}"
+                    )))
+                }
+            };
+            let status = response.status();
+
+            if status.is_success() {
+                return Self::parse_json_response(response, context).await;
+            }
 
-        let error_body = response.text().await.unwrap_or_default();
+            if Self::is_retryable_status(status) && attempt < MAX_ATTEMPTS {
+                let retry_after = Self::retry_after(response.headers());
+                if let Some(delay) = Self::retry_delay(attempt, retry_after) {
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+            }
 
-        if Self::is_expired_token_response(status, &error_body) {
-            self.refresh_access_token().await?;
+            let retry_after = Self::retry_after(response.headers());
+            let error_body = response.text().await.unwrap_or_default();
 
-            let retried_response = self.search_albums_request(search_term).await?;
-            if retried_response.status().is_success() {
-                return Self::parse_album_search_response(retried_response).await;
+            if !refreshed_token && Self::is_expired_token_response(status, &error_body) {
+                refreshed_token = true;
+                self.invalidate_access_token(&access_token).await;
+                continue;
             }
 
-            let retried_status = retried_response.status();
-            let retried_error_body = retried_response.text().await.unwrap_or_default();
-            return Err(SpotifyError(format!(
-                "Spotify search failed after token refresh (status {}): {}",
-                retried_status, retried_error_body
-            )));
+            return Err(SpotifyError::from_status(
+                status,
+                retry_after,
+                format!("Spotify {context} failed with status {status}: {error_body}"),
+            ));
         }
-
-        Err(SpotifyError(format!(
-            "Spotify search failed with status {}: {}",
-            status, error_body
-        )))
     }
 
-    async fn search_albums_request(&self, query: &str) -> Result<reqwest::Response, SpotifyError> {
-        let access_token = self
-            .access_token
-            .as_deref()
-            .ok_or_else(|| SpotifyError("Spotify access token is not available".to_string()))?;
-
-        self.http_client
-            .get("https://api.spotify.com/v1/search")
-            .query(&[("q", query), ("type", "album"), ("limit", "10")])
-            .bearer_auth(access_token)
-            .send()
-            .await
-            .map_err(|e| SpotifyError(format!("Spotify request failed: {e}")))
+    async fn parse_json_response<T: DeserializeOwned>(
+        response: reqwest::Response,
+        context: &str,
+    ) -> Result<T, SpotifyError> {
+        response.json().await.map_err(|e| {
+            SpotifyError::Unexpected(format!("Failed to parse Spotify {context} response: {e}"))
+        })
     }
 
-    async fn ensure_access_token(&mut self) -> Result<(), SpotifyError> {
-        if self.has_valid_access_token() {
-            return Ok(());
+    /// Return a valid access token, requesting a new one if the cached token is missing or
+    /// expired. The cache lock is held during the refresh so only one request goes out.
+    async fn access_token(&self) -> Result<String, SpotifyError> {
+        let mut cached = self.access_token.lock().await;
+
+        if let Some(token) = cached.as_ref() {
+            if Instant::now() < token.expires_at {
+                return Ok(token.value.clone());
+            }
         }
 
-        self.refresh_access_token().await
+        let token = self.request_access_token().await?;
+        let value = token.value.clone();
+        *cached = Some(token);
+        Ok(value)
     }
 
-    fn has_valid_access_token(&self) -> bool {
-        match (self.access_token.as_ref(), self.access_token_expires_at) {
-            (Some(_), Some(expires_at)) => Instant::now() < expires_at,
-            _ => false,
+    /// Drop the cached token if it is still the one that was rejected. If another request
+    /// already replaced it, the fresh token is kept.
+    async fn invalidate_access_token(&self, rejected: &str) {
+        let mut cached = self.access_token.lock().await;
+        if cached.as_ref().is_some_and(|token| token.value == rejected) {
+            *cached = None;
         }
     }
 
-    async fn refresh_access_token(&mut self) -> Result<(), SpotifyError> {
-        let token_response = self
+    async fn request_access_token(&self) -> Result<AccessToken, SpotifyError> {
+        let request = self
             .http_client
-            .post("https://accounts.spotify.com/api/token")
+            .post(format!("{}/api/token", self.accounts_base_url))
             .basic_auth(&self.client_id, Some(&self.client_secret))
-            .form(&[("grant_type", "client_credentials")])
+            .form(&[("grant_type", "client_credentials")]);
+        let token_body = Self::send_token_request(request).await?;
+
+        let expires_in = token_body.expires_in.max(30);
+
+        Ok(AccessToken {
+            value: token_body.access_token,
+            expires_at: Instant::now() + Duration::from_secs(expires_in - 10),
+        })
+    }
+
+    /// Send a request to the accounts service token endpoint and parse the token it grants.
+    async fn send_token_request(
+        request: reqwest::RequestBuilder,
+    ) -> Result<SpotifyTokenResponse, SpotifyError> {
+        let token_response = request
             .send()
             .await
-            .map_err(|e| SpotifyError(format!("Spotify token request failed: {e}")))?;
+            .map_err(|e| SpotifyError::Unavailable(format!("Spotify token request failed: {e}")))?;
 
-        if !token_response.status().is_success() {
-            let status = token_response.status();
+        let status = token_response.status();
+        if !status.is_success() {
+            let retry_after = Self::retry_after(token_response.headers());
             let body = token_response.text().await.unwrap_or_default();
-            return Err(SpotifyError(format!(
-                "Spotify token request failed with status {}: {}",
-                status, body
-            )));
+            let message = format!("Spotify token request failed with status {status}: {body}");
+            // The accounts service answers bad client credentials, codes and refresh tokens
+            // with 400 (`invalid_client` / `invalid_grant`).
+            return Err(if status == reqwest::StatusCode::BAD_REQUEST {
+                SpotifyError::Auth(message)
+            } else {
+                SpotifyError::from_status(status, retry_after, message)
+            });
         }
 
-        let token_body: SpotifyTokenResponse = token_response
-            .json()
-            .await
-            .map_err(|e| SpotifyError(format!("Failed to parse Spotify token response: {e}")))?;
-
-        let expires_in = token_body.expires_in.max(30);
-        let expires_at = Instant::now() + Duration::from_secs(expires_in - 10);
+        token_response.json().await.map_err(|e| {
+            SpotifyError::Unexpected(format!("Failed to parse Spotify token response: {e}"))
+        })
+    }
 
-        self.access_token = Some(token_body.access_token);
-        self.access_token_expires_at = Some(expires_at);
+    fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+        status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
 
-        Ok(())
+    /// The delay requested by a `Retry-After` header, in whole seconds as Spotify sends it.
+    fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+        headers
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
     }
 
-    async fn parse_album_search_response(
-        response: reqwest::Response,
-    ) -> Result<Vec<AlbumSearchItem>, SpotifyError> {
-        let body: SpotifySearchResponse = response
-            .json()
-            .await
-            .map_err(|e| SpotifyError(format!("Failed to parse Spotify response: {e}")))?;
+    fn backoff_delay(attempt: u32) -> Duration {
+        RETRY_BASE_DELAY * 2u32.pow(attempt.saturating_sub(1))
+    }
 
-        Ok(body
-            .albums
-            .items
-            .into_iter()
-            .map(|album| AlbumSearchItem {
-                id: album.id,
-                name: album.name,
-                artists: album
-                    .artists
-                    .into_iter()
-                    .map(|artist| artist.name)
-                    .collect::<Vec<_>>()
-                    .join(", "),
-                image_url: album.images.first().map(|image| image.url.clone()),
-                spotify_url: album.external_urls.spotify,
-            })
-            .collect::<Vec<_>>())
+    /// How long to wait before retrying `attempt`: what Spotify asked for, or the backoff.
+    /// `None` if that is longer than we wait out inside a request.
+    fn retry_delay(attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let delay = retry_after.unwrap_or_else(|| Self::backoff_delay(attempt));
+        (delay <= MAX_RETRY_DELAY).then_some(delay)
     }
 
     fn is_expired_token_response(status: reqwest::StatusCode, body: &str) -> bool {
@@ -276,24 +437,89 @@ impl SpotifyClient {
     }
 }
 
+#[async_trait]
+impl MusicCatalog for SpotifyClient {
+    async fn search_albums(&self, query: &str) -> Result<Vec<AlbumSearchItem>, SpotifyError> {
+        SpotifyClient::search_albums(self, query).await
+    }
+
+    async fn get_album_tracks(&self, album_id: &str) -> Result<Vec<AlbumTrackItem>, SpotifyError> {
+        SpotifyClient::get_album_tracks(self, album_id).await
+    }
+
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetails, SpotifyError> {
+        SpotifyClient::get_album(self, album_id).await
+    }
+
+    async fn get_track_album(&self, track_id: &str) -> Result<AlbumSearchItem, SpotifyError> {
+        SpotifyClient::get_track_album(self, track_id).await
+    }
+}
+
+impl From<AlbumDetails> for AlbumSearchItem {
+    fn from(album: AlbumDetails) -> Self {
+        AlbumSearchItem {
+            id: album.id,
+            name: album.name,
+            artists: album.artists,
+            image_url: album.images.into_iter().next().map(|image| image.url),
+            spotify_url: album.spotify_url,
+        }
+    }
+}
+
+impl From<SpotifyAlbum> for AlbumSearchItem {
+    fn from(album: SpotifyAlbum) -> Self {
+        AlbumSearchItem {
+            id: album.id,
+            name: album.name,
+            artists: album
+                .artists
+                .into_iter()
+                .map(|artist| artist.name)
+                .collect::<Vec<_>>()
+                .join(", "),
+            image_url: album.images.first().map(|image| image.url.clone()),
+            spotify_url: album.external_urls.spotify,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyTrackWithAlbum {
+    album: SpotifyAlbum,
+}
+
 #[derive(Debug, Deserialize)]
 struct SpotifyTracksResponse {
     items: Vec<SpotifyTrack>,
+    next: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct SpotifyTrack {
     id: String,
     name: String,
+    #[serde(default = "default_disc_number")]
+    disc_number: u32,
     track_number: u32,
     duration_ms: u64,
     external_urls: Option<SpotifyExternalUrls>,
 }
 
+pub(crate) fn default_disc_number() -> u32 {
+    1
+}
+
 #[derive(Debug, Deserialize)]
 struct SpotifyTokenResponse {
     access_token: String,
     expires_in: u64,
+    /// Only granted by the authorization code flow.
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -315,17 +541,216 @@ struct SpotifyAlbum {
     external_urls: SpotifyExternalUrls,
 }
 
+#[derive(Debug, Deserialize)]
+struct SpotifyAlbumDetails {
+    id: String,
+    name: String,
+    artists: Vec<SpotifyArtist>,
+    images: Vec<SpotifyImage>,
+    external_urls: SpotifyExternalUrls,
+    release_date: Option<String>,
+    label: Option<String>,
+    #[serde(default)]
+    genres: Vec<String>,
+    total_tracks: u32,
+    external_ids: Option<SpotifyExternalIds>,
+}
+
 #[derive(Debug, Deserialize)]
 struct SpotifyArtist {
+    id: Option<String>,
     name: String,
 }
 
 #[derive(Debug, Deserialize)]
 struct SpotifyImage {
     url: String,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyExternalIds {
+    upc: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct SpotifyExternalUrls {
     spotify: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Disc 1 has 70 tracks and disc 2 has 50, so the listing spans three pages.
+    fn track_listing() -> Vec<(u32, u32)> {
+        let disc_1 = (1..=70).map(|n| (1, n));
+        let disc_2 = (1..=50).map(|n| (2, n));
+        disc_1.chain(disc_2).collect()
+    }
+
+    async fn album_tracks(
+        Path(album_id): Path<String>,
+        Query(query): Query<HashMap<String, usize>>,
+    ) -> Result<Json<Value>, StatusCode> {
+        let (offset, limit) = (query["offset"], query["limit"]);
+        if limit > ALBUM_TRACKS_PAGE_SIZE {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+
+        let listing = track_listing();
+        let items: Vec<Value> = listing
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(disc, number)| {
+                json!({
+                    "id": format!("{album_id}-{disc}-{number}"),
+                    "name": format!("Track {disc}.{number}"),
+                    "disc_number": disc,
+                    "track_number": number,
+                    "duration_ms": 1000,
+                })
+            })
+            .collect();
+        let next = (offset + limit < listing.len()).then(|| {
+            format!(
+                "/v1/albums/{album_id}/tracks?offset={}&limit={limit}",
+                offset + limit
+            )
+        });
+
+        Ok(Json(json!({ "items": items, "next": next })))
+    }
+
+    #[tokio::test]
+    async fn album_tracks_follow_every_page_and_keep_discs() {
+        let base_url =
+            stand_in::serve(Router::new().route("/v1/albums/{album_id}/tracks", get(album_tracks)))
+                .await;
+        let client = stand_in::client(&base_url);
+
+        let tracks = client.get_album_tracks("album").await.unwrap();
+
+        let listing: Vec<(u32, u32)> = tracks
+            .iter()
+            .map(|t| (t.disc_number, t.track_number))
+            .collect();
+        assert_eq!(listing, track_listing());
+        assert_eq!(tracks[70].id, "album-2-1");
+        assert_eq!(tracks[70].name, "Track 2.1");
+    }
+
+    fn retry_after_header(value: &str) -> Option<Duration> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            reqwest::header::HeaderValue::from_str(value).unwrap(),
+        );
+        SpotifyClient::retry_after(&headers)
+    }
+
+    #[test]
+    fn retry_after_is_whole_seconds() {
+        assert_eq!(retry_after_header("3"), Some(Duration::from_secs(3)));
+        assert_eq!(retry_after_header(" 0 "), Some(Duration::ZERO));
+        assert_eq!(retry_after_header("soon"), None);
+        assert_eq!(retry_after_header("-1"), None);
+        assert_eq!(retry_after_header("1.5"), None);
+        assert_eq!(retry_after_header("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(
+            SpotifyClient::retry_after(&reqwest::header::HeaderMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        let delays: Vec<Duration> = (1..=MAX_ATTEMPTS)
+            .map(SpotifyClient::backoff_delay)
+            .collect();
+        assert_eq!(delays[0], RETRY_BASE_DELAY);
+        for pair in delays.windows(2) {
+            assert_eq!(pair[1], pair[0] * 2);
+        }
+        assert!(delays.iter().all(|delay| *delay <= MAX_RETRY_DELAY));
+    }
+
+    #[test]
+    fn long_retry_after_is_not_waited_out() {
+        assert_eq!(
+            SpotifyClient::retry_delay(1, Some(MAX_RETRY_DELAY)),
+            Some(MAX_RETRY_DELAY)
+        );
+        assert_eq!(
+            SpotifyClient::retry_delay(1, Some(MAX_RETRY_DELAY + Duration::from_secs(1))),
+            None
+        );
+        assert_eq!(
+            SpotifyClient::retry_delay(2, None),
+            Some(SpotifyClient::backoff_delay(2))
+        );
+        assert_eq!(
+            SpotifyClient::retry_delay(3, Some(Duration::ZERO)),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_are_retried() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = calls.clone();
+        let base_url = stand_in::serve(Router::new().route(
+            "/v1/albums/{album_id}/tracks",
+            get(move || {
+                let call = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move {
+                    if call == 0 {
+                        Err((StatusCode::TOO_MANY_REQUESTS, [("retry-after", "0")]))
+                    } else {
+                        Ok(Json(json!({ "items": [], "next": null })))
+                    }
+                }
+            }),
+        ))
+        .await;
+        let client = stand_in::client(&base_url);
+
+        assert!(client.get_album_tracks("album").await.unwrap().is_empty());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn missing_disc_number_means_disc_one() {
+        let base_url = stand_in::serve(Router::new().route(
+            "/v1/albums/{album_id}/tracks",
+            get(|| async {
+                Json(json!({
+                    "items": [
+                        { "id": "a", "name": "A", "track_number": 1, "duration_ms": 1000 },
+                        { "id": "b", "name": "B", "track_number": 2, "duration_ms": 1000 }
+                    ],
+                    "next": null
+                }))
+            }),
+        ))
+        .await;
+        let client = stand_in::client(&base_url);
+
+        let tracks = client.get_album_tracks("album").await.unwrap();
+
+        assert_eq!(tracks.len(), 2);
+        assert!(tracks.iter().all(|t| t.disc_number == 1));
+    }
+}
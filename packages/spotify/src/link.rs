@@ -0,0 +1,161 @@
+const OPEN_SPOTIFY_HOST: &str = "open.spotify.com";
+
+/// An album or track referenced by a Spotify link or URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpotifyLink {
+    Album(String),
+    Track(String),
+}
+
+/// Parse a `spotify:album:<id>` / `spotify:track:<id>` URI or an
+/// `open.spotify.com/album/<id>` / `open.spotify.com/track/<id>` link (with or
+/// without scheme, locale prefix such as `/intl-sv/`, and share query string).
+/// Returns `None` for anything else, including bare IDs.
+pub fn parse_link(input: &str) -> Option<SpotifyLink> {
+    let input = input.trim();
+
+    if let Some(uri) = input.strip_prefix("spotify:") {
+        let (kind, id) = uri.split_once(':')?;
+        return link_from_parts(kind, id);
+    }
+
+    if input.contains(OPEN_SPOTIFY_HOST) {
+        return link_from_url(input);
+    }
+
+    None
+}
+
+/// Extract an album ID from user input: either a bare album ID or an album link
+/// or URI accepted by [`parse_link`]. Track links are not album references.
+pub fn parse_album_id(input: &str) -> Option<String> {
+    match parse_link(input) {
+        Some(SpotifyLink::Album(id)) => Some(id),
+        Some(SpotifyLink::Track(_)) => None,
+        None => valid_id(input.trim()),
+    }
+}
+
+fn link_from_parts(kind: &str, id: &str) -> Option<SpotifyLink> {
+    let id = valid_id(id)?;
+    match kind {
+        "album" => Some(SpotifyLink::Album(id)),
+        "track" => Some(SpotifyLink::Track(id)),
+        _ => None,
+    }
+}
+
+fn link_from_url(url: &str) -> Option<SpotifyLink> {
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let path = url.strip_prefix(OPEN_SPOTIFY_HOST)?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut kind = segments.next()?;
+    if kind.starts_with("intl-") {
+        kind = segments.next()?;
+    }
+
+    link_from_parts(kind, segments.next()?)
+}
+
+/// Spotify IDs are base62; the fixture catalog also uses `-` and `_`.
+fn valid_id(id: &str) -> Option<String> {
+    let is_valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    is_valid.then(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_uris() {
+        assert_eq!(
+            parse_link("spotify:album:4aawyAB9vmqN3uQ7FjRGTy"),
+            Some(SpotifyLink::Album("4aawyAB9vmqN3uQ7FjRGTy".to_string()))
+        );
+        assert_eq!(
+            parse_link("  spotify:track:11dFghVXANMlKmJXsNCbNl\n"),
+            Some(SpotifyLink::Track("11dFghVXANMlKmJXsNCbNl".to_string()))
+        );
+        assert_eq!(parse_link("spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"), None);
+        assert_eq!(parse_link("spotify:album:"), None);
+        assert_eq!(parse_link("spotify:album"), None);
+    }
+
+    #[test]
+    fn parses_links() {
+        let album = Some(SpotifyLink::Album("4aawyAB9vmqN3uQ7FjRGTy".to_string()));
+        assert_eq!(
+            parse_link("https://open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy"),
+            album
+        );
+        assert_eq!(
+            parse_link("http://open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy/"),
+            album
+        );
+        assert_eq!(
+            parse_link("open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy"),
+            album
+        );
+        assert_eq!(
+            parse_link("https://open.spotify.com/intl-sv/album/4aawyAB9vmqN3uQ7FjRGTy"),
+            album
+        );
+        assert_eq!(
+            parse_link("https://open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy?si=abc123&nd=1"),
+            album
+        );
+        assert_eq!(
+            parse_link("https://open.spotify.com/intl-xx/track/11dFghVXANMlKmJXsNCbNl#top"),
+            Some(SpotifyLink::Track("11dFghVXANMlKmJXsNCbNl".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_other_links() {
+        assert_eq!(parse_link("https://open.spotify.com/intl-sv/"), None);
+        assert_eq!(
+            parse_link("https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M"),
+            None
+        );
+        assert_eq!(parse_link("https://open.spotify.com/album/"), None);
+        assert_eq!(
+            parse_link("https://open.spotify.com/album/4aaw%20yAB"),
+            None
+        );
+        assert_eq!(
+            parse_link("https://example.com/album/4aawyAB9vmqN3uQ7FjRGTy"),
+            None
+        );
+        assert_eq!(parse_link("4aawyAB9vmqN3uQ7FjRGTy"), None);
+    }
+
+    #[test]
+    fn parses_album_ids() {
+        let id = Some("4aawyAB9vmqN3uQ7FjRGTy".to_string());
+        assert_eq!(parse_album_id(" 4aawyAB9vmqN3uQ7FjRGTy "), id);
+        assert_eq!(parse_album_id("spotify:album:4aawyAB9vmqN3uQ7FjRGTy"), id);
+        assert_eq!(
+            parse_album_id("https://open.spotify.com/intl-de/album/4aawyAB9vmqN3uQ7FjRGTy?si=x"),
+            id
+        );
+        assert_eq!(
+            parse_album_id("fixture-album_1"),
+            Some("fixture-album_1".to_string())
+        );
+        assert_eq!(parse_album_id("spotify:track:11dFghVXANMlKmJXsNCbNl"), None);
+        assert_eq!(
+            parse_album_id("https://open.spotify.com/track/11dFghVXANMlKmJXsNCbNl"),
+            None
+        );
+        assert_eq!(parse_album_id(""), None);
+        assert_eq!(parse_album_id("not an id"), None);
+    }
+}
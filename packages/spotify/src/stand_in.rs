@@ -0,0 +1,33 @@
+//! A local stand-in for the Spotify Web API and accounts service, for tests.
+
+use axum::{routing::post, Json, Router};
+use serde_json::{json, Value};
+
+use crate::SpotifyClient;
+
+/// Serve `routes`, plus the accounts service's token endpoint, on a free local port.
+/// Returns the base URL for both hosts.
+pub async fn serve(routes: Router) -> String {
+    let app = routes.route("/api/token", post(token));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind a local port");
+    let base_url = format!("http://{}", listener.local_addr().expect("local address"));
+
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    base_url
+}
+
+/// A client talking to the stand-in at `base_url`.
+pub fn client(base_url: &str) -> SpotifyClient {
+    SpotifyClient::new("client-id".to_string(), "client-secret".to_string())
+        .with_base_urls(base_url, base_url)
+}
+
+async fn token() -> Json<Value> {
+    Json(json!({
+        "access_token": "stand-in-token",
+        "token_type": "Bearer",
+        "expires_in": 3600,
+    }))
+}
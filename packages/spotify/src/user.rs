@@ -0,0 +1,380 @@
+//! The authorization code (PKCE) flow and the Web API calls made on a user's behalf.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{SpotifyClient, SpotifyError, SpotifyExternalUrls, MAX_ATTEMPTS};
+
+/// Scopes needed to create and edit the club playlist.
+pub const PLAYLIST_SCOPES: &str = "playlist-modify-private playlist-modify-public";
+
+/// Most track URIs the playlist tracks endpoint accepts per request.
+const PLAYLIST_TRACKS_PAGE_SIZE: usize = 100;
+
+/// Tokens granted for a Spotify user.
+#[derive(Debug, Clone)]
+pub struct UserToken {
+    pub access_token: String,
+    /// Spotify may omit this on refresh, in which case the previous one stays valid.
+    pub refresh_token: Option<String>,
+    /// Seconds until `access_token` expires.
+    pub expires_in: u64,
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpotifyUser {
+    pub id: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    pub id: String,
+    pub url: String,
+}
+
+impl SpotifyClient {
+    /// The accounts service page to send the user to. Spotify redirects back to
+    /// `redirect_uri` with `code` and `state` query parameters.
+    pub fn authorize_url(
+        &self,
+        redirect_uri: &str,
+        state: &str,
+        code_challenge: &str,
+        scopes: &str,
+    ) -> Result<String, SpotifyError> {
+        let mut url = reqwest::Url::parse(&format!("{}/authorize", self.accounts_base_url))
+            .map_err(|e| SpotifyError::Config(format!("Invalid Spotify accounts URL: {e}")))?;
+
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("state", state)
+            .append_pair("scope", scopes)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("code_challenge", code_challenge);
+
+        Ok(url.into())
+    }
+
+    /// Exchange the `code` Spotify redirected back with for user tokens.
+    pub async fn exchange_authorization_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<UserToken, SpotifyError> {
+        let request = self
+            .http_client
+            .post(format!("{}/api/token", self.accounts_base_url))
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", &self.client_id),
+                ("code_verifier", code_verifier),
+            ]);
+
+        Ok(Self::send_token_request(request).await?.into())
+    }
+
+    pub async fn refresh_user_token(&self, refresh_token: &str) -> Result<UserToken, SpotifyError> {
+        let request = self
+            .http_client
+            .post(format!("{}/api/token", self.accounts_base_url))
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", &self.client_id),
+            ]);
+
+        Ok(Self::send_token_request(request).await?.into())
+    }
+
+    pub async fn current_user(&self, access_token: &str) -> Result<SpotifyUser, SpotifyError> {
+        let url = format!("{}/v1/me", self.api_base_url);
+        let response = self
+            .send_user_request(
+                || self.http_client.get(&url),
+                access_token,
+                "current user",
+                Retry::Failures,
+            )
+            .await?;
+        let user: SpotifyUserResponse = Self::parse_json_response(response, "current user").await?;
+
+        Ok(SpotifyUser {
+            id: user.id,
+            display_name: user.display_name,
+        })
+    }
+
+    pub async fn create_playlist(
+        &self,
+        access_token: &str,
+        user_id: &str,
+        name: &str,
+        description: &str,
+    ) -> Result<Playlist, SpotifyError> {
+        let url = format!("{}/v1/users/{user_id}/playlists", self.api_base_url);
+        let body = json!({ "name": name, "description": description, "public": false });
+        let response = self
+            .send_user_request(
+                || self.http_client.post(&url).json(&body),
+                access_token,
+                "create playlist",
+                Retry::RateLimited,
+            )
+            .await?;
+        let playlist: SpotifyPlaylistResponse =
+            Self::parse_json_response(response, "create playlist").await?;
+
+        Ok(Playlist {
+            id: playlist.id,
+            url: playlist.external_urls.spotify,
+        })
+    }
+
+    pub async fn update_playlist_details(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        name: &str,
+        description: &str,
+    ) -> Result<(), SpotifyError> {
+        let url = format!("{}/v1/playlists/{playlist_id}", self.api_base_url);
+        let body = json!({ "name": name, "description": description });
+        self.send_user_request(
+            || self.http_client.put(&url).json(&body),
+            access_token,
+            "update playlist",
+            Retry::Failures,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the playlist's contents with `track_uris` (`spotify:track:<id>`), in order.
+    pub async fn replace_playlist_tracks(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        track_uris: &[String],
+    ) -> Result<(), SpotifyError> {
+        let url = format!("{}/v1/playlists/{playlist_id}/tracks", self.api_base_url);
+        let mut pages = track_uris.chunks(PLAYLIST_TRACKS_PAGE_SIZE);
+
+        // PUT replaces everything (an empty list clears the playlist); further pages are appended.
+        let first = pages.next().unwrap_or_default();
+        let body = json!({ "uris": first });
+        self.send_user_request(
+            || self.http_client.put(&url).json(&body),
+            access_token,
+            "replace playlist tracks",
+            Retry::Failures,
+        )
+        .await?;
+
+        for page in pages {
+            let body = json!({ "uris": page });
+            self.send_user_request(
+                || self.http_client.post(&url).json(&body),
+                access_token,
+                "add playlist tracks",
+                Retry::RateLimited,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send a request authorised with a user's access token, retrying as `retry` allows.
+    /// Returns the successful response.
+    async fn send_user_request(
+        &self,
+        build: impl Fn() -> reqwest::RequestBuilder,
+        access_token: &str,
+        context: &str,
+        retry: Retry,
+    ) -> Result<reqwest::Response, SpotifyError> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            let response = match build().bearer_auth(access_token).send().await {
+                Ok(response) => response,
+                Err(_) if retry == Retry::Failures && attempt < MAX_ATTEMPTS => {
+                    tokio::time::sleep(Self::backoff_delay(attempt)).await;
+                    continue;
+                }
+                Err(e) => {
+                    return Err(SpotifyError::Unavailable(format!(
+                        "Spotify {context} request failed: {e}"
+                    )))
+                }
+            };
+            let status = response.status();
+
+            if status.is_success() {
+                return Ok(response);
+            }
+
+            let retry_after = Self::retry_after(response.headers());
+            let retryable = match retry {
+                Retry::Failures => Self::is_retryable_status(status),
+                Retry::RateLimited => status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            };
+            if retryable && attempt < MAX_ATTEMPTS {
+                if let Some(delay) = Self::retry_delay(attempt, retry_after) {
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+            }
+
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(SpotifyError::from_status(
+                status,
+                retry_after,
+                format!("Spotify {context} failed with status {status}: {error_body}"),
+            ));
+        }
+    }
+}
+
+/// Which failed attempts a user request is sent again after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Retry {
+    /// Network errors, rate limiting and server errors, like [`SpotifyClient::get_json`]
+    /// does. Only for requests that are safe to repeat.
+    Failures,
+    /// Only rate limiting, which Spotify answers before acting on the request. For requests
+    /// that would add things twice if a lost response made us repeat them.
+    RateLimited,
+}
+
+impl From<crate::SpotifyTokenResponse> for UserToken {
+    fn from(token: crate::SpotifyTokenResponse) -> Self {
+        UserToken {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_in: token.expires_in,
+            scope: token.scope,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyUserResponse {
+    id: String,
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyPlaylistResponse {
+    id: String,
+    external_urls: SpotifyExternalUrls,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        extract::State,
+        http::{Method, StatusCode},
+        routing::put,
+        Json, Router,
+    };
+    use serde_json::Value;
+
+    use super::*;
+    use crate::stand_in;
+
+    /// The method and URI count of every playlist tracks request the stand-in received.
+    type Requests = Arc<Mutex<Vec<(Method, usize)>>>;
+
+    async fn playlist_tracks(
+        State(requests): State<Requests>,
+        method: Method,
+        Json(body): Json<Value>,
+    ) -> StatusCode {
+        let uris = body["uris"].as_array().map_or(0, Vec::len);
+        if uris > PLAYLIST_TRACKS_PAGE_SIZE {
+            return StatusCode::BAD_REQUEST;
+        }
+        requests.lock().unwrap().push((method, uris));
+        StatusCode::CREATED
+    }
+
+    async fn serve_playlist() -> (SpotifyClient, Requests) {
+        let requests = Requests::default();
+        let routes = Router::new()
+            .route(
+                "/v1/playlists/{playlist_id}/tracks",
+                put(playlist_tracks).post(playlist_tracks),
+            )
+            .with_state(requests.clone());
+        let base_url = stand_in::serve(routes).await;
+        (stand_in::client(&base_url), requests)
+    }
+
+    fn track_uris(count: usize) -> Vec<String> {
+        (0..count).map(|n| format!("spotify:track:{n}")).collect()
+    }
+
+    #[tokio::test]
+    async fn replacing_tracks_puts_the_first_page_and_posts_the_rest() {
+        let (client, requests) = serve_playlist().await;
+
+        client
+            .replace_playlist_tracks("user-token", "playlist", &track_uris(250))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [(Method::PUT, 100), (Method::POST, 100), (Method::POST, 50)]
+        );
+    }
+
+    #[tokio::test]
+    async fn replacing_with_no_tracks_clears_the_playlist() {
+        let (client, requests) = serve_playlist().await;
+
+        client
+            .replace_playlist_tracks("user-token", "playlist", &[])
+            .await
+            .unwrap();
+
+        assert_eq!(*requests.lock().unwrap(), [(Method::PUT, 0)]);
+    }
+
+    #[tokio::test]
+    async fn adding_tracks_is_not_repeated_after_a_server_error() {
+        let calls = Arc::new(Mutex::new(0));
+        let routes = Router::new()
+            .route(
+                "/v1/playlists/{playlist_id}/tracks",
+                put(|| async { StatusCode::CREATED }).post(
+                    |State(calls): State<Arc<Mutex<u32>>>| async move {
+                        *calls.lock().unwrap() += 1;
+                        StatusCode::BAD_GATEWAY
+                    },
+                ),
+            )
+            .with_state(calls.clone());
+        let client = stand_in::client(&stand_in::serve(routes).await);
+
+        let result = client
+            .replace_playlist_tracks("user-token", "playlist", &track_uris(101))
+            .await;
+
+        assert!(matches!(result, Err(SpotifyError::Unavailable(_))));
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+}
@@ -1,18 +1,45 @@
+use api::admin_add_member;
+use api::admin_add_unavailability;
+use api::admin_audit_log;
+use api::admin_create_member_invite;
 use api::admin_delete_history_entry;
 use api::admin_delete_member;
+use api::admin_fairness_report;
+use api::admin_remove_unavailability;
+use api::admin_removed_members;
+use api::admin_rename_member;
 use api::admin_reorder_members;
+use api::admin_restore_member;
+use api::admin_reveal_reviews;
+use api::admin_rotation_skips;
 use api::admin_set_current;
-use api::admin_set_member_password;
+use api::admin_set_reviews_locked;
 use api::admin_spotify_album_search;
 use api::admin_update_current;
-use api::api_models::{Data, HistoryEntry, SetCurrentRequest, SpotifyAlbumSearchItem};
-use api::{get_current, get_history};
+use api::api_models::{
+    album_art_path, member_invite_path, ActorKind, AdminSession, ArtSize, AuditAction,
+    AuditActorId, AuditEntry, AuditLogFilter, Data, HistoryEntry, Member, MemberId, PickImbalance,
+    RevealPolicy, SetCurrentRequest, SkipReason, SpotifyAlbumSearchItem, Unavailability,
+    DEFAULT_REVIEW_CLOSE_DAYS, MAX_UNAVAILABILITY_NOTE_LEN,
+};
+use api::{admin_access, admin_create_credential, admin_revoke_credential, admin_set_member_admin};
+use api::{admin_login, admin_login_member, admin_logout, admin_session};
+use api::{
+    admin_spotify_account, admin_spotify_authorize, admin_spotify_disconnect,
+    admin_spotify_export_playlist, spotify_complete_authorization,
+};
+use api::{get_current, get_history, get_next_picker, get_unavailability, ApiError};
 use dioxus::document::eval;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::fi_icons::FiTrash2;
+use dioxus_free_icons::icons::fi_icons::{FiEdit2, FiTrash2};
 use dioxus_free_icons::Icon;
 use std::time::Duration;
 
+use crate::errors::error_message;
+use crate::session_storage::{
+    clear_session_token, store_session_token, stored_session_token, ADMIN_SESSION_KEY,
+};
+
 const ADMIN_SCSS: Asset = asset!("/assets/styling/admin.scss");
 
 // ── Shell layout ──────────────────────────────────────────────────────────────
@@ -20,15 +47,17 @@ const ADMIN_SCSS: Asset = asset!("/assets/styling/admin.scss");
 /// Outer shell for all admin pages.
 ///
 /// Initialises shared state, provides it via `AdminCtx`, and renders the
-/// header, token input, tab bar, and then `children` (the active tab content).
+/// header, login, tab bar, and then `children` (the active tab content).
 ///
-/// `active_tab` must be one of `"album"`, `"rotation"`, `"historik"`, or
-/// `"lossenord"` so the correct tab can be highlighted.
+/// `active_tab` must be one of `"album"`, `"rotation"`, `"historik"`,
+/// `"lossenord"`, `"atkomst"`, `"logg"` or `"spotify"` so the correct tab can be highlighted.
 #[component]
 pub fn AdminShell(active_tab: &'static str, children: Element) -> Element {
     let mut admin_token = use_signal(String::new);
-    let members = use_signal(Vec::<String>::new);
-    let original_members = use_signal(Vec::<String>::new);
+    let mut logged_in_as = use_signal(|| None::<String>);
+    let mut is_restoring_session = use_signal(|| true);
+    let members = use_signal(Vec::<Member>::new);
+    let original_members = use_signal(Vec::<Member>::new);
     let current_data = use_signal(|| None::<Data>);
     let history = use_signal(|| None::<Result<Vec<HistoryEntry>, String>>);
 
@@ -47,18 +76,48 @@ pub fn AdminShell(active_tab: &'static str, children: Element) -> Element {
 
     use_future(move || async move {
         if let Ok(data) = get_current().await {
-            let list: Vec<String> = data.members.iter().map(|m| m.to_string()).collect();
-            members_w.set(list.clone());
-            original_members_w.set(list);
+            members_w.set(data.members.clone());
+            original_members_w.set(data.members.clone());
             current_data_w.set(Some(data));
         }
     });
 
     use_future(move || async move {
-        let result = get_history().await.map_err(|e| e.to_string());
+        let result = get_history().await.map_err(|e| admin_error_message(&e));
         history_w.set(Some(result));
     });
 
+    // Pick up a login from an earlier visit.
+    use_future(move || async move {
+        if let Some(token) = stored_session_token(ADMIN_SESSION_KEY).await {
+            match admin_session(token).await {
+                Ok(session) => {
+                    admin_token.set(session.token);
+                    logged_in_as.set(Some(session.label));
+                }
+                Err(ApiError::Unauthorized(_)) => clear_session_token(ADMIN_SESSION_KEY),
+                Err(_) => {}
+            }
+        }
+        is_restoring_session.set(false);
+    });
+
+    let on_login = move |session: AdminSession| {
+        store_session_token(ADMIN_SESSION_KEY, &session.token);
+        admin_token.set(session.token);
+        logged_in_as.set(Some(session.label));
+    };
+
+    let logout = move |_| {
+        let token = admin_token();
+        spawn(async move {
+            let _ = admin_logout(token).await;
+        });
+        clear_session_token(ADMIN_SESSION_KEY);
+        admin_token.set(String::new());
+        logged_in_as.set(None);
+    };
+
     let tab = move |slug: &'static str, href: &'static str, label: &'static str| {
         let class = if active_tab == slug {
             "admin-tab admin-tab-active"
@@ -79,18 +138,16 @@ pub fn AdminShell(active_tab: &'static str, children: Element) -> Element {
                 p { "Hantera nuvarande album, möte och väljare." }
             }
 
-            div { class: "card admin-section",
-                label { class: "admin-label", r#for: "admin-token",
-                    "Admin-token"
-                    span { class: "required-star", " *" }
-                }
-                input {
-                    id: "admin-token",
-                    r#type: "password",
-                    placeholder: "ADMIN_TOKEN",
-                    value: "{admin_token}",
-                    oninput: move |e| admin_token.write().clone_from(&e.value()),
+            if let Some(label) = logged_in_as() {
+                div { class: "card admin-section admin-session",
+                    span {
+                        "Inloggad som "
+                        strong { "{label}" }
+                    }
+                    button { class: "admin-button-ghost", onclick: logout, "Logga ut" }
                 }
+            } else if !is_restoring_session() {
+                AdminLogin { on_login }
             }
 
             div { class: "admin-tab-bar",
@@ -98,6 +155,9 @@ pub fn AdminShell(active_tab: &'static str, children: Element) -> Element {
                 {tab("rotation", "/admin/rotation",  "Medlemmar")}
                 {tab("historik", "/admin/historik",  "Historik")}
                 {tab("lossenord", "/admin/l%C3%B6senord", "Lösenord")}
+                {tab("atkomst",  "/admin/%C3%A5tkomst", "Åtkomst")}
+                {tab("logg",     "/admin/logg",      "Logg")}
+                {tab("spotify",  "/admin/spotify",   "Spotify")}
             }
 
             {children}
@@ -107,6 +167,160 @@ pub fn AdminShell(active_tab: &'static str, children: Element) -> Element {
     }
 }
 
+/// Admin calls only fail authorisation on the admin session, so say so.
+fn admin_error_message(err: &ApiError) -> String {
+    match err {
+        ApiError::Unauthorized(_) => "Inte inloggad, eller sessionen har gått ut".to_string(),
+        other => error_message(other),
+    }
+}
+
+/// Log in with an admin credential's token, or as a member with admin rights.
+#[component]
+fn AdminLogin(on_login: Callback<AdminSession>) -> Element {
+    let members = use_context::<AdminCtx>().members;
+
+    let mut as_member = use_signal(|| false);
+    let mut token = use_signal(String::new);
+    let mut member_id = use_signal(|| None::<MemberId>);
+    let mut password = use_signal(String::new);
+    let mut login_error = use_signal(|| None::<String>);
+    let mut is_logging_in = use_signal(|| false);
+
+    let can_log_in = move || {
+        !is_logging_in()
+            && if as_member() {
+                member_id().is_some() && !password().is_empty()
+            } else {
+                !token().trim().is_empty()
+            }
+    };
+
+    let mut login = move || {
+        if !can_log_in() {
+            return;
+        }
+        login_error.set(None);
+        is_logging_in.set(true);
+        spawn(async move {
+            let result = match member_id().filter(|_| as_member()) {
+                Some(id) => admin_login_member(id, password()).await,
+                None => admin_login(token().trim().to_string()).await,
+            };
+            match result {
+                Ok(session) => {
+                    token.set(String::new());
+                    password.set(String::new());
+                    on_login(session);
+                }
+                Err(ApiError::Unauthorized(_)) if as_member() => login_error.set(Some(
+                    "Fel lösenord, eller så är medlemmen inte admin".to_string(),
+                )),
+                Err(ApiError::Unauthorized(_)) => {
+                    login_error.set(Some("Okänd eller återkallad admin-nyckel".to_string()))
+                }
+                Err(e) => login_error.set(Some(error_message(&e))),
+            }
+            is_logging_in.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Logga in" }
+            div { class: "admin-login-modes",
+                button {
+                    class: if as_member() { "admin-button-ghost" } else { "admin-button" },
+                    onclick: move |_| {
+                        as_member.set(false);
+                        login_error.set(None);
+                    },
+                    "Admin-nyckel"
+                }
+                button {
+                    class: if as_member() { "admin-button" } else { "admin-button-ghost" },
+                    onclick: move |_| {
+                        as_member.set(true);
+                        login_error.set(None);
+                    },
+                    "Medlem"
+                }
+            }
+
+            if as_member() {
+                div { class: "admin-field-group",
+                    div { class: "admin-field",
+                        label { class: "admin-label", r#for: "admin-login-member", "Medlem" }
+                        select {
+                            id: "admin-login-member",
+                            onchange: move |e| member_id.set(e.value().parse().ok()),
+                            option {
+                                value: "",
+                                disabled: true,
+                                selected: member_id().is_none(),
+                                "Välj medlem..."
+                            }
+                            for member in members() {
+                                option {
+                                    value: "{member.id}",
+                                    selected: member_id() == Some(member.id),
+                                    "{member.name}"
+                                }
+                            }
+                        }
+                    }
+                    div { class: "admin-field",
+                        label { class: "admin-label", r#for: "admin-login-password", "Lösenord" }
+                        input {
+                            id: "admin-login-password",
+                            r#type: "password",
+                            value: "{password}",
+                            oninput: move |e| password.set(e.value()),
+                            onkeydown: move |e| {
+                                if e.key() == Key::Enter {
+                                    login();
+                                }
+                            },
+                        }
+                    }
+                }
+            } else {
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "admin-login-token", "Admin-nyckel" }
+                    input {
+                        id: "admin-login-token",
+                        r#type: "password",
+                        placeholder: "Admin-nyckel",
+                        value: "{token}",
+                        oninput: move |e| token.set(e.value()),
+                        onkeydown: move |e| {
+                            if e.key() == Key::Enter {
+                                login();
+                            }
+                        },
+                    }
+                }
+            }
+
+            if let Some(err) = login_error() {
+                p { class: "admin-error", "{err}" }
+            }
+
+            button {
+                class: "admin-button admin-button-submit",
+                disabled: !can_log_in(),
+                onclick: move |_| login(),
+                if is_logging_in() {
+                    span { class: "spinner" }
+                    "Loggar in\u{2026}"
+                } else {
+                    "Logga in"
+                }
+            }
+        }
+    }
+}
+
 async fn wait_for_debounce() {
     gloo_timers::future::sleep(Duration::from_millis(250)).await;
 }
@@ -118,14 +332,22 @@ async fn wait_for_debounce() {
 #[derive(Clone, Copy)]
 pub struct AdminCtx {
     pub admin_token: Signal<String>,
-    pub members: Signal<Vec<String>>,
-    pub original_members: Signal<Vec<String>>,
+    pub members: Signal<Vec<Member>>,
+    pub original_members: Signal<Vec<Member>>,
     pub current_data: Signal<Option<Data>>,
     pub history: Signal<Option<Result<Vec<HistoryEntry>, String>>>,
 }
 
 // ── Tab: Nytt album ───────────────────────────────────────────────────────────
 
+fn reveal_policy_label(policy: RevealPolicy) -> &'static str {
+    match policy {
+        RevealPolicy::AfterOwnReview => "När man själv har satt betyg",
+        RevealPolicy::AtMeetingTime => "När mötet börjar",
+        RevealPolicy::AdminReveal => "När en admin visar dem",
+    }
+}
+
 #[component]
 pub fn AdminAlbum() -> Element {
     let ctx = use_context::<AdminCtx>();
@@ -140,13 +362,44 @@ pub fn AdminAlbum() -> Element {
     let mut spotify_search_request_id = use_signal(|| 0_u64);
 
     let mut selected_album = use_signal(|| None::<SpotifyAlbumSearchItem>);
-    let mut picker = use_signal(String::new);
+    let mut picker = use_signal(|| None::<MemberId>);
     let mut meeting_date = use_signal(String::new);
     let mut meeting_time_val = use_signal(String::new);
     let mut meeting_location = use_signal(String::new);
+    let mut reveal_policy = use_signal(RevealPolicy::default);
+    let mut review_close_days = use_signal(|| DEFAULT_REVIEW_CLOSE_DAYS.to_string());
     let mut submit_state = use_signal(|| None::<Result<(), String>>);
     let mut is_editing_current = use_signal(|| false);
     let mut is_submitting = use_signal(|| false);
+    let mut reveal_state = use_signal(|| None::<Result<(), String>>);
+    let mut is_revealing = use_signal(|| false);
+    let mut lock_state = use_signal(|| None::<Result<(), String>>);
+    let mut is_locking = use_signal(|| false);
+
+    let mut picker_chosen = use_signal(|| false);
+
+    // Whose turn it is depends on who is away on the meeting date. Refetched when the
+    // current meeting changes, as the turn passes on.
+    let next_picker = use_resource(move || async move {
+        let _ = current_data();
+        let date = meeting_date();
+        get_next_picker((!date.is_empty()).then_some(date))
+            .await
+            .ok()
+            .flatten()
+    });
+    let next_picker_id = use_memo(move || next_picker().flatten().map(|m| m.id));
+
+    // A new meeting is for whoever's turn it is, unless the admin picks someone else.
+    use_effect(move || {
+        let Some(next) = next_picker_id() else {
+            return;
+        };
+        let chosen = picker_chosen() && picker().is_some();
+        if !is_editing_current() && !chosen && picker() != Some(next) {
+            picker.set(Some(next));
+        }
+    });
 
     rsx! {
         if let Some(data) = current_data() {
@@ -160,10 +413,13 @@ pub fn AdminAlbum() -> Element {
                                 onclick: move |_| {
                                     is_editing_current.set(false);
                                     selected_album.set(None);
-                                    picker.set(String::new());
+                                    picker_chosen.set(false);
+                                    picker.set(None);
                                     meeting_date.set(String::new());
                                     meeting_time_val.set(String::new());
                                     meeting_location.set(String::new());
+                                    reveal_policy.set(RevealPolicy::default());
+                                    review_close_days.set(DEFAULT_REVIEW_CLOSE_DAYS.to_string());
                                     spotify_query.set(String::new());
                                     spotify_search_state.set(None);
                                     submit_state.set(None);
@@ -172,6 +428,89 @@ pub fn AdminAlbum() -> Element {
                             }
                         }
                     } else {
+                        if let (Some(meeting_id), Some(meeting)) = (data.current_meeting_id.clone(), data.next_meeting.clone()) {
+                            if meeting.reviews_revealed {
+                                p { class: "admin-hint", "Betygen för nuvarande album är synliga för alla." }
+                            } else {
+                                div { class: "admin-reveal-row",
+                                    span { class: "admin-hint",
+                                        "Betygen visas {reveal_policy_label(meeting.reveal_policy).to_lowercase()}."
+                                    }
+                                    button {
+                                        class: "admin-button-ghost",
+                                        disabled: is_revealing(),
+                                        onclick: move |_| {
+                                            let token = admin_token();
+                                            let meeting_id = meeting_id.clone();
+                                            reveal_state.set(None);
+                                            is_revealing.set(true);
+                                            spawn(async move {
+                                                let result = admin_reveal_reviews(token, meeting_id)
+                                                    .await
+                                                    .map_err(|e| admin_error_message(&e));
+                                                if result.is_ok() {
+                                                    if let Ok(fresh_data) = get_current().await {
+                                                        current_data.set(Some(fresh_data));
+                                                    }
+                                                }
+                                                is_revealing.set(false);
+                                                reveal_state.set(Some(result));
+                                            });
+                                        },
+                                        "Visa betygen nu"
+                                    }
+                                }
+                            }
+                            if let Some(Err(err)) = reveal_state() {
+                                p { class: "admin-error", "Fel: {err}" }
+                            }
+                        }
+                        if let Some(meeting_id) = data.current_meeting_id.clone() {
+                            div { class: "admin-reveal-row",
+                                {
+                                    let (locked, close_days) = data
+                                        .next_meeting
+                                        .as_ref()
+                                        .map(|m| (m.reviews_locked, m.review_close_days))
+                                        .unwrap_or_default();
+                                    rsx! {
+                                        span { class: "admin-hint",
+                                            if locked {
+                                                "En admin har stängt recensionerna."
+                                            } else {
+                                                "Recensionerna stänger {close_days} dagar efter mötet."
+                                            }
+                                        }
+                                        button {
+                                            class: "admin-button-ghost",
+                                            disabled: is_locking(),
+                                            onclick: move |_| {
+                                                let token = admin_token();
+                                                let meeting_id = meeting_id.clone();
+                                                lock_state.set(None);
+                                                is_locking.set(true);
+                                                spawn(async move {
+                                                    let result = admin_set_reviews_locked(token, meeting_id, !locked)
+                                                        .await
+                                                        .map_err(|e| admin_error_message(&e));
+                                                    if result.is_ok() {
+                                                        if let Ok(fresh_data) = get_current().await {
+                                                            current_data.set(Some(fresh_data));
+                                                        }
+                                                    }
+                                                    is_locking.set(false);
+                                                    lock_state.set(Some(result));
+                                                });
+                                            },
+                                            if locked { "Öppna recensionerna" } else { "Stäng recensionerna" }
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(Err(err)) = lock_state() {
+                                p { class: "admin-error", "Fel: {err}" }
+                            }
+                        }
                         button {
                             class: "admin-button",
                             onclick: move |_| {
@@ -184,19 +523,21 @@ pub fn AdminAlbum() -> Element {
                                             image_url: if album.album_art.is_empty() {
                                                 None
                                             } else {
-                                                Some(album.album_art.clone())
+                                                Some(album_art_path(&album.id, ArtSize::Small))
                                             },
                                             spotify_url: album.spotify_url.clone(),
                                         }));
                                     }
                                     if let Some(person) = &data.current_person {
-                                        picker.set(person.to_string());
+                                        picker.set(Some(person.id));
                                     }
                                     if let Some(meeting) = &data.next_meeting {
                                         meeting_date.set(meeting.date.clone());
                                         meeting_time_val.set(meeting.time.clone().unwrap_or_default());
                                         meeting_location
                                             .set(meeting.location.clone().unwrap_or_default());
+                                        reveal_policy.set(meeting.reveal_policy);
+                                        review_close_days.set(meeting.review_close_days.to_string());
                                     }
                                 }
                                 is_editing_current.set(true);
@@ -204,6 +545,17 @@ pub fn AdminAlbum() -> Element {
                             },
                             "Redigera nuvarande"
                         }
+                        if let Some(next) = next_picker().flatten() {
+                            button {
+                                class: "admin-button-ghost",
+                                onclick: move |_| {
+                                    picker_chosen.set(false);
+                                    picker.set(Some(next.id));
+                                    submit_state.set(None);
+                                },
+                                "Förbered nästa möte ({next.name}s tur)"
+                            }
+                        }
                     }
                 }
             }
@@ -238,7 +590,7 @@ pub fn AdminAlbum() -> Element {
             } else {
                 input {
                     r#type: "text",
-                    placeholder: "Sök album...",
+                    placeholder: "Sök album eller klistra in en Spotify-länk...",
                     value: "{spotify_query}",
                     oninput: move |event| {
                         let query = event.value();
@@ -255,7 +607,7 @@ pub fn AdminAlbum() -> Element {
                         let token = admin_token();
                         if token.trim().is_empty() {
                             spotify_search_state
-                                .set(Some(Err("Ange admin-token först".to_string())));
+                                .set(Some(Err("Logga in först".to_string())));
                             return;
                         }
 
@@ -268,7 +620,7 @@ pub fn AdminAlbum() -> Element {
 
                             let result = admin_spotify_album_search(token, query)
                                 .await
-                                .map_err(|err| err.to_string());
+                                .map_err(|e| admin_error_message(&e));
 
                             if spotify_search_request_id() != current_request_id {
                                 return;
@@ -367,6 +719,37 @@ pub fn AdminAlbum() -> Element {
                         oninput: move |e| meeting_location.set(e.value()),
                     }
                 }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "reveal-policy", "Visa betygen" }
+                    select {
+                        id: "reveal-policy",
+                        onchange: move |e| {
+                            if let Some(policy) = RevealPolicy::parse(&e.value()) {
+                                reveal_policy.set(policy);
+                            }
+                        },
+                        for policy in RevealPolicy::ALL {
+                            option {
+                                value: policy.as_str(),
+                                selected: reveal_policy() == policy,
+                                "{reveal_policy_label(policy)}"
+                            }
+                        }
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "review-close-days",
+                        "Stäng recensionerna (dagar efter mötet)"
+                    }
+                    input {
+                        id: "review-close-days",
+                        r#type: "number",
+                        min: 0,
+                        max: 365,
+                        value: "{review_close_days}",
+                        oninput: move |e| review_close_days.set(e.value()),
+                    }
+                }
             }
         }
 
@@ -376,19 +759,25 @@ pub fn AdminAlbum() -> Element {
                 span { class: "required-star", " *" }
             }
             select {
-                value: "{picker}",
-                onchange: move |e| picker.set(e.value()),
+                onchange: move |e| {
+                    picker_chosen.set(true);
+                    picker.set(e.value().parse().ok());
+                },
                 option {
                     value: "",
                     disabled: true,
-                    selected: picker().is_empty(),
+                    selected: picker().is_none(),
                     "Välj person..."
                 }
                 for member in members() {
                     option {
-                        value: "{member}",
-                        selected: picker() == member,
-                        "{member}"
+                        value: "{member.id}",
+                        selected: picker() == Some(member.id),
+                        if !is_editing_current() && next_picker_id() == Some(member.id) {
+                            "{member.name} (på tur)"
+                        } else {
+                            "{member.name}"
+                        }
                     }
                 }
             }
@@ -402,15 +791,14 @@ pub fn AdminAlbum() -> Element {
             button {
                 class: "admin-button admin-button-submit",
                 disabled: selected_album().is_none()
-                    || picker().is_empty()
+                    || picker().is_none()
                     || meeting_date().is_empty()
                     || admin_token().trim().is_empty()
                     || is_submitting(),
                 onclick: move |_| {
                     let token = admin_token();
                     let Some(album) = selected_album() else { return; };
-                    let picker_val = picker();
-                    if picker_val.is_empty() { return; }
+                    let Some(picker_id) = picker() else { return; };
 
                     let opt_str = |s: String| -> Option<String> {
                         if s.trim().is_empty() { None } else { Some(s) }
@@ -418,42 +806,49 @@ pub fn AdminAlbum() -> Element {
                     let date = meeting_date();
                     let time = opt_str(meeting_time_val());
                     let location = opt_str(meeting_location());
-                    let art_url = album.image_url.unwrap_or_default();
+                    let reveal_policy_val = reveal_policy();
+                    let Ok(close_days) = review_close_days().trim().parse::<u32>() else {
+                        submit_state.set(Some(Err("Ange antal dagar som ett heltal".to_string())));
+                        return;
+                    };
                     let editing = is_editing_current();
 
                     submit_state.set(None);
                     is_submitting.set(true);
                     spawn(async move {
                         let req = SetCurrentRequest {
-                            album_id: album.id,
-                            album_name: album.name,
-                            album_artist: album.artists,
-                            album_art_url: art_url,
-                            album_spotify_url: album.spotify_url,
-                            picker: picker_val,
+                            album: album.id,
+                            picker_id,
                             meeting_date: date,
                             meeting_time: time,
                             meeting_location: location,
+                            reveal_policy: reveal_policy_val,
+                            review_close_days: close_days,
                         };
                         let result = if editing {
-                            admin_update_current(token, req).await.map_err(|e| e.to_string())
+                            admin_update_current(token, req).await
                         } else {
-                            admin_set_current(token, req).await.map_err(|e| e.to_string())
-                        };
+                            admin_set_current(token, req).await
+                        }
+                        .map_err(|e| admin_error_message(&e));
                         if result.is_ok() {
                             if let Ok(fresh_data) = get_current().await {
                                 current_data.set(Some(fresh_data));
                             }
                             if !editing {
-                                let fresh = get_history().await.map_err(|e| e.to_string());
+                                let fresh =
+                                    get_history().await.map_err(|e| admin_error_message(&e));
                                 history.set(Some(fresh));
                             }
                             is_editing_current.set(false);
                             selected_album.set(None);
-                            picker.set(String::new());
+                            picker_chosen.set(false);
+                            picker.set(None);
                             meeting_date.set(String::new());
                             meeting_time_val.set(String::new());
                             meeting_location.set(String::new());
+                            reveal_policy.set(RevealPolicy::default());
+                            review_close_days.set(DEFAULT_REVIEW_CLOSE_DAYS.to_string());
                             spotify_query.set(String::new());
                             spotify_search_state.set(None);
                         }
@@ -488,10 +883,46 @@ pub fn AdminRotation() -> Element {
     let admin_token = ctx.admin_token;
     let mut members = ctx.members;
     let mut original_members = ctx.original_members;
+    let mut current_data = ctx.current_data;
 
     let mut reorder_state = use_signal(|| None::<Result<(), String>>);
     let mut is_submitting = use_signal(|| false);
-    let mut deleting_member: Signal<Option<String>> = use_signal(|| None);
+    let mut deleting_member: Signal<Option<MemberId>> = use_signal(|| None);
+
+    // (id, edited name) of the member being renamed.
+    let mut renaming = use_signal(|| None::<(MemberId, String)>);
+    let mut new_member = use_signal(String::new);
+    // `None` adds the member last.
+    let mut new_member_position = use_signal(|| None::<usize>);
+    let mut member_state = use_signal(|| None::<Result<String, String>>);
+    let mut is_saving_member = use_signal(|| false);
+
+    let mut removed_members = use_resource(move || async move {
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        Some(
+            admin_removed_members(token)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    // Adding, renaming and restoring save the order on the server, so unsaved
+    // reordering has to be saved first.
+    let order_dirty = move || members() != original_members();
+    let member_busy = move || {
+        admin_token().trim().is_empty()
+            || is_submitting()
+            || is_saving_member()
+            || deleting_member().is_some()
+    };
+
+    let mut set_saved_order = move |list: Vec<Member>| {
+        members.set(list.clone());
+        original_members.set(list);
+    };
 
     rsx! {
         div { class: "card admin-section",
@@ -500,63 +931,124 @@ pub fn AdminRotation() -> Element {
 
             div { class: "member-order-list",
                 for (i , member) in members().iter().enumerate() {
-                    div { key: "{member}", class: "member-order-row",
-                        span { class: "member-order-name", "{member}" }
-                        div { class: "member-order-buttons",
-                            button {
-                                class: "admin-button-ghost",
-                                disabled: i == 0 || is_submitting() || deleting_member().is_some(),
-                                onclick: move |_| {
-                                    let mut list = members();
-                                    if i > 0 {
-                                        list.swap(i - 1, i);
-                                        members.write().clone_from(&list);
-                                        reorder_state.set(None);
-                                    }
-                                },
-                                "↑"
+                    div { key: "{member.id}", class: "member-order-row",
+                        if let Some((member_id, draft)) = renaming().filter(|(id, _)| *id == member.id) {
+                            input {
+                                class: "member-rename-input",
+                                r#type: "text",
+                                value: "{draft}",
+                                oninput: move |e: FormEvent| renaming.set(Some((member_id, e.value()))),
                             }
-                            button {
-                                class: "admin-button-ghost",
-                                disabled: i + 1 >= members().len() || is_submitting() || deleting_member().is_some(),
-                                onclick: move |_| {
-                                    let mut list = members();
-                                    if i + 1 < list.len() {
-                                        list.swap(i, i + 1);
-                                        members.write().clone_from(&list);
-                                        reorder_state.set(None);
-                                    }
-                                },
-                                "↓"
+                            div { class: "member-order-buttons",
+                                button {
+                                    class: "admin-button",
+                                    disabled: draft.trim().is_empty() || member_busy(),
+                                    onclick: {
+                                        let old_name = member.name.clone();
+                                        let new_name = draft.trim().to_string();
+                                        move |_| {
+                                            let token = admin_token();
+                                            let (old_name, new_name) = (old_name.clone(), new_name.clone());
+                                            member_state.set(None);
+                                            is_saving_member.set(true);
+                                            spawn(async move {
+                                                let result = admin_rename_member(token, member_id, new_name.clone())
+                                                    .await
+                                                    .map(|()| format!("✓ {old_name} heter nu {new_name}"))
+                                                    .map_err(|e| admin_error_message(&e));
+                                                if result.is_ok() {
+                                                    let rename = |list: &mut Vec<Member>| {
+                                                        for member in list.iter_mut().filter(|m| m.id == member_id) {
+                                                            member.name = new_name.as_str().into();
+                                                        }
+                                                    };
+                                                    rename(&mut members.write());
+                                                    rename(&mut original_members.write());
+                                                    if let Ok(fresh_data) = get_current().await {
+                                                        current_data.set(Some(fresh_data));
+                                                    }
+                                                    renaming.set(None);
+                                                }
+                                                is_saving_member.set(false);
+                                                member_state.set(Some(result));
+                                            });
+                                        }
+                                    },
+                                    "Spara"
+                                }
+                                button {
+                                    class: "admin-button-ghost",
+                                    disabled: is_saving_member(),
+                                    onclick: move |_| renaming.set(None),
+                                    "Avbryt"
+                                }
                             }
-                            button {
-                                class: "admin-button-ghost admin-history-delete",
-                                title: "Ta bort medlem",
-                                disabled: admin_token().trim().is_empty()
-                                    || is_submitting()
-                                    || deleting_member().is_some(),
-                                onclick: {
-                                    let name = member.clone();
-                                    move |_| {
-                                        let token = admin_token();
-                                        let member_name = name.clone();
-                                        deleting_member.set(Some(member_name.clone()));
-                                        spawn(async move {
-                                            if admin_delete_member(token, member_name.clone())
-                                                .await
-                                                .is_ok()
-                                            {
-                                                members.write().retain(|m| *m != member_name);
-                                                original_members.write().retain(|m| *m != member_name);
-                                            }
-                                            deleting_member.set(None);
-                                        });
+                        } else {
+                            span { class: "member-order-name", "{member.name}" }
+                            div { class: "member-order-buttons",
+                                button {
+                                    class: "admin-button-ghost",
+                                    disabled: i == 0 || is_submitting() || deleting_member().is_some(),
+                                    onclick: move |_| {
+                                        let mut list = members();
+                                        if i > 0 {
+                                            list.swap(i - 1, i);
+                                            members.write().clone_from(&list);
+                                            reorder_state.set(None);
+                                        }
+                                    },
+                                    "↑"
+                                }
+                                button {
+                                    class: "admin-button-ghost",
+                                    disabled: i + 1 >= members().len() || is_submitting() || deleting_member().is_some(),
+                                    onclick: move |_| {
+                                        let mut list = members();
+                                        if i + 1 < list.len() {
+                                            list.swap(i, i + 1);
+                                            members.write().clone_from(&list);
+                                            reorder_state.set(None);
+                                        }
+                                    },
+                                    "↓"
+                                }
+                                button {
+                                    class: "admin-button-ghost",
+                                    title: "Byt namn",
+                                    disabled: member_busy() || order_dirty(),
+                                    onclick: {
+                                        let member = member.clone();
+                                        move |_| {
+                                            member_state.set(None);
+                                            renaming.set(Some((member.id, member.name.to_string())));
+                                        }
+                                    },
+                                    Icon { icon: FiEdit2 }
+                                }
+                                button {
+                                    class: "admin-button-ghost admin-history-delete",
+                                    title: "Ta bort medlem",
+                                    disabled: member_busy(),
+                                    onclick: {
+                                        let member_id = member.id;
+                                        move |_| {
+                                            let token = admin_token();
+                                            deleting_member.set(Some(member_id));
+                                            spawn(async move {
+                                                if admin_delete_member(token, member_id).await.is_ok() {
+                                                    members.write().retain(|m| m.id != member_id);
+                                                    original_members.write().retain(|m| m.id != member_id);
+                                                    removed_members.restart();
+                                                }
+                                                deleting_member.set(None);
+                                            });
+                                        }
+                                    },
+                                    if deleting_member() == Some(member.id) {
+                                        span { class: "spinner" }
+                                    } else {
+                                        Icon { icon: FiTrash2 }
                                     }
-                                },
-                                if deleting_member().as_deref() == Some(member.as_str()) {
-                                    span { class: "spinner" }
-                                } else {
-                                    Icon { icon: FiTrash2 }
                                 }
                             }
                         }
@@ -566,16 +1058,17 @@ pub fn AdminRotation() -> Element {
 
             button {
                 class: "admin-button admin-button-submit",
-                disabled: members() == original_members() || admin_token().trim().is_empty() || is_submitting(),
+                disabled: !order_dirty() || admin_token().trim().is_empty() || is_submitting(),
                 onclick: move |_| {
                     let token = admin_token();
                     let ordered = members();
                     reorder_state.set(None);
                     is_submitting.set(true);
                     spawn(async move {
-                        let result = admin_reorder_members(token, ordered.clone())
+                        let ordered_ids = ordered.iter().map(|m| m.id).collect();
+                        let result = admin_reorder_members(token, ordered_ids)
                             .await
-                            .map_err(|e| e.to_string());
+                            .map_err(|e| admin_error_message(&e));
                         if result.is_ok() {
                             original_members.write().clone_from(&ordered);
                         }
@@ -599,35 +1092,521 @@ pub fn AdminRotation() -> Element {
                 }
             }
         }
-    }
-}
 
-// ── Tab: Historik ─────────────────────────────────────────────────────────────
+        div { class: "card admin-section",
+            h2 { "Lägg till medlem" }
+            if order_dirty() {
+                p { class: "admin-hint", "Spara ändringarna i ordningen först." }
+            }
 
-#[component]
-pub fn AdminHistory() -> Element {
-    let ctx = use_context::<AdminCtx>();
-    let admin_token = ctx.admin_token;
-    let mut history = ctx.history;
-    let mut deleting_id: Signal<Option<String>> = use_signal(|| None);
+            div { class: "admin-field-group",
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "new-member-name", "Namn" }
+                    input {
+                        id: "new-member-name",
+                        r#type: "text",
+                        placeholder: "Namn...",
+                        value: "{new_member}",
+                        oninput: move |e| new_member.set(e.value()),
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "new-member-position", "Plats i rotationen" }
+                    select {
+                        id: "new-member-position",
+                        onchange: move |e| new_member_position.set(e.value().parse().ok()),
+                        option { value: "", selected: new_member_position().is_none(), "Sist" }
+                        option { value: "0", selected: new_member_position() == Some(0), "Först" }
+                        for (i , member) in members().iter().enumerate().take(members().len().saturating_sub(1)) {
+                            option {
+                                value: "{i + 1}",
+                                selected: new_member_position() == Some(i + 1),
+                                "Efter {member.name}"
+                            }
+                        }
+                    }
+                }
+            }
 
-    // Refresh history every time this tab is mounted.
-    use_effect(move || {
-        history.set(None);
-        spawn(async move {
-            let result = get_history().await.map_err(|e| e.to_string());
-            history.set(Some(result));
-        });
-    });
+            button {
+                class: "admin-button admin-button-submit",
+                disabled: new_member().trim().is_empty() || member_busy() || order_dirty(),
+                onclick: move |_| {
+                    let token = admin_token();
+                    let name = new_member().trim().to_string();
+                    let position = new_member_position().unwrap_or(members().len()) as u32;
+                    member_state.set(None);
+                    is_saving_member.set(true);
+                    spawn(async move {
+                        let result = admin_add_member(token, name.clone(), position)
+                            .await
+                            .map(|list| {
+                                set_saved_order(list);
+                                new_member.set(String::new());
+                                new_member_position.set(None);
+                                format!("✓ {name} har lagts till")
+                            })
+                            .map_err(|e| admin_error_message(&e));
+                        is_saving_member.set(false);
+                        member_state.set(Some(result));
+                    });
+                },
+                if is_saving_member() {
+                    span { class: "spinner" }
+                    "Sparar\u{2026}"
+                } else {
+                    "Lägg till"
+                }
+            }
+
+            match member_state() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "admin-success", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "admin-error", "Fel: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
 
-    rsx! {
         div { class: "card admin-section",
-            h2 { "Historik" }
-            p { class: "admin-hint", "Ta bort tidigare poster." }
+            h2 { "Borttagna medlemmar" }
+            p { class: "admin-hint",
+                "Återställda medlemmar hamnar sist i rotationen, med sina recensioner kvar."
+            }
 
-            match history() {
-                None => rsx! {
-                    p { class: "admin-hint", "Laddar\u{2026}" }
+            match removed_members() {
+                None | Some(None) => rsx! {
+                    p { class: "admin-hint", "Logga in för att se borttagna medlemmar." }
+                },
+                Some(Some(Err(e))) => rsx! {
+                    p { class: "admin-error", "Fel: {e}" }
+                },
+                Some(Some(Ok(removed))) if removed.is_empty() => rsx! {
+                    p { class: "admin-hint", "Inga borttagna medlemmar." }
+                },
+                Some(Some(Ok(removed))) => rsx! {
+                    div { class: "member-order-list",
+                        for member in removed {
+                            div { key: "{member.id}", class: "member-order-row",
+                                span { class: "member-order-name", "{member.name}" }
+                                span { class: "member-removed-at", "Borttagen {member.deleted_at}" }
+                                div { class: "member-order-buttons",
+                                    button {
+                                        class: "admin-button-ghost",
+                                        disabled: member_busy() || order_dirty(),
+                                        onclick: {
+                                            let (member_id, name) = (member.id, member.name.clone());
+                                            move |_| {
+                                                let token = admin_token();
+                                                let name = name.clone();
+                                                member_state.set(None);
+                                                is_saving_member.set(true);
+                                                spawn(async move {
+                                                    let result = admin_restore_member(token, member_id)
+                                                        .await
+                                                        .map(|list| {
+                                                            set_saved_order(list);
+                                                            removed_members.restart();
+                                                            format!("✓ {name} är tillbaka")
+                                                        })
+                                                        .map_err(|e| admin_error_message(&e));
+                                                    is_saving_member.set(false);
+                                                    member_state.set(Some(result));
+                                                });
+                                            }
+                                        },
+                                        "Återställ"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        AdminFairness {}
+        AdminAvailability {}
+        AdminRotationSkips {}
+    }
+}
+
+fn pick_imbalance_label(imbalance: PickImbalance) -> &'static str {
+    match imbalance {
+        PickImbalance::Behind => "Ligger efter",
+        PickImbalance::Ahead => "Ligger före",
+    }
+}
+
+/// Picks per member against their share, so the rotation can be corrected by reordering.
+#[component]
+fn AdminFairness() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+    let current_data = ctx.current_data;
+
+    // Refetched when the current meeting or rotation changes.
+    let report = use_resource(move || async move {
+        let _ = current_data();
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        Some(
+            admin_fairness_report(token)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Rättvisa" }
+            p { class: "admin-hint",
+                "Antal val sedan medlemmen gick med, mot dess andel av mötena sedan dess. "
+                "Den som ligger en hel tur efter eller före flaggas."
+            }
+
+            match report() {
+                None | Some(None) => rsx! {
+                    p { class: "admin-hint", "Laddar\u{2026}" }
+                },
+                Some(Some(Err(e))) => rsx! {
+                    p { class: "admin-error", "Fel: {e}" }
+                },
+                Some(Some(Ok(report))) => rsx! {
+                    p { class: "admin-hint", "{report.total_meetings} möten totalt." }
+                    div { class: "admin-fairness-wrap",
+                        table { class: "admin-fairness-table",
+                            thead {
+                                tr {
+                                    th { "Medlem" }
+                                    th { "Val" }
+                                    th { "Andel" }
+                                    th { "Senaste val" }
+                                    th { "Överhoppad" }
+                                    th {}
+                                }
+                            }
+                            tbody {
+                                for row in report.members {
+                                    tr { key: "{row.member.id}",
+                                        td {
+                                            "{row.member.name}"
+                                            if row.joined_mid_cycle {
+                                                if let Some(joined_at) = &row.joined_at {
+                                                    span { class: "admin-fairness-joined",
+                                                        "Gick med {joined_at.get(..10).unwrap_or(joined_at)}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        td { "{row.picks}" }
+                                        td { "{row.expected_picks:.1}" }
+                                        td {
+                                            match (&row.last_pick_date, row.days_since_last_pick) {
+                                                (Some(date), Some(days)) => rsx! { "{date} ({days} dagar sedan)" },
+                                                (Some(date), None) => rsx! { "{date}" },
+                                                (None, _) => rsx! { "Aldrig" },
+                                            }
+                                        }
+                                        td { "{row.skipped_turns}" }
+                                        td {
+                                            if let Some(imbalance) = row.imbalance {
+                                                span {
+                                                    class: "admin-badge",
+                                                    class: if imbalance == PickImbalance::Behind { "admin-badge-behind" },
+                                                    "{pick_imbalance_label(imbalance)} ({row.balance:+.1})"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+fn skip_reason_label(reason: SkipReason) -> &'static str {
+    match reason {
+        SkipReason::Unavailable => "Borta",
+        SkipReason::Passed => "Förbigången",
+    }
+}
+
+/// Dates when members are away. Their turn is skipped, but they keep their place.
+#[component]
+fn AdminAvailability() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+    let members = ctx.members;
+    let mut current_data = ctx.current_data;
+
+    let mut ranges = use_resource(move || async move {
+        get_unavailability()
+            .await
+            .map_err(|e| admin_error_message(&e))
+    });
+
+    let mut member_id = use_signal(|| None::<MemberId>);
+    let mut start_date = use_signal(String::new);
+    let mut end_date = use_signal(String::new);
+    let mut note = use_signal(String::new);
+    let mut availability_error = use_signal(|| None::<String>);
+    let mut is_saving = use_signal(|| false);
+
+    // Who is away changes whose turn it is, so the rotation is fetched again too.
+    let mut saved = move |result: Result<Vec<Unavailability>, ApiError>| match result {
+        Ok(_) => {
+            ranges.restart();
+            spawn(async move {
+                if let Ok(fresh_data) = get_current().await {
+                    current_data.set(Some(fresh_data));
+                }
+            });
+        }
+        Err(e) => availability_error.set(Some(admin_error_message(&e))),
+    };
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Frånvaro" }
+            p { class: "admin-hint",
+                "Den som är borta på mötesdagen hoppas över och behåller sin plats till nästa varv."
+            }
+
+            match ranges() {
+                None => rsx! {
+                    p { class: "admin-hint", "Laddar\u{2026}" }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "admin-error", "Fel: {e}" }
+                },
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    p { class: "admin-hint", "Ingen frånvaro inlagd." }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "member-order-list",
+                        for range in list {
+                            div { key: "{range.id}", class: "member-order-row",
+                                span { class: "member-order-name", "{range.member_name}" }
+                                span { class: "member-removed-at",
+                                    "{range.start_date} – {range.end_date}"
+                                    if let Some(note) = &range.note {
+                                        " ({note})"
+                                    }
+                                }
+                                div { class: "member-order-buttons",
+                                    button {
+                                        class: "admin-button-ghost",
+                                        disabled: is_saving(),
+                                        onclick: move |_| {
+                                            let token = admin_token();
+                                            availability_error.set(None);
+                                            is_saving.set(true);
+                                            spawn(async move {
+                                                saved(admin_remove_unavailability(token, range.id).await);
+                                                is_saving.set(false);
+                                            });
+                                        },
+                                        "Ta bort"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+
+            div { class: "admin-field-group",
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "away-member", "Medlem" }
+                    select {
+                        id: "away-member",
+                        onchange: move |e| member_id.set(e.value().parse().ok()),
+                        option {
+                            value: "",
+                            disabled: true,
+                            selected: member_id().is_none(),
+                            "Välj person..."
+                        }
+                        for member in members() {
+                            option {
+                                value: "{member.id}",
+                                selected: member_id() == Some(member.id),
+                                "{member.name}"
+                            }
+                        }
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "away-start", "Från och med" }
+                    input {
+                        id: "away-start",
+                        r#type: "date",
+                        value: "{start_date}",
+                        oninput: move |e| {
+                            let val = e.value();
+                            if end_date().is_empty() || end_date() < val {
+                                end_date.set(val.clone());
+                            }
+                            start_date.set(val);
+                        },
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "away-end", "Till och med" }
+                    input {
+                        id: "away-end",
+                        r#type: "date",
+                        min: "{start_date}",
+                        value: "{end_date}",
+                        oninput: move |e| end_date.set(e.value()),
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "away-note", "Anteckning" }
+                    input {
+                        id: "away-note",
+                        r#type: "text",
+                        maxlength: MAX_UNAVAILABILITY_NOTE_LEN as i64,
+                        placeholder: "t.ex. Resa",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value()),
+                    }
+                }
+            }
+
+            button {
+                class: "admin-button admin-button-submit",
+                disabled: member_id().is_none() || start_date().is_empty() || end_date().is_empty()
+                    || admin_token().trim().is_empty() || is_saving(),
+                onclick: move |_| {
+                    let token = admin_token();
+                    let Some(id) = member_id() else { return; };
+                    let note_val = note().trim().to_string();
+                    availability_error.set(None);
+                    is_saving.set(true);
+                    spawn(async move {
+                        let result = admin_add_unavailability(
+                                token,
+                                id,
+                                start_date(),
+                                end_date(),
+                                (!note_val.is_empty()).then_some(note_val),
+                            )
+                            .await;
+                        if result.is_ok() {
+                            member_id.set(None);
+                            start_date.set(String::new());
+                            end_date.set(String::new());
+                            note.set(String::new());
+                        }
+                        saved(result);
+                        is_saving.set(false);
+                    });
+                },
+                if is_saving() {
+                    span { class: "spinner" }
+                    "Sparar\u{2026}"
+                } else {
+                    "Lägg till frånvaro"
+                }
+            }
+
+            if let Some(err) = availability_error() {
+                p { class: "admin-error", "Fel: {err}" }
+            }
+        }
+    }
+}
+
+/// Turns that were skipped when meetings were set, so nobody is passed over unnoticed.
+#[component]
+fn AdminRotationSkips() -> Element {
+    let admin_token = use_context::<AdminCtx>().admin_token;
+
+    let skips = use_resource(move || async move {
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        Some(
+            admin_rotation_skips(token)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Överhoppade turer" }
+            p { class: "admin-hint",
+                "Medlemmar som stod på tur när ett möte sattes, men inte valde album."
+            }
+
+            match skips() {
+                None | Some(None) => rsx! {
+                    p { class: "admin-hint", "Laddar\u{2026}" }
+                },
+                Some(Some(Err(e))) => rsx! {
+                    p { class: "admin-error", "Fel: {e}" }
+                },
+                Some(Some(Ok(list))) if list.is_empty() => rsx! {
+                    p { class: "admin-hint", "Ingen har hoppats över än." }
+                },
+                Some(Some(Ok(list))) => rsx! {
+                    div { class: "member-order-list",
+                        for skip in list {
+                            div {
+                                key: "{skip.meeting_id}-{skip.member_id}",
+                                class: "member-order-row",
+                                span { class: "member-order-name", "{skip.member_name}" }
+                                span { class: "member-removed-at",
+                                    "{skip.meeting_date} \u{2022} {skip.album_name}"
+                                }
+                                span { class: "admin-badge", "{skip_reason_label(skip.reason)}" }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+// ── Tab: Historik ─────────────────────────────────────────────────────────────
+
+#[component]
+pub fn AdminHistory() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+    let mut history = ctx.history;
+    let mut deleting_id: Signal<Option<String>> = use_signal(|| None);
+
+    // Refresh history every time this tab is mounted.
+    use_effect(move || {
+        history.set(None);
+        spawn(async move {
+            let result = get_history().await.map_err(|e| admin_error_message(&e));
+            history.set(Some(result));
+        });
+    });
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Historik" }
+            p { class: "admin-hint", "Ta bort tidigare poster." }
+
+            match history() {
+                None => rsx! {
+                    p { class: "admin-hint", "Laddar\u{2026}" }
                 },
                 Some(Err(e)) => rsx! {
                     p { class: "admin-error", "Fel: {e}" }
@@ -701,39 +1680,41 @@ pub fn AdminPasswords() -> Element {
     let admin_token = ctx.admin_token;
     let members = ctx.members;
 
-    let mut pw_member = use_signal(String::new);
-    let mut pw_result = use_signal(|| None::<Result<String, String>>);
+    let mut pw_member = use_signal(|| None::<Member>);
+    // (link, expires at) of the invite just created.
+    let mut pw_result = use_signal(|| None::<Result<(String, String), String>>);
     let mut pw_copied = use_signal(|| false);
     let mut is_submitting = use_signal(|| false);
 
     rsx! {
         div { class: "card admin-section",
-            h2 { "Generera lösenord" }
+            h2 { "Inbjudningslänk" }
             p { class: "admin-hint",
-                "Generera ett slumpmässigt lösenord för en medlem. \
-                 Lösenordet visas en gång – dela det med medlemmen direkt."
+                "Skapa en länk där medlemmen väljer sitt eget lösenord. \
+                 Länken kan användas en gång och slutar gälla efter en vecka, \
+                 eller när en ny länk skapas för samma medlem."
             }
 
             div { class: "admin-field",
                 label { class: "admin-label", r#for: "pw-member", "Medlem" }
                 select {
                     id: "pw-member",
-                    value: "{pw_member}",
                     onchange: move |e| {
-                        pw_member.set(e.value());
+                        let id = e.value().parse::<MemberId>().ok();
+                        pw_member.set(members().into_iter().find(|m| Some(m.id) == id));
                         pw_result.set(None);
                     },
                     option {
                         value: "",
                         disabled: true,
-                        selected: pw_member().is_empty(),
+                        selected: pw_member().is_none(),
                         "Välj medlem..."
                     }
                     for member in members() {
                         option {
-                            value: "{member}",
-                            selected: pw_member() == member,
-                            "{member}"
+                            value: "{member.id}",
+                            selected: pw_member().is_some_and(|m| m.id == member.id),
+                            "{member.name}"
                         }
                     }
                 }
@@ -741,25 +1722,33 @@ pub fn AdminPasswords() -> Element {
 
             button {
                 class: "admin-button admin-button-submit",
-                disabled: pw_member().is_empty() || admin_token().trim().is_empty() || is_submitting(),
+                disabled: pw_member().is_none() || admin_token().trim().is_empty() || is_submitting(),
                 onclick: move |_| {
                     let token = admin_token();
-                    let name = pw_member();
+                    let Some(member) = pw_member() else { return; };
                     pw_result.set(None);
                     is_submitting.set(true);
                     spawn(async move {
-                        let result = admin_set_member_password(token, name)
-                            .await
-                            .map_err(|e| e.to_string());
+                        let result = match admin_create_member_invite(token, member.id).await {
+                            Ok(invite) => {
+                                let origin = eval("return window.location.origin;")
+                                    .join::<String>()
+                                    .await
+                                    .unwrap_or_default();
+                                let link = format!("{origin}{}", member_invite_path(&invite.token));
+                                Ok((link, invite.expires_at))
+                            }
+                            Err(e) => Err(admin_error_message(&e)),
+                        };
                         is_submitting.set(false);
                         pw_result.set(Some(result));
                     });
                 },
                 if is_submitting() {
                     span { class: "spinner" }
-                    "Genererar\u{2026}"
+                    "Skapar\u{2026}"
                 } else {
-                    "Generera lösenord"
+                    "Skapa länk"
                 }
             }
 
@@ -768,7 +1757,7 @@ pub fn AdminPasswords() -> Element {
             }
         }
 
-        if let Some(Ok(ref plain)) = pw_result() {
+        if let Some(Ok((ref link, ref expires_at))) = pw_result() {
             div {
                 class: "admin-pw-modal-backdrop",
                 onclick: move |_| {
@@ -780,15 +1769,17 @@ pub fn AdminPasswords() -> Element {
                     onclick: move |e| e.stop_propagation(),
 
                     div { class: "admin-pw-modal-header",
-                        h2 { "Lösenord genererat" }
+                        h2 { "Inbjudningslänk skapad" }
                         p { class: "admin-pw-modal-member",
                             "för "
-                            strong { "{pw_member()}" }
+                            if let Some(member) = pw_member() {
+                                strong { "{member.name}" }
+                            }
                         }
                     }
 
                     div { class: "admin-pw-modal-code-wrap",
-                        code { class: "admin-pw-code", "{plain}" }
+                        code { class: "admin-pw-code", "{link}" }
                         button {
                             class: if pw_copied() {
                                 "admin-button admin-pw-copy-btn admin-pw-copy-btn--done"
@@ -796,7 +1787,7 @@ pub fn AdminPasswords() -> Element {
                                 "admin-button admin-pw-copy-btn"
                             },
                             onclick: {
-                                let text = plain.clone();
+                                let text = link.clone();
                                 move |_| {
                                     let text = text.clone();
                                     spawn(async move {
@@ -815,9 +1806,9 @@ pub fn AdminPasswords() -> Element {
                     div { class: "admin-pw-modal-warning",
                         span { class: "admin-pw-warning-icon", "⚠" }
                         p {
-                            "Det här lösenordet visas bara en gång och "
+                            "Länken visas bara en gång och "
                             strong { "kan inte återskapas" }
-                            ". Dela det med medlemmen innan du stänger."
+                            ". Dela den med medlemmen innan du stänger. Den gäller till {expires_at}."
                         }
                     }
 
@@ -834,3 +1825,661 @@ pub fn AdminPasswords() -> Element {
         }
     }
 }
+
+// ── Tab: Åtkomst ──────────────────────────────────────────────────────────────
+
+/// How long a new admin credential is valid, in days. `None` never expires.
+const CREDENTIAL_VALIDITY: [(Option<u32>, &str); 4] = [
+    (None, "Går inte ut"),
+    (Some(30), "30 dagar"),
+    (Some(90), "90 dagar"),
+    (Some(365), "1 år"),
+];
+
+#[component]
+pub fn AdminCredentials() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+    let members = ctx.members;
+
+    let mut access = use_resource(move || async move {
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        Some(
+            admin_access(token)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    let mut new_label = use_signal(String::new);
+    let mut new_valid_days = use_signal(|| None::<u32>);
+    // (label, token) of the credential just created. The token is only shown here.
+    let mut created = use_signal(|| None::<Result<(String, String), String>>);
+    let mut revoke_state = use_signal(|| None::<Result<String, String>>);
+    let mut admin_member_state = use_signal(|| None::<Result<String, String>>);
+    let mut is_working = use_signal(|| false);
+
+    let Some(Some(loaded)) = access() else {
+        return rsx! {
+            div { class: "card admin-section",
+                h2 { "Åtkomst" }
+                p { class: "admin-hint", "Logga in för att hantera åtkomst." }
+            }
+        };
+    };
+    let access_data = match loaded {
+        Ok(data) => data,
+        Err(e) => {
+            return rsx! {
+                div { class: "card admin-section",
+                    h2 { "Åtkomst" }
+                    p { class: "admin-error", "Fel: {e}" }
+                }
+            };
+        }
+    };
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Admin-nycklar" }
+            p { class: "admin-hint",
+                "Varje nyckel har ett namn och kan återkallas, till exempel när någon slutar vara admin."
+            }
+
+            div { class: "member-order-list",
+                for credential in access_data.credentials {
+                    div { key: "{credential.id}", class: "member-order-row",
+                        span { class: "member-order-name", "{credential.label}" }
+                        span { class: "member-removed-at",
+                            if let Some(revoked_at) = &credential.revoked_at {
+                                "Återkallad {revoked_at}"
+                            } else if let Some(expires_at) = &credential.expires_at {
+                                "Går ut {expires_at}"
+                            } else {
+                                "Skapad {credential.created_at}"
+                            }
+                        }
+                        if credential.revoked_at.is_none() {
+                            div { class: "member-order-buttons",
+                                button {
+                                    class: "admin-button-ghost admin-history-delete",
+                                    disabled: is_working(),
+                                    onclick: {
+                                        let (id, label) = (credential.id, credential.label.clone());
+                                        move |_| {
+                                            let (token, label) = (admin_token(), label.clone());
+                                            revoke_state.set(None);
+                                            is_working.set(true);
+                                            spawn(async move {
+                                                let result = admin_revoke_credential(token, id)
+                                                    .await
+                                                    .map(|()| format!("✓ {label} är återkallad"))
+                                                    .map_err(|e| admin_error_message(&e));
+                                                access.restart();
+                                                is_working.set(false);
+                                                revoke_state.set(Some(result));
+                                            });
+                                        }
+                                    },
+                                    "Återkalla"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            match revoke_state() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "admin-success", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "admin-error", "Fel: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
+
+        div { class: "card admin-section",
+            h2 { "Ny admin-nyckel" }
+
+            div { class: "admin-field-group",
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "new-credential-label", "Namn" }
+                    input {
+                        id: "new-credential-label",
+                        r#type: "text",
+                        placeholder: "Till exempel vem nyckeln är till...",
+                        value: "{new_label}",
+                        oninput: move |e| new_label.set(e.value()),
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "new-credential-validity", "Giltighet" }
+                    select {
+                        id: "new-credential-validity",
+                        onchange: move |e| new_valid_days.set(e.value().parse().ok()),
+                        for (days , label) in CREDENTIAL_VALIDITY {
+                            option {
+                                value: days.map(|d| d.to_string()).unwrap_or_default(),
+                                selected: new_valid_days() == days,
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+            }
+
+            button {
+                class: "admin-button admin-button-submit",
+                disabled: new_label().trim().is_empty() || is_working(),
+                onclick: move |_| {
+                    let token = admin_token();
+                    let label = new_label().trim().to_string();
+                    let valid_days = new_valid_days();
+                    created.set(None);
+                    is_working.set(true);
+                    spawn(async move {
+                        let result = admin_create_credential(token, label.clone(), valid_days)
+                            .await
+                            .map(|plain| (label, plain))
+                            .map_err(|e| admin_error_message(&e));
+                        if result.is_ok() {
+                            new_label.set(String::new());
+                            access.restart();
+                        }
+                        is_working.set(false);
+                        created.set(Some(result));
+                    });
+                },
+                "Skapa nyckel"
+            }
+
+            match created() {
+                Some(Ok((label, plain))) => rsx! {
+                    p { class: "admin-success", "✓ Nyckel för {label} skapad. Den visas bara nu:" }
+                    code { class: "admin-pw-code", "{plain}" }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "admin-error", "Fel: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
+
+        div { class: "card admin-section",
+            h2 { "Admin-medlemmar" }
+            p { class: "admin-hint",
+                "Medlemmar med adminrättigheter loggar in här med sitt eget lösenord."
+            }
+
+            div { class: "member-order-list",
+                for member in members() {
+                    div { key: "{member.id}", class: "member-order-row",
+                        span { class: "member-order-name", "{member.name}" }
+                        if access_data.admin_members.contains(&member.id) {
+                            span { class: "admin-badge", "Admin" }
+                        }
+                        div { class: "member-order-buttons",
+                            button {
+                                class: "admin-button-ghost",
+                                disabled: is_working(),
+                                onclick: {
+                                    let is_admin = access_data.admin_members.contains(&member.id);
+                                    let member = member.clone();
+                                    move |_| {
+                                        let (token, member) = (admin_token(), member.clone());
+                                        admin_member_state.set(None);
+                                        is_working.set(true);
+                                        spawn(async move {
+                                            let result = admin_set_member_admin(token, member.id, !is_admin)
+                                                .await
+                                                .map(|()| if is_admin {
+                                                    format!("✓ {} är inte längre admin", member.name)
+                                                } else {
+                                                    format!("✓ {} är nu admin", member.name)
+                                                })
+                                                .map_err(|e| admin_error_message(&e));
+                                            access.restart();
+                                            is_working.set(false);
+                                            admin_member_state.set(Some(result));
+                                        });
+                                    }
+                                },
+                                if access_data.admin_members.contains(&member.id) {
+                                    "Ta bort admin"
+                                } else {
+                                    "Gör till admin"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            match admin_member_state() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "admin-success", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "admin-error", "Fel: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+// ── Tab: Logg ─────────────────────────────────────────────────────────────────
+
+fn audit_action_label(action: AuditAction) -> &'static str {
+    match action {
+        AuditAction::SetCurrent => "Nytt album",
+        AuditAction::UpdateCurrent => "Ändrade nuvarande album",
+        AuditAction::DeleteHistoryEntry => "Tog bort historikpost",
+        AuditAction::ReorderMembers => "Ändrade ordningen",
+        AuditAction::AddMember => "La till medlem",
+        AuditAction::RenameMember => "Bytte namn på medlem",
+        AuditAction::DeleteMember => "Tog bort medlem",
+        AuditAction::RestoreMember => "Återställde medlem",
+        AuditAction::CreateMemberInvite => "Skapade inbjudningslänk",
+        AuditAction::SetMemberAdmin => "Ändrade adminrättighet",
+        AuditAction::CreateCredential => "Skapade admin-nyckel",
+        AuditAction::RevokeCredential => "Återkallade admin-nyckel",
+        AuditAction::ConnectSpotify => "Kopplade Spotify-konto",
+        AuditAction::DisconnectSpotify => "Kopplade bort Spotify-konto",
+        AuditAction::ExportPlaylist => "Exporterade spellistan",
+        AuditAction::ReviewAlbum => "Recenserade album",
+        AuditAction::ReviewTrack => "Recenserade låt",
+        AuditAction::ChangePassword => "Bytte lösenord",
+        AuditAction::RedeemInvite => "Valde lösenord via inbjudan",
+        AuditAction::RevealReviews => "Visade betygen",
+        AuditAction::SetReviewsLocked => "Låste eller öppnade recensionerna",
+        AuditAction::AddUnavailability => "Lade till frånvaro",
+        AuditAction::RemoveUnavailability => "Tog bort frånvaro",
+    }
+}
+
+fn actor_kind_label(kind: ActorKind) -> &'static str {
+    match kind {
+        ActorKind::Admin => "Admin",
+        ActorKind::Member => "Medlem",
+    }
+}
+
+#[component]
+pub fn AdminAuditLog() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+
+    let mut action_filter = use_signal(|| None::<AuditAction>);
+    let mut actor_filter = use_signal(|| None::<AuditActorId>);
+    // Pages loaded with "Visa fler", after the first one.
+    let mut older_entries = use_signal(Vec::<AuditEntry>::new);
+    let mut older_has_more = use_signal(|| None::<bool>);
+    let mut more_error = use_signal(|| None::<String>);
+    let mut is_loading_more = use_signal(|| false);
+
+    let first_page = use_resource(move || async move {
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        let filter = AuditLogFilter {
+            action: action_filter(),
+            actor: actor_filter(),
+            before_id: None,
+        };
+        Some(
+            admin_audit_log(token, filter)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    let mut reset_older = move || {
+        older_entries.set(Vec::new());
+        older_has_more.set(None);
+        more_error.set(None);
+    };
+
+    let Some(Some(loaded)) = first_page() else {
+        return rsx! {
+            div { class: "card admin-section",
+                h2 { "Logg" }
+                p { class: "admin-hint", "Logga in för att se loggen." }
+            }
+        };
+    };
+    let page = match loaded {
+        Ok(page) => page,
+        Err(e) => {
+            return rsx! {
+                div { class: "card admin-section",
+                    h2 { "Logg" }
+                    p { class: "admin-error", "Fel: {e}" }
+                }
+            };
+        }
+    };
+
+    let entries: Vec<AuditEntry> = page.entries.into_iter().chain(older_entries()).collect();
+    let has_more = older_has_more().unwrap_or(page.has_more);
+    let oldest_id = entries.last().map(|entry| entry.id);
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Logg" }
+            p { class: "admin-hint", "Alla ändringar, nyast först." }
+
+            div { class: "admin-field-group",
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "audit-action", "Händelse" }
+                    select {
+                        id: "audit-action",
+                        onchange: move |e| {
+                            reset_older();
+                            action_filter.set(AuditAction::parse(&e.value()));
+                        },
+                        option { value: "", selected: action_filter().is_none(), "Alla" }
+                        for action in AuditAction::ALL {
+                            option {
+                                value: action.as_str(),
+                                selected: action_filter() == Some(action),
+                                "{audit_action_label(action)}"
+                            }
+                        }
+                    }
+                }
+                div { class: "admin-field",
+                    label { class: "admin-label", r#for: "audit-actor", "Vem" }
+                    select {
+                        id: "audit-actor",
+                        onchange: move |e| {
+                            reset_older();
+                            actor_filter.set(AuditActorId::parse_key(&e.value()));
+                        },
+                        option { value: "", selected: actor_filter().is_none(), "Alla" }
+                        for actor in page.actors {
+                            option {
+                                value: actor.id.as_key(),
+                                selected: actor_filter() == Some(actor.id),
+                                "{actor.name}"
+                            }
+                        }
+                    }
+                }
+            }
+
+            if entries.is_empty() {
+                p { class: "admin-hint", "Inga händelser." }
+            }
+
+            div { class: "admin-history-list",
+                for entry in entries {
+                    div { key: "{entry.id}", class: "admin-history-row audit-row",
+                        div { class: "admin-history-info",
+                            span { class: "admin-history-album",
+                                "{audit_action_label(entry.action)}"
+                            }
+                            span { class: "admin-history-meta",
+                                "{entry.at} · {actor_kind_label(entry.actor_kind)} {entry.actor}"
+                            }
+                            if let Some(before) = &entry.before {
+                                code { class: "audit-change", "Före: {before}" }
+                            }
+                            if let Some(after) = &entry.after {
+                                code { class: "audit-change", "Efter: {after}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(err) = more_error() {
+                p { class: "admin-error", "Fel: {err}" }
+            }
+
+            if has_more {
+                button {
+                    class: "admin-button-ghost",
+                    disabled: is_loading_more(),
+                    onclick: move |_| {
+                        let filter = AuditLogFilter {
+                            action: action_filter(),
+                            actor: actor_filter(),
+                            before_id: oldest_id,
+                        };
+                        more_error.set(None);
+                        is_loading_more.set(true);
+                        spawn(async move {
+                            match admin_audit_log(admin_token(), filter).await {
+                                Ok(page) => {
+                                    older_entries.write().extend(page.entries);
+                                    older_has_more.set(Some(page.has_more));
+                                }
+                                Err(e) => more_error.set(Some(admin_error_message(&e))),
+                            }
+                            is_loading_more.set(false);
+                        });
+                    },
+                    if is_loading_more() {
+                        span { class: "spinner" }
+                        "Laddar\u{2026}"
+                    } else {
+                        "Visa fler"
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ── Tab: Spotify ──────────────────────────────────────────────────────────────
+
+#[component]
+pub fn AdminSpotify() -> Element {
+    let ctx = use_context::<AdminCtx>();
+    let admin_token = ctx.admin_token;
+    let mut action_state = use_signal(|| None::<Result<String, String>>);
+    let mut is_working = use_signal(|| false);
+
+    let mut account = use_resource(move || async move {
+        let token = admin_token();
+        if token.trim().is_empty() {
+            return None;
+        }
+        Some(
+            admin_spotify_account(token)
+                .await
+                .map_err(|e| admin_error_message(&e)),
+        )
+    });
+
+    let connect = move |_| {
+        let token = admin_token();
+        action_state.set(None);
+        is_working.set(true);
+        spawn(async move {
+            match admin_spotify_authorize(token).await {
+                Ok(url) => {
+                    let _ = eval(&format!("window.location.href = {url:?};"));
+                }
+                Err(e) => {
+                    action_state.set(Some(Err(admin_error_message(&e))));
+                    is_working.set(false);
+                }
+            }
+        });
+    };
+
+    let export = move |_| {
+        let token = admin_token();
+        action_state.set(None);
+        is_working.set(true);
+        spawn(async move {
+            let result = admin_spotify_export_playlist(token)
+                .await
+                .map(|export| {
+                    account.restart();
+                    let mut message = format!("✓ Spellistan har {} låtar", export.track_count);
+                    if export.skipped_albums > 0 {
+                        message.push_str(&format!(
+                            " ({} album utan låtbetyg hoppades över)",
+                            export.skipped_albums
+                        ));
+                    }
+                    message
+                })
+                .map_err(|e| admin_error_message(&e));
+            is_working.set(false);
+            action_state.set(Some(result));
+        });
+    };
+
+    let disconnect = move |_| {
+        let token = admin_token();
+        action_state.set(None);
+        is_working.set(true);
+        spawn(async move {
+            let result = admin_spotify_disconnect(token)
+                .await
+                .map(|()| "✓ Spotify-kontot är bortkopplat".to_string())
+                .map_err(|e| admin_error_message(&e));
+            account.restart();
+            is_working.set(false);
+            action_state.set(Some(result));
+        });
+    };
+
+    rsx! {
+        div { class: "card admin-section",
+            h2 { "Spotify" }
+            p { class: "admin-hint",
+                "Anslut ett Spotify-konto för att exportera klubbens spellista: \
+                 den högst betygsatta låten från varje album vi har lyssnat på."
+            }
+
+            match account() {
+                None | Some(None) => rsx! {
+                    p { class: "admin-hint", "Logga in för att se Spotify-kopplingen." }
+                },
+                Some(Some(Err(e))) => rsx! {
+                    p { class: "admin-error", "Fel: {e}" }
+                },
+                Some(Some(Ok(None))) => rsx! {
+                    p { "Inget Spotify-konto är anslutet." }
+                    button {
+                        class: "admin-button admin-button-submit",
+                        disabled: is_working(),
+                        onclick: connect,
+                        "Anslut Spotify"
+                    }
+                },
+                Some(Some(Ok(Some(acc)))) => rsx! {
+                    p {
+                        "Ansluten som "
+                        strong { {acc.display_name.clone().unwrap_or_else(|| acc.user_id.clone())} }
+                    }
+                    if let Some(url) = acc.playlist_url.clone() {
+                        p {
+                            a {
+                                href: "{url}",
+                                target: "_blank",
+                                rel: "noopener noreferrer",
+                                "Öppna spellistan"
+                            }
+                        }
+                    }
+                    div { class: "admin-spotify-actions",
+                        button {
+                            class: "admin-button admin-button-submit",
+                            disabled: is_working(),
+                            onclick: export,
+                            if is_working() {
+                                span { class: "spinner" }
+                                "Exporterar\u{2026}"
+                            } else {
+                                "Exportera spellista"
+                            }
+                        }
+                        button {
+                            class: "admin-button-ghost",
+                            disabled: is_working(),
+                            onclick: disconnect,
+                            "Koppla från"
+                        }
+                    }
+                },
+            }
+
+            match action_state() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "admin-success", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "admin-error", "Fel: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+/// Landing page for Spotify's redirect after the admin approves (or denies) access.
+/// Completes the connection on the server and links back to the Spotify tab.
+#[component]
+pub fn AdminSpotifyCallback(code: String, state: String, error: String) -> Element {
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    use_future(move || {
+        let (code, state, error) = (code.clone(), state.clone(), error.clone());
+        async move {
+            if !error.is_empty() || code.is_empty() {
+                result.set(Some(Err(format!("Spotify nekade anslutningen ({error})"))));
+                return;
+            }
+            let outcome = spotify_complete_authorization(code, state)
+                .await
+                .map(|acc| acc.display_name.unwrap_or(acc.user_id))
+                .map_err(|e| match e {
+                    ApiError::Unauthorized(_) => {
+                        "Anslutningen har gått ut, försök igen från admin-sidan".to_string()
+                    }
+                    other => error_message(&other),
+                });
+            result.set(Some(outcome));
+        }
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ADMIN_SCSS }
+
+        div { class: "admin-page-wrapper",
+            header {
+                h1 { "Spotify" }
+            }
+
+            div { class: "card admin-section",
+                match result() {
+                    None => rsx! {
+                        p { class: "admin-hint",
+                            span { class: "spinner" }
+                            "Ansluter\u{2026}"
+                        }
+                    },
+                    Some(Ok(name)) => rsx! {
+                        p { class: "admin-success", "✓ Ansluten som {name}" }
+                    },
+                    Some(Err(err)) => rsx! {
+                        p { class: "admin-error", "Fel: {err}" }
+                    },
+                }
+                a { href: "/admin/spotify", "Tillbaka till admin" }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use api::api_models::AlbumMetadata;
+use dioxus::prelude::*;
+
+const ALBUM_FACTS_SCSS: Asset = asset!("/assets/styling/album_facts.scss");
+
+/// A compact "year · runtime · label" line for an album. Renders nothing when no
+/// metadata is known.
+#[component]
+pub fn AlbumFacts(metadata: Option<AlbumMetadata>, #[props(default)] class: String) -> Element {
+    let Some(metadata) = metadata else {
+        return rsx! {};
+    };
+
+    let facts: Vec<String> = [
+        metadata.release_year().map(str::to_string),
+        metadata.duration_ms.map(format_runtime),
+        metadata.label.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if facts.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ALBUM_FACTS_SCSS }
+        p { class: "album-facts {class}", {facts.join(" · ")} }
+    }
+}
+
+/// Format a runtime as "42 min" or "1 h 12 min".
+fn format_runtime(duration_ms: i64) -> String {
+    let minutes = (duration_ms + 30_000) / 60_000;
+    if minutes < 60 {
+        format!("{minutes} min")
+    } else {
+        format!("{} h {} min", minutes / 60, minutes % 60)
+    }
+}
@@ -1 +1,2 @@
+pub mod album_facts;
 pub mod stars;
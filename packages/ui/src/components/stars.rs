@@ -1,8 +1,9 @@
+use api::api_models::Reviews;
 use dioxus::prelude::*;
 
 const STARS_SCSS: Asset = asset!("/assets/styling/stars.scss");
 
-/// The three possible states for an album's review score.
+/// The possible states for an album's review score.
 #[derive(Clone, PartialEq)]
 pub enum ReviewScore {
     /// Reviews have not been fetched yet.
@@ -11,6 +12,8 @@ pub enum ReviewScore {
     NoReviews,
     /// Fetch complete; average score and number of votes.
     Rated { avg: f32, count: usize },
+    /// Fetch complete; the scores aren't revealed yet, but this many have voted.
+    Hidden { count: usize },
 }
 
 impl ReviewScore {
@@ -26,6 +29,17 @@ impl ReviewScore {
             }
         }
     }
+
+    /// The album score of a meeting's reviews, unless they're still hidden.
+    pub fn from_reviews(reviews: &Reviews) -> Self {
+        if !reviews.revealed {
+            return Self::Hidden {
+                count: reviews.reviewer_count as usize,
+            };
+        }
+        let scores: Vec<u8> = reviews.album_reviews.iter().map(|r| r.score).collect();
+        Self::from_scores(&scores)
+    }
 }
 
 #[component]
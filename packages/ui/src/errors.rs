@@ -0,0 +1,25 @@
+use api::ApiError;
+
+/// A user-facing description of a failed server call, chosen by the kind of error.
+pub fn error_message(err: &ApiError) -> String {
+    match err {
+        ApiError::Unauthorized(_) => "Behörighet saknas".to_string(),
+        ApiError::Validation(message) => format!("Ogiltig förfrågan: {message}"),
+        ApiError::NotFound(message) => format!("Hittades inte: {message}"),
+        ApiError::UpstreamUnavailable(_) => {
+            "Spotify svarar inte just nu, försök igen om en stund".to_string()
+        }
+        ApiError::RateLimited {
+            retry_after_secs: Some(secs),
+        } => format!("För många anrop till Spotify, försök igen om {secs} s"),
+        ApiError::RateLimited {
+            retry_after_secs: None,
+        } => "För många anrop till Spotify, försök igen om en stund".to_string(),
+        ApiError::LockedOut { retry_after_secs } => format!(
+            "För många misslyckade inloggningar, försök igen om {} min",
+            retry_after_secs.div_ceil(60)
+        ),
+        ApiError::ReviewsClosed(_) => "Recensionerna för det här albumet är stängda".to_string(),
+        ApiError::Internal(message) => format!("Något gick fel: {message}"),
+    }
+}
@@ -1,6 +1,8 @@
+use crate::components::album_facts::AlbumFacts;
 use crate::components::stars::{AverageStars, ReviewScore};
+use crate::errors::error_message;
 use crate::SiteFooter;
-use api::api_models::HistoryEntry;
+use api::api_models::{album_art_path, ArtSize, HistoryEntry};
 use api::{get_history, get_reviews};
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::fa_brands_icons::FaSpotify;
@@ -15,7 +17,7 @@ pub fn History() -> Element {
     let mut entries = use_signal(|| None::<Result<Vec<HistoryEntry>, String>>);
 
     use_future(move || async move {
-        let result = get_history().await.map_err(|e| e.to_string());
+        let result = get_history().await.map_err(|e| error_message(&e));
         entries.set(Some(result));
     });
 
@@ -110,11 +112,8 @@ fn HistoryCard(entry: HistoryEntry) -> Element {
     use_future(move || {
         let meeting_id = meeting_id.clone();
         async move {
-            match get_reviews(meeting_id).await {
-                Ok(reviews) => {
-                    let scores: Vec<u8> = reviews.album_reviews.iter().map(|r| r.score).collect();
-                    score.set(ReviewScore::from_scores(&scores));
-                }
+            match get_reviews(meeting_id, None).await {
+                Ok(reviews) => score.set(ReviewScore::from_reviews(&reviews)),
                 Err(_) => score.set(ReviewScore::NoReviews),
             }
         }
@@ -125,7 +124,7 @@ fn HistoryCard(entry: HistoryEntry) -> Element {
             div { class: "history-card-art-wrapper",
                 img {
                     class: "history-card-art",
-                    src: "{entry.album_art}",
+                    src: album_art_path(&entry.album_id, ArtSize::Medium),
                     alt: "{entry.album_name} album cover",
                 }
             }
@@ -135,6 +134,7 @@ fn HistoryCard(entry: HistoryEntry) -> Element {
                     div { class: "history-card-titles",
                         p { class: "history-card-album", "{entry.album_name}" }
                         p { class: "history-card-artist", "{entry.album_artist}" }
+                        AlbumFacts { metadata: entry.album_metadata.clone() }
                     }
                 }
 
@@ -182,17 +182,26 @@ fn HistoryCard(entry: HistoryEntry) -> Element {
                             span { class: "history-card-score-count", "Inga betyg" }
                         }
                     },
+                    ReviewScore::Hidden { count } => rsx! {
+                        div { class: "history-card-score",
+                            AverageStars { avg: 0.0, placeholder: true }
+                            span { class: "history-card-score-count", "Dolda ({count})" }
+                        }
+                    },
                     ReviewScore::Loading => rsx! {},
                 }
 
-                a {
-                    href: "{entry.spotify_url}",
-                    target: "_blank",
-                    rel: "noopener noreferrer",
-                    class: "history-spotify-link",
-                    Icon { icon: FaSpotify }
-                    "Spotify"
-                    Icon { icon: FiExternalLink }
+                div { class: "history-card-links",
+                    a { href: "/history/{entry.id}", class: "history-review-link", "Betyg och recensioner" }
+                    a {
+                        href: "{entry.spotify_url}",
+                        target: "_blank",
+                        rel: "noopener noreferrer",
+                        class: "history-spotify-link",
+                        Icon { icon: FaSpotify }
+                        "Spotify"
+                        Icon { icon: FiExternalLink }
+                    }
                 }
             }
         }
@@ -0,0 +1,152 @@
+use crate::errors::error_message;
+use crate::session_storage::{store_session_token, REVIEW_SESSION_KEY};
+use crate::SiteFooter;
+use api::api_models::{Member, MIN_PASSWORD_LEN};
+use api::{member_invite, redeem_member_invite, ApiError};
+use dioxus::prelude::*;
+
+const REVIEW_SCSS: Asset = asset!("/assets/styling/review.scss");
+
+/// Invite links are only ever "not found" when they can't be used any more.
+fn invite_error_message(err: &ApiError) -> String {
+    match err {
+        ApiError::NotFound(_) => {
+            "Länken är ogiltig, redan använd eller har gått ut. Be en admin om en ny.".to_string()
+        }
+        other => error_message(other),
+    }
+}
+
+/// Lets a member choose their own password with a link from an admin, then logs them in.
+#[component]
+pub fn Invite(token: ReadSignal<String>) -> Element {
+    let invited = use_resource(move || async move {
+        member_invite(token())
+            .await
+            .map_err(|e| invite_error_message(&e))
+    });
+
+    let mut password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut save_error = use_signal(|| None::<String>);
+    let mut is_saving = use_signal(|| false);
+    let mut logged_in_as = use_signal(|| None::<Member>);
+
+    let mut save = move || {
+        if password().chars().count() < MIN_PASSWORD_LEN {
+            save_error.set(Some(format!(
+                "Lösenordet måste vara minst {MIN_PASSWORD_LEN} tecken"
+            )));
+            return;
+        }
+        if password() != confirm_password() {
+            save_error.set(Some("Lösenorden matchar inte".to_string()));
+            return;
+        }
+        save_error.set(None);
+        is_saving.set(true);
+        spawn(async move {
+            match redeem_member_invite(token(), password()).await {
+                Ok(session) => {
+                    store_session_token(REVIEW_SESSION_KEY, &session.token);
+                    logged_in_as.set(Some(session.member));
+                }
+                Err(e) => save_error.set(Some(invite_error_message(&e))),
+            }
+            is_saving.set(false);
+        });
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: REVIEW_SCSS }
+
+        div { class: "page-wrapper",
+            header {
+                h1 { "Välj lösenord" }
+            }
+
+            match invited() {
+                None => rsx! {
+                    div { class: "review-loading", "Laddar…" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "review-empty card",
+                        p { "{err}" }
+                    }
+                },
+                Some(Ok(_)) if logged_in_as().is_some() => rsx! {
+                    div { class: "card review-login-card",
+                        p { class: "review-success",
+                            "Klart! Du är inloggad som "
+                            strong { {logged_in_as().map(|m| m.name.to_string())} }
+                            "."
+                        }
+                        a { class: "review-button", href: "/review", "Gå till recensionerna" }
+                    }
+                },
+                Some(Ok(member)) => rsx! {
+                    div { class: "card review-login-card",
+                        h2 { "Hej {member.name}!" }
+                        p { class: "review-login-hint",
+                            "Välj ett lösenord att logga in med när du recenserar. \
+                             Länken kan bara användas en gång."
+                        }
+
+                        div { class: "review-login-fields",
+                            div { class: "review-field",
+                                label { class: "review-label", r#for: "invite-pw", "Lösenord" }
+                                input {
+                                    id: "invite-pw",
+                                    r#type: "password",
+                                    autocomplete: "new-password",
+                                    placeholder: "Minst {MIN_PASSWORD_LEN} tecken",
+                                    value: "{password}",
+                                    oninput: move |e| {
+                                        password.set(e.value());
+                                        save_error.set(None);
+                                    },
+                                }
+                            }
+                            div { class: "review-field",
+                                label { class: "review-label", r#for: "invite-pw-confirm", "Upprepa lösenord" }
+                                input {
+                                    id: "invite-pw-confirm",
+                                    r#type: "password",
+                                    autocomplete: "new-password",
+                                    value: "{confirm_password}",
+                                    oninput: move |e| {
+                                        confirm_password.set(e.value());
+                                        save_error.set(None);
+                                    },
+                                    onkeydown: move |e| {
+                                        if e.key() == Key::Enter {
+                                            save();
+                                        }
+                                    },
+                                }
+                            }
+                        }
+
+                        if let Some(err) = save_error() {
+                            p { class: "review-error", "{err}" }
+                        }
+
+                        button {
+                            class: "review-button",
+                            disabled: password().is_empty() || is_saving(),
+                            onclick: move |_| save(),
+                            if is_saving() {
+                                span { class: "spinner" }
+                                "Sparar\u{2026}"
+                            } else {
+                                "Spara lösenord"
+                            }
+                        }
+                    }
+                },
+            }
+
+            SiteFooter {}
+        }
+    }
+}
@@ -2,13 +2,22 @@ use dioxus::prelude::*;
 
 pub mod components;
 mod admin_view;
+mod errors;
 mod history_view;
+mod invite_view;
 mod main_view;
 mod review_view;
-pub use admin_view::{AdminAlbum, AdminCtx, AdminHistory, AdminPasswords, AdminRotation, AdminShell};
+mod session_storage;
+mod stats_view;
+pub use admin_view::{
+    AdminAlbum, AdminAuditLog, AdminCredentials, AdminCtx, AdminHistory, AdminPasswords,
+    AdminRotation, AdminShell, AdminSpotify, AdminSpotifyCallback,
+};
 pub use history_view::History;
+pub use invite_view::Invite;
 pub use main_view::Main;
-pub use review_view::Review;
+pub use review_view::{MeetingReview, Review};
+pub use stats_view::Stats;
 
 const GLOBAL_SCSS: Asset = asset!("/assets/styling/globals.scss");
 
@@ -28,6 +37,7 @@ pub fn SiteFooter() -> Element {
             a { href: "/", "Startsida" }
             a { href: "/review", "Recensera" }
             a { href: "/history", "Historik" }
+            a { href: "/stats", "Statistik" }
             a { href: "/admin", "Admin" }
         }
     }
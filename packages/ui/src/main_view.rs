@@ -1,5 +1,6 @@
-use api::api_models::{Album, Data, Meeting, Name};
+use api::api_models::{Album, Data, Meeting, Member, MemberId};
 
+use crate::components::album_facts::AlbumFacts;
 use crate::components::stars::{AverageStars, ReviewScore};
 use crate::SiteFooter;
 use api::{get_current, get_reviews};
@@ -24,11 +25,8 @@ pub fn Main() -> Element {
         if let Ok(ref d) = current_data {
             if let Some(ref meeting_id) = d.current_meeting_id {
                 let meeting_id = meeting_id.clone();
-                match get_reviews(meeting_id).await {
-                    Ok(r) => {
-                        let scores: Vec<u8> = r.album_reviews.iter().map(|rv| rv.score).collect();
-                        score.set(ReviewScore::from_scores(&scores));
-                    }
+                match get_reviews(meeting_id, None).await {
+                    Ok(r) => score.set(ReviewScore::from_reviews(&r)),
                     Err(e) => {
                         eprintln!("Error fetching reviews: {e}");
                         score.set(ReviewScore::NoReviews);
@@ -96,14 +94,16 @@ fn RenderData(data: ReadSignal<Data>, score: ReviewScore) -> Element {
         div { class: "row",
             UpcomingRotation {
                 current_person: data().current_person,
-                members: data().members,
+                next_person: data().next_person,
+                rotation: data().rotation,
+                unavailable: data().unavailable,
             }
         }
     }
 }
 
 #[component]
-fn CurrentAlbumView(album: Album, picked_by: Option<Name>, score: ReviewScore) -> Element {
+fn CurrentAlbumView(album: Album, picked_by: Option<Member>, score: ReviewScore) -> Element {
     rsx! {
         div { class: "current-album-container gap-6",
             //  Album art
@@ -119,10 +119,11 @@ fn CurrentAlbumView(album: Album, picked_by: Option<Name>, score: ReviewScore) -
             div { class: "album-info-container",
                 h3 { class: "album-name", "{album.name}" }
                 p { class: "album-artist", "{album.artist}" }
+                AlbumFacts { metadata: album.metadata.clone(), class: "album-facts-main" }
                 if let Some(picker) = picked_by {
                     p { class: "album-picked-by",
                         "Vald av "
-                        span { "{picker}" }
+                        span { "{picker.name}" }
                     }
                 }
 
@@ -152,6 +153,14 @@ fn CurrentAlbumView(album: Album, picked_by: Option<Name>, score: ReviewScore) -
                             span { class: "main-album-score-count", "Inga betyg ännu" }
                         }
                     },
+                    ReviewScore::Hidden { count } => rsx! {
+                        div { class: "main-album-score",
+                            AverageStars { avg: 0.0, placeholder: true }
+                            span { class: "main-album-score-count",
+                                {format!("Betygen är dolda ännu ({} röster)", count)}
+                            }
+                        }
+                    },
                     ReviewScore::Loading => rsx! {},
                 }
 
@@ -214,32 +223,25 @@ fn NextMeeting(next_meeting: Option<Meeting>) -> Element {
 }
 
 #[component]
-fn UpcomingRotation(current_person: Option<Name>, members: Vec<Name>) -> Element {
-    let num_members = members.len() as i64;
-
-    let ordered_members = {
-        let curr_index = current_person
-            .as_ref()
-            .and_then(|cp| members.iter().position(|name| name == cp))
-            .unwrap_or(0) as i64;
+fn UpcomingRotation(
+    current_person: Option<Member>,
+    next_person: Option<Member>,
+    rotation: Vec<Member>,
+    unavailable: Vec<MemberId>,
+) -> Element {
+    let current_id = current_person.as_ref().map(|cp| cp.id);
+    let next_id = next_person.as_ref().map(|np| np.id);
 
-        let mut ordered = members
-            .iter()
-            .enumerate()
-            .map(|(i, n)| {
-                (
-                    ((i as i64 - curr_index + num_members) % num_members),
-                    n.clone(),
-                )
-            })
-            .collect::<Vec<_>>();
-
-        ordered.sort_by_key(|(order, _)| *order);
-        ordered
-            .into_iter()
-            .map(|(_, name)| name)
-            .collect::<Vec<_>>()
-    };
+    // The current picker comes last in the rotation; show them first, above whoever is next.
+    let ordered_members = current_person
+        .iter()
+        .cloned()
+        .chain(
+            rotation
+                .into_iter()
+                .filter(|member| Some(member.id) != current_id),
+        )
+        .collect::<Vec<_>>();
 
     rsx! {
         div { class: "card full-width",
@@ -251,19 +253,20 @@ fn UpcomingRotation(current_person: Option<Name>, members: Vec<Name>) -> Element
             div { class: "upcoming-grid",
                 for (i , member) in ordered_members.iter().enumerate() {
                     div {
-                        key: "{member}",
+                        key: "{member.id}",
                         class: "upcoming-grid-element",
                         class: if i == 0 { "upcoming-grid-element-current" } else { "upcoming-grid-element-normal" },
                         div { class: "order-text", "{i + 1}" }
                         div { class: if i == 0 { "current-name-text" } else { "inactive-name-text" },
-                            "{member}"
+                            "{member.name}"
                         }
 
-                        if i == 0 {
+                        if Some(member.id) == current_id {
                             div { class: "sub-name-text", "Nuvarande" }
-                        }
-                        if i == 1 {
+                        } else if Some(member.id) == next_id {
                             div { class: "sub-name-text", "Nästa" }
+                        } else if unavailable.contains(&member.id) {
+                            div { class: "sub-name-text", "Borta, hoppas över" }
                         }
                     }
                 }
@@ -1,13 +1,37 @@
+use super::tracks_by_disc;
 use crate::components::stars::AverageStars;
-use api::api_models::{AlbumTrack, Reviews};
+use api::api_models::{AlbumReview, AlbumTrack, RevealPolicy, Reviews, TrackReview};
 use dioxus::prelude::*;
 use dioxus_free_icons::{icons::fa_brands_icons::FaSpotify, Icon};
 
+/// A track with its average score and how many members scored it.
+type TrackAverage = (AlbumTrack, Option<f32>, usize);
+
+fn reveal_hint(policy: RevealPolicy) -> &'static str {
+    match policy {
+        RevealPolicy::AfterOwnReview => "Betygen visas när du har gett albumet ett betyg.",
+        RevealPolicy::AtMeetingTime => "Betygen visas när mötet börjar.",
+        RevealPolicy::AdminReveal => "Betygen visas när en admin visar dem.",
+    }
+}
+
 #[component]
 pub fn AggregateScores(
     reviews: ReadSignal<Reviews>,
     tracks: ReadSignal<Vec<AlbumTrack>>,
 ) -> Element {
+    if !reviews().revealed {
+        return rsx! {
+            div { class: "card review-aggregate-card",
+                h3 { "Gemensamma betyg" }
+                p { class: "review-section-hint", "{reveal_hint(reviews().reveal_policy)}" }
+                span { class: "review-aggregate-count",
+                    {format!("{} har gett betyg hittills", reviews().reviewer_count)}
+                }
+            }
+        };
+    }
+
     if reviews().album_reviews.is_empty() && reviews().track_reviews.is_empty() && tracks.is_empty()
     {
         return rsx! {};
@@ -20,27 +44,54 @@ pub fn AggregateScores(
         Some(sum as f32 / reviews().album_reviews.len() as f32)
     };
 
-    // Build per-track averages; always sorted by track number.
-    let mut track_data: Vec<(AlbumTrack, Option<f32>, usize)> = tracks()
+    // Build per-track averages, grouped by disc and sorted by track number.
+    let track_average = |track: &AlbumTrack| -> (Option<f32>, usize) {
+        let scores: Vec<u8> = reviews()
+            .track_reviews
+            .iter()
+            .filter(|r| r.track_id == track.track_id)
+            .map(|r| r.score)
+            .collect();
+        let count = scores.len();
+        let avg = if scores.is_empty() {
+            None
+        } else {
+            let sum: u32 = scores.iter().map(|&s| s as u32).sum();
+            Some(sum as f32 / count as f32)
+        };
+        (avg, count)
+    };
+    let disc_data: Vec<(u32, Vec<TrackAverage>)> = tracks_by_disc(&tracks())
         .into_iter()
-        .map(|track| {
-            let scores: Vec<u8> = reviews()
-                .track_reviews
-                .iter()
-                .filter(|r| r.track_id == track.track_id)
-                .map(|r| r.score)
+        .map(|(disc, disc_tracks)| {
+            let rows = disc_tracks
+                .into_iter()
+                .map(|track| {
+                    let (avg, count) = track_average(&track);
+                    (track, avg, count)
+                })
                 .collect();
-            let count = scores.len();
-            let avg = if scores.is_empty() {
-                None
-            } else {
-                let sum: u32 = scores.iter().map(|&s| s as u32).sum();
-                Some(sum as f32 / count as f32)
-            };
-            (track, avg, count)
+            (disc, rows)
         })
         .collect();
-    track_data.sort_by_key(|(t, _, _)| t.track_number);
+    let multi_disc = disc_data.len() > 1;
+
+    let mut written_reviews: Vec<AlbumReview> = reviews()
+        .album_reviews
+        .into_iter()
+        .filter(|r| r.text.is_some())
+        .collect();
+    written_reviews.sort_by(|a, b| a.member_name.cmp(&b.member_name));
+
+    let track_comments = |track: &AlbumTrack| -> Vec<TrackReview> {
+        let mut comments: Vec<TrackReview> = reviews()
+            .track_reviews
+            .into_iter()
+            .filter(|r| r.track_id == track.track_id && r.comment.is_some())
+            .collect();
+        comments.sort_by(|a, b| a.member_name.cmp(&b.member_name));
+        comments
+    };
 
     rsx! {
         div { class: "card review-aggregate-card",
@@ -57,36 +108,66 @@ pub fn AggregateScores(
                 }
             }
 
-            if !track_data.is_empty() {
+            if !written_reviews.is_empty() {
+                div { class: "review-aggregate-written",
+                    h4 { "Recensioner" }
+                    for review in written_reviews.iter() {
+                        div { key: "{review.member_id}", class: "review-written",
+                            div { class: "review-written-header",
+                                span { class: "review-comment-author", "{review.member_name}" }
+                                span { class: "review-aggregate-count", "{review.score} / 10" }
+                            }
+                            p { class: "review-written-text",
+                                {review.text.clone().unwrap_or_default()}
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !disc_data.is_empty() {
                 div { class: "review-aggregate-tracks",
                     h4 { "Låtar" }
-                    for (track , avg_opt , count) in track_data.iter() {
-                        div {
-                            key: "{track.track_id}",
-                            class: "review-aggregate-track-row",
-                            span { class: "review-track-num", "{track.track_number}" }
-                            span { class: "review-track-name", "{track.track_name}" }
-                            span { class: "review-track-spotify-slot",
-                                if let Some(ref url) = track.spotify_url {
-                                    a {
-                                        href: "{url}",
-                                        target: "_blank",
-                                        rel: "noopener noreferrer",
-                                        class: "review-track-spotify-link",
-                                        Icon { icon: FaSpotify }
+                    for (disc , track_data) in disc_data.iter() {
+                        if multi_disc {
+                            h5 { key: "disc-{disc}", class: "review-disc-heading", "Skiva {disc}" }
+                        }
+                        for (track , avg_opt , count) in track_data.iter() {
+                            div {
+                                key: "{track.track_id}",
+                                class: "review-aggregate-track-row",
+                                span { class: "review-track-num", "{track.track_number}" }
+                                span { class: "review-track-name", "{track.track_name}" }
+                                span { class: "review-track-spotify-slot",
+                                    if let Some(ref url) = track.spotify_url {
+                                        a {
+                                            href: "{url}",
+                                            target: "_blank",
+                                            rel: "noopener noreferrer",
+                                            class: "review-track-spotify-link",
+                                            Icon { icon: FaSpotify }
+                                        }
+                                    }
+                                }
+                                if let Some(avg) = avg_opt {
+                                    AverageStars { avg: *avg }
+                                    span { class: "review-aggregate-num", {format!("{:.1}", avg)} }
+                                    span { class: "review-aggregate-count", "({count})" }
+                                } else {
+                                    AverageStars { avg: 0.0, placeholder: true }
+                                    span { class: "review-aggregate-num review-aggregate-no-reviews",
+                                        "–"
                                     }
+                                    span {}
                                 }
                             }
-                            if let Some(avg) = avg_opt {
-                                AverageStars { avg: *avg }
-                                span { class: "review-aggregate-num", {format!("{:.1}", avg)} }
-                                span { class: "review-aggregate-count", "({count})" }
-                            } else {
-                                AverageStars { avg: 0.0, placeholder: true }
-                                span { class: "review-aggregate-num review-aggregate-no-reviews",
-                                    "–"
+                            for comment in track_comments(track) {
+                                p {
+                                    key: "{track.track_id}-{comment.member_id}",
+                                    class: "review-aggregate-track-comment",
+                                    span { class: "review-comment-author", "{comment.member_name}: " }
+                                    {comment.comment.clone().unwrap_or_default()}
                                 }
-                                span {}
                             }
                         }
                     }
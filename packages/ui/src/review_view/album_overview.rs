@@ -1,4 +1,4 @@
-use api::api_models::{Album, Name};
+use api::api_models::{album_art_path, Album, ArtSize, Member};
 use dioxus::prelude::*;
 use dioxus_free_icons::{
     icons::{fa_brands_icons::FaSpotify, fi_icons::FiExternalLink},
@@ -6,14 +6,17 @@ use dioxus_free_icons::{
 };
 
 #[component]
-pub fn CurrentAlbumView(album: ReadSignal<Album>, picked_by: ReadSignal<Option<Name>>) -> Element {
+pub fn CurrentAlbumView(
+    album: ReadSignal<Album>,
+    picked_by: ReadSignal<Option<Member>>,
+) -> Element {
     rsx! {
         // ── Album info ──────────────────────────────────────
         div { class: "card review-album-card",
             div { class: "review-album-art-wrap",
                 img {
                     class: "review-album-art",
-                    src: "{album().album_art}",
+                    src: album_art_path(&album().id, ArtSize::Medium),
                     alt: "{album().name} album cover",
                 }
             }
@@ -23,7 +26,7 @@ pub fn CurrentAlbumView(album: ReadSignal<Album>, picked_by: ReadSignal<Option<N
                 if let Some(ref picker) = picked_by() {
                     p { class: "review-album-picker",
                         "Vald av "
-                        span { class: "review-album-picker-name", "{picker}" }
+                        span { class: "review-album-picker-name", "{picker.name}" }
                     }
                 }
                 a {
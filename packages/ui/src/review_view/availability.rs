@@ -0,0 +1,183 @@
+use crate::errors::error_message;
+use api::api_models::{Member, Unavailability, MAX_UNAVAILABILITY_NOTE_LEN};
+use api::{add_unavailability, get_unavailability, remove_unavailability};
+use dioxus::prelude::*;
+
+/// The logged-in member's own dates away, when their turn to pick is skipped.
+#[component]
+pub fn AvailabilityView(session_token: ReadSignal<String>, member: ReadSignal<Member>) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut ranges = use_signal(|| None::<Vec<Unavailability>>);
+    let mut start_date = use_signal(String::new);
+    let mut end_date = use_signal(String::new);
+    let mut note = use_signal(String::new);
+    let mut availability_error = use_signal(|| None::<String>);
+    let mut is_saving = use_signal(|| false);
+
+    use_effect(move || {
+        if !is_open() {
+            return;
+        }
+        spawn(async move {
+            match get_unavailability().await {
+                Ok(list) => ranges.set(Some(list)),
+                Err(e) => availability_error.set(Some(error_message(&e))),
+            }
+        });
+    });
+
+    let mut add = move || {
+        let note_val = note().trim().to_string();
+        availability_error.set(None);
+        is_saving.set(true);
+        spawn(async move {
+            let result = add_unavailability(
+                session_token(),
+                start_date(),
+                end_date(),
+                (!note_val.is_empty()).then_some(note_val),
+            )
+            .await;
+            match result {
+                Ok(list) => {
+                    ranges.set(Some(list));
+                    start_date.set(String::new());
+                    end_date.set(String::new());
+                    note.set(String::new());
+                }
+                Err(e) => availability_error.set(Some(error_message(&e))),
+            }
+            is_saving.set(false);
+        });
+    };
+
+    if !is_open() {
+        return rsx! {
+            div { class: "review-change-password-toggle",
+                button {
+                    class: "review-logout-btn",
+                    onclick: move |_| is_open.set(true),
+                    "Frånvaro"
+                }
+            }
+        };
+    }
+
+    let own_ranges = ranges()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.member_id == member().id)
+        .collect::<Vec<_>>();
+
+    rsx! {
+        div { class: "card review-login-card",
+            h2 { "Frånvaro" }
+            p { class: "review-login-hint",
+                "Är du borta när det är din tur att välja album hoppas du över, och behåller din plats till nästa varv."
+            }
+
+            if ranges().is_none() {
+                p { class: "review-login-hint", "Laddar…" }
+            } else if own_ranges.is_empty() {
+                p { class: "review-login-hint", "Du har ingen frånvaro inlagd." }
+            } else {
+                ul { class: "review-availability-list",
+                    for range in own_ranges {
+                        li { key: "{range.id}", class: "review-availability-row",
+                            span { "{range.start_date} – {range.end_date}" }
+                            if let Some(note) = &range.note {
+                                span { class: "review-login-hint", "{note}" }
+                            }
+                            button {
+                                class: "review-logout-btn",
+                                disabled: is_saving(),
+                                onclick: move |_| {
+                                    availability_error.set(None);
+                                    is_saving.set(true);
+                                    spawn(async move {
+                                        match remove_unavailability(session_token(), range.id).await {
+                                            Ok(list) => ranges.set(Some(list)),
+                                            Err(e) => availability_error.set(Some(error_message(&e))),
+                                        }
+                                        is_saving.set(false);
+                                    });
+                                },
+                                "Ta bort"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "review-login-fields",
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "away-start", "Från och med" }
+                    input {
+                        id: "away-start",
+                        r#type: "date",
+                        value: "{start_date}",
+                        oninput: move |e| {
+                            let val = e.value();
+                            if end_date().is_empty() || end_date() < val {
+                                end_date.set(val.clone());
+                            }
+                            start_date.set(val);
+                            availability_error.set(None);
+                        },
+                    }
+                }
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "away-end", "Till och med" }
+                    input {
+                        id: "away-end",
+                        r#type: "date",
+                        min: "{start_date}",
+                        value: "{end_date}",
+                        oninput: move |e| {
+                            end_date.set(e.value());
+                            availability_error.set(None);
+                        },
+                    }
+                }
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "away-note", "Anteckning" }
+                    input {
+                        id: "away-note",
+                        r#type: "text",
+                        maxlength: MAX_UNAVAILABILITY_NOTE_LEN as i64,
+                        placeholder: "t.ex. Resa",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value()),
+                    }
+                }
+            }
+
+            if let Some(err) = availability_error() {
+                p { class: "review-error", "{err}" }
+            }
+
+            div { class: "review-change-password-actions",
+                button {
+                    class: "review-button",
+                    disabled: start_date().is_empty() || end_date().is_empty() || is_saving(),
+                    onclick: move |_| add(),
+                    if is_saving() {
+                        span { class: "spinner" }
+                        "Sparar\u{2026}"
+                    } else {
+                        "Lägg till"
+                    }
+                }
+                button {
+                    class: "review-logout-btn",
+                    disabled: is_saving(),
+                    onclick: move |_| {
+                        availability_error.set(None);
+                        is_open.set(false);
+                    },
+                    "Stäng"
+                }
+            }
+        }
+    }
+}
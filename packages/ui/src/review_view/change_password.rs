@@ -0,0 +1,163 @@
+use crate::errors::error_message;
+use crate::session_storage::{store_session_token, REVIEW_SESSION_KEY};
+use api::api_models::{MemberSession, MIN_PASSWORD_LEN};
+use api::{change_member_password, ApiError};
+use dioxus::prelude::*;
+
+#[component]
+pub fn ChangePasswordView(
+    session_token: ReadSignal<String>,
+    on_changed: Callback<MemberSession>,
+) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut current_password = use_signal(String::new);
+    let mut new_password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut change_error = use_signal(|| None::<String>);
+    let mut is_changing = use_signal(|| false);
+    let mut is_changed = use_signal(|| false);
+
+    let mut reset = move || {
+        current_password.set(String::new());
+        new_password.set(String::new());
+        confirm_password.set(String::new());
+        change_error.set(None);
+    };
+
+    let mut change = move || {
+        if new_password().chars().count() < MIN_PASSWORD_LEN {
+            change_error.set(Some(format!(
+                "Lösenordet måste vara minst {MIN_PASSWORD_LEN} tecken"
+            )));
+            return;
+        }
+        if new_password() != confirm_password() {
+            change_error.set(Some("Lösenorden matchar inte".to_string()));
+            return;
+        }
+        change_error.set(None);
+        is_changing.set(true);
+        spawn(async move {
+            match change_member_password(session_token(), current_password(), new_password()).await
+            {
+                Ok(session) => {
+                    // Changing the password logs out every session, this one included,
+                    // so carry on with the new one.
+                    store_session_token(REVIEW_SESSION_KEY, &session.token);
+                    on_changed(session);
+                    reset();
+                    is_open.set(false);
+                    is_changed.set(true);
+                }
+                Err(ApiError::Unauthorized(_)) => {
+                    change_error.set(Some("Fel nuvarande lösenord".to_string()));
+                }
+                Err(e) => change_error.set(Some(error_message(&e))),
+            }
+            is_changing.set(false);
+        });
+    };
+
+    if !is_open() {
+        return rsx! {
+            div { class: "review-change-password-toggle",
+                if is_changed() {
+                    p { class: "review-success", "✓ Lösenordet är bytt" }
+                }
+                button {
+                    class: "review-logout-btn",
+                    onclick: move |_| {
+                        is_changed.set(false);
+                        is_open.set(true);
+                    },
+                    "Byt lösenord"
+                }
+            }
+        };
+    }
+
+    rsx! {
+        div { class: "card review-login-card",
+            h2 { "Byt lösenord" }
+            p { class: "review-login-hint",
+                "Du loggas ut på alla andra enheter när lösenordet byts."
+            }
+
+            div { class: "review-login-fields",
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "change-pw-current", "Nuvarande lösenord" }
+                    input {
+                        id: "change-pw-current",
+                        r#type: "password",
+                        autocomplete: "current-password",
+                        value: "{current_password}",
+                        oninput: move |e| {
+                            current_password.set(e.value());
+                            change_error.set(None);
+                        },
+                    }
+                }
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "change-pw-new", "Nytt lösenord" }
+                    input {
+                        id: "change-pw-new",
+                        r#type: "password",
+                        autocomplete: "new-password",
+                        placeholder: "Minst {MIN_PASSWORD_LEN} tecken",
+                        value: "{new_password}",
+                        oninput: move |e| {
+                            new_password.set(e.value());
+                            change_error.set(None);
+                        },
+                    }
+                }
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "change-pw-confirm", "Upprepa nytt lösenord" }
+                    input {
+                        id: "change-pw-confirm",
+                        r#type: "password",
+                        autocomplete: "new-password",
+                        value: "{confirm_password}",
+                        oninput: move |e| {
+                            confirm_password.set(e.value());
+                            change_error.set(None);
+                        },
+                        onkeydown: move |e| {
+                            if e.key() == Key::Enter {
+                                change();
+                            }
+                        },
+                    }
+                }
+            }
+
+            if let Some(err) = change_error() {
+                p { class: "review-error", "{err}" }
+            }
+
+            div { class: "review-change-password-actions",
+                button {
+                    class: "review-button",
+                    disabled: current_password().is_empty() || new_password().is_empty()
+                        || is_changing(),
+                    onclick: move |_| change(),
+                    if is_changing() {
+                        span { class: "spinner" }
+                        "Byter\u{2026}"
+                    } else {
+                        "Byt lösenord"
+                    }
+                }
+                button {
+                    class: "review-logout-btn",
+                    disabled: is_changing(),
+                    onclick: move |_| {
+                        reset();
+                        is_open.set(false);
+                    },
+                    "Avbryt"
+                }
+            }
+        }
+    }
+}
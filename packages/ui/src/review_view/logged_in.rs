@@ -1,4 +1,5 @@
-use api::api_models::{AlbumTrack, Reviews};
+use super::tracks_by_disc;
+use api::api_models::{AlbumTrack, Member, Reviews, MAX_REVIEW_TEXT_LEN, MAX_TRACK_COMMENT_LEN};
 use dioxus::{core::EventHandler, prelude::*};
 use dioxus_free_icons::{icons::fa_brands_icons::FaSpotify, Icon};
 use std::collections::HashMap;
@@ -12,13 +13,21 @@ fn change_class(staged: u8, server: u8) -> &'static str {
     }
 }
 
+/// Like [`change_class`], but also marks an edited text when the score is unchanged.
+fn change_class_with_text(staged: u8, server: u8, text_changed: bool) -> &'static str {
+    match change_class(staged, server) {
+        "" if text_changed => "changed-text",
+        class => class,
+    }
+}
+
 #[component]
 pub fn ReviewLoggedInView(
-    logged_in_as: ReadSignal<String>,
+    logged_in_as: ReadSignal<Member>,
     reviews: ReadSignal<Reviews>,
     tracks: ReadSignal<Vec<AlbumTrack>>,
-    review_album: Callback<u8, ()>,
-    review_track: Callback<(String, u8), ()>,
+    review_album: Callback<(u8, Option<String>), ()>,
+    review_track: Callback<(String, u8, Option<String>), ()>,
     logout: Callback<(), ()>,
     album_review_error: ReadSignal<Option<String>>,
     track_review_error: ReadSignal<Option<String>>,
@@ -28,11 +37,20 @@ pub fn ReviewLoggedInView(
         reviews()
             .album_reviews
             .iter()
-            .find(|r| r.member_name == logged_in_as())
+            .find(|r| r.member_id == logged_in_as().id)
             .map(|r| r.score)
             .unwrap_or(0)
     });
 
+    let album_text = use_memo(move || {
+        reviews()
+            .album_reviews
+            .iter()
+            .find(|r| r.member_id == logged_in_as().id)
+            .and_then(|r| r.text.clone())
+            .unwrap_or_default()
+    });
+
     // Pre-fill existing per-track ratings for this member
     let track_ratings = use_memo(move || {
         let mut map: HashMap<String, u8> = HashMap::new();
@@ -40,7 +58,7 @@ pub fn ReviewLoggedInView(
         for tr in reviews()
             .track_reviews
             .iter()
-            .filter(|r| r.member_name == logged_in_as())
+            .filter(|r| r.member_id == logged_in_as().id)
         {
             map.insert(tr.track_id.clone(), tr.score);
         }
@@ -48,12 +66,33 @@ pub fn ReviewLoggedInView(
         map
     });
 
+    let track_comments = use_memo(move || {
+        let mut map: HashMap<String, String> = HashMap::new();
+
+        for tr in reviews()
+            .track_reviews
+            .iter()
+            .filter(|r| r.member_id == logged_in_as().id)
+        {
+            if let Some(comment) = &tr.comment {
+                map.insert(tr.track_id.clone(), comment.clone());
+            }
+        }
+
+        map
+    });
+
     // Local staged changes (not yet submitted to API)
     // Note: clippy suggests `use_signal(album_rating)` but Memo<T> is not FnOnce() — closure is required.
     #[allow(clippy::redundant_closure)]
     let mut staged_album: Signal<u8> = use_signal(|| album_rating());
     #[allow(clippy::redundant_closure)]
     let mut staged_track_scores: Signal<HashMap<String, u8>> = use_signal(|| track_ratings());
+    #[allow(clippy::redundant_closure)]
+    let mut staged_album_text: Signal<String> = use_signal(|| album_text());
+    #[allow(clippy::redundant_closure)]
+    let mut staged_track_comments: Signal<HashMap<String, String>> =
+        use_signal(|| track_comments());
 
     // Keep staged values in sync when server reviews change (e.g. after submit)
     use_effect(move || {
@@ -61,8 +100,19 @@ pub fn ReviewLoggedInView(
         // the server-side memos change.
         staged_album.set(album_rating());
         staged_track_scores.set(track_ratings());
+        staged_album_text.set(album_text());
+        staged_track_comments.set(track_comments());
     });
 
+    let staged_comment = move |track_id: &str| {
+        staged_track_comments()
+            .get(track_id)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let server_comment =
+        move |track_id: &str| track_comments().get(track_id).cloned().unwrap_or_default();
+
     // Has local changes compared to server
     let has_changes = use_memo(move || {
         if staged_album() != album_rating() {
@@ -73,11 +123,21 @@ pub fn ReviewLoggedInView(
             return true;
         }
 
-        false
+        if staged_album_text().trim() != album_text() {
+            return true;
+        }
+
+        tracks()
+            .iter()
+            .any(|t| staged_comment(&t.track_id).trim() != server_comment(&t.track_id))
     });
 
     let mut is_submitting = use_signal(|| false);
 
+    // Closed windows keep the scores readable but not editable.
+    let window = use_memo(move || reviews().window);
+    let is_open = window().open;
+
     // Clear the submitting flag once the server response has updated `reviews`.
     use_effect(move || {
         let _ = reviews(); // subscribe
@@ -89,16 +149,22 @@ pub fn ReviewLoggedInView(
         reset_errors(());
         is_submitting.set(true);
 
-        // Submit album if changed
-        if staged_album() != album_rating() {
-            review_album(staged_album());
+        // Submit album if changed. The text is only sent when it was edited.
+        let text = staged_album_text();
+        let text_changed = text.trim() != album_text();
+        if staged_album() != album_rating() || text_changed {
+            review_album((staged_album(), text_changed.then_some(text)));
         }
 
         // Submit each changed track
-        for (tid, &score) in staged_track_scores().iter() {
+        for track in tracks().iter() {
+            let tid = &track.track_id;
+            let score = staged_track_scores().get(tid).copied().unwrap_or(0);
             let server_score = track_ratings().get(tid).copied().unwrap_or(0);
-            if score != server_score {
-                review_track((tid.clone(), score));
+            let comment = staged_comment(tid);
+            let comment_changed = comment.trim() != server_comment(tid);
+            if score != server_score || comment_changed {
+                review_track((tid.clone(), score, comment_changed.then_some(comment)));
             }
         }
     });
@@ -108,7 +174,7 @@ pub fn ReviewLoggedInView(
             // ── Logout ────────────────────────────
             div { class: "review-logged-banner",
                 span { "Inloggad som " }
-                strong { "{logged_in_as}" }
+                strong { "{logged_in_as().name}" }
                 button {
                     class: "review-logout-btn",
                     onclick: move |_| {
@@ -119,6 +185,20 @@ pub fn ReviewLoggedInView(
                 }
             }
 
+            if !is_open {
+                p { class: "review-closed-banner",
+                    if window().locked {
+                        "En admin har stängt recensionerna för det här albumet."
+                    } else {
+                        "Recensionerna för det här albumet har stängt."
+                    }
+                }
+            } else if let Some(closes_at) = window().closes_at {
+                p { class: "review-section-hint",
+                    "Du kan recensera till och med {closes_at.get(..16).unwrap_or(&closes_at)}."
+                }
+            }
+
             // ── Album review ────────────────────────────
             div { class: "card review-section",
                 h3 { "Albumbetyg" }
@@ -131,6 +211,7 @@ pub fn ReviewLoggedInView(
                             div { class: "star-wrap {change_class}",
                                 StarRating {
                                     score: staged_album(),
+                                    disabled: !is_open,
                                     on_change: move |s| {
                                         staged_album.set(s);
                                     },
@@ -141,6 +222,23 @@ pub fn ReviewLoggedInView(
                     span { class: "review-score-text", "{staged_album()} / 10" }
                 }
 
+                div { class: "review-field",
+                    label { class: "review-label", r#for: "review-album-text", "Recension (valfri)" }
+                    textarea {
+                        id: "review-album-text",
+                        class: "review-album-text",
+                        disabled: !is_open,
+                        rows: 4,
+                        maxlength: MAX_REVIEW_TEXT_LEN as i64,
+                        placeholder: "Vad tyckte du om albumet?",
+                        value: "{staged_album_text}",
+                        oninput: move |e| {
+                            reset_errors(());
+                            staged_album_text.set(e.value());
+                        },
+                    }
+                }
+
                 if let Some(e) = album_review_error() {
                     p { class: "review-error", "Fel: {e}" }
                 }
@@ -149,30 +247,63 @@ pub fn ReviewLoggedInView(
             // ── Track reviews ────────────────────────────
             div { class: "card review-section",
                 h3 { "Låtbetyg" }
-                p { class: "review-section-hint", "Sätt ett betyg för varje låt." }
+                p { class: "review-section-hint",
+                    "Sätt ett betyg för varje låt. Betygsatta låtar kan också få en kort kommentar."
+                }
 
                 div { class: "review-track-list",
-                    for track in tracks().iter() {
-                        {
-                            let server_score = *track_ratings().get(&track.track_id).unwrap_or(&0);
-                            let staged_score = *staged_track_scores().get(&track.track_id).unwrap_or(&0);
-                            let tc = change_class(staged_score, server_score);
-                            rsx! {
-                                div { class: "track-wrap {tc}",
-                                    TrackRatingRow {
-                                        key: "{track.track_id}",
-                                        track: track.clone(),
-                                        score: staged_score,
-                                        on_change: {
-                                            let tid = track.track_id.clone();
-                                            let mut staged_track_scores = staged_track_scores;
-                                            move |s| {
-                                                reset_errors(());
-                                                let mut new = staged_track_scores().clone();
-                                                new.insert(tid.clone(), s);
-                                                staged_track_scores.set(new);
+                    {
+                        let discs = tracks_by_disc(&tracks());
+                        let multi_disc = discs.len() > 1;
+                        rsx! {
+                            for (disc , disc_tracks) in discs {
+                                if multi_disc {
+                                    h4 { key: "disc-{disc}", class: "review-disc-heading", "Skiva {disc}" }
+                                }
+                                for track in disc_tracks {
+                                    {
+                                        let server_score = *track_ratings().get(&track.track_id).unwrap_or(&0);
+                                        let staged_score = *staged_track_scores().get(&track.track_id).unwrap_or(&0);
+                                        let comment = staged_comment(&track.track_id);
+                                        let comment_changed = comment.trim() != server_comment(&track.track_id);
+                                        let tc = change_class_with_text(staged_score, server_score, comment_changed);
+                                        rsx! {
+                                            div { class: "track-wrap {tc}",
+                                                TrackRatingRow {
+                                                    key: "{track.track_id}",
+                                                    track: track.clone(),
+                                                    score: staged_score,
+                                                    disabled: !is_open,
+                                                    on_change: {
+                                                        let tid = track.track_id.clone();
+                                                        let mut staged_track_scores = staged_track_scores;
+                                                        move |s| {
+                                                            reset_errors(());
+                                                            let mut new = staged_track_scores().clone();
+                                                            new.insert(tid.clone(), s);
+                                                            staged_track_scores.set(new);
+                                                        }
+                                                    },
+                                                }
+                                                if staged_score > 0 || !comment.is_empty() {
+                                                    input {
+                                                        class: "review-track-comment",
+                                                        r#type: "text",
+                                                        disabled: !is_open,
+                                                        maxlength: MAX_TRACK_COMMENT_LEN as i64,
+                                                        placeholder: "Kommentar (valfri)",
+                                                        value: "{comment}",
+                                                        oninput: {
+                                                            let tid = track.track_id.clone();
+                                                            move |e: FormEvent| {
+                                                                reset_errors(());
+                                                                staged_track_comments.write().insert(tid.clone(), e.value());
+                                                            }
+                                                        },
+                                                    }
+                                                }
                                             }
-                                        },
+                                        }
                                     }
                                 }
                             }
@@ -181,7 +312,7 @@ pub fn ReviewLoggedInView(
                 }
 
                 // Submit / reset controls
-                div { class: "review-submit-row",
+                div { class: "review-submit-row", hidden: !is_open,
                     button {
                         class: "review-submit-btn",
                         disabled: (!has_changes() || is_submitting()).then_some("disabled"),
@@ -202,6 +333,8 @@ pub fn ReviewLoggedInView(
                             reset_errors(());
                             staged_album.set(album_rating());
                             staged_track_scores.set(track_ratings());
+                            staged_album_text.set(album_text());
+                            staged_track_comments.set(track_comments());
                         },
                         "Återställ"
                     }
@@ -216,7 +349,12 @@ pub fn ReviewLoggedInView(
 }
 
 #[component]
-fn TrackRatingRow(track: AlbumTrack, score: u8, on_change: EventHandler<u8>) -> Element {
+fn TrackRatingRow(
+    track: AlbumTrack,
+    score: u8,
+    #[props(default = false)] disabled: bool,
+    on_change: EventHandler<u8>,
+) -> Element {
     rsx! {
         div { class: "review-track-row",
             span { class: "review-track-num", "{track.track_number}" }
@@ -233,7 +371,7 @@ fn TrackRatingRow(track: AlbumTrack, score: u8, on_change: EventHandler<u8>) ->
                 }
             }
             div { class: "review-track-rating",
-                StarRating { score, on_change }
+                StarRating { score, disabled, on_change }
                 span { class: "review-track-score-text",
                     if score == 0 {
                         "–"
@@ -247,14 +385,27 @@ fn TrackRatingRow(track: AlbumTrack, score: u8, on_change: EventHandler<u8>) ->
 }
 
 #[component]
-fn StarRating(score: u8, on_change: EventHandler<u8>) -> Element {
+fn StarRating(
+    score: u8,
+    #[props(default = false)] disabled: bool,
+    on_change: EventHandler<u8>,
+) -> Element {
     let mut hover: Signal<Option<u8>> = use_signal(|| None);
 
-    let display = hover().unwrap_or(score);
+    let display = if disabled {
+        score
+    } else {
+        hover().unwrap_or(score)
+    };
+    let class = if disabled {
+        "star-rating star-rating-disabled"
+    } else {
+        "star-rating"
+    };
 
     rsx! {
         div {
-            class: "star-rating",
+            class: "{class}",
             role: "group",
             "aria-label": format!("Betyg {} av 10", score),
             onmouseleave: move |_| hover.set(None),
@@ -288,6 +439,9 @@ fn StarRating(score: u8, on_change: EventHandler<u8>) -> Element {
                                 "aria-label": "{left_score} av 10",
                                 onmouseenter: move |_| hover.set(Some(left_score)),
                                 onclick: move |_| {
+                                    if disabled {
+                                        return;
+                                    }
                                     let new = if score == left_score { 0 } else { left_score };
                                     on_change.call(new);
                                     hover.set(None);
@@ -300,6 +454,9 @@ fn StarRating(score: u8, on_change: EventHandler<u8>) -> Element {
                                 "aria-label": "{right_score} av 10",
                                 onmouseenter: move |_| hover.set(Some(right_score)),
                                 onclick: move |_| {
+                                    if disabled {
+                                        return;
+                                    }
                                     let new = if score == right_score { left_score } else { right_score };
                                     on_change.call(new);
                                     hover.set(None);
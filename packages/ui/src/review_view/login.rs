@@ -1,13 +1,13 @@
-use api::api_models::Name;
+use api::api_models::{Member, MemberId};
 use dioxus::prelude::*;
 
 #[component]
 pub fn ReviewLoginView(
-    member_name: Signal<String>,
+    member_id: Signal<Option<MemberId>>,
     password: Signal<String>,
     login_error: Signal<Option<String>>,
     is_logging_in: ReadSignal<bool>,
-    members: ReadSignal<Vec<Name>>,
+    members: ReadSignal<Vec<Member>>,
     perform_login: Callback<()>,
 ) -> Element {
     rsx! {
@@ -20,22 +20,21 @@ pub fn ReviewLoginView(
                     label { class: "review-label", r#for: "review-member", "Namn" }
                     select {
                         id: "review-member",
-                        value: "{member_name}",
                         onchange: move |e| {
-                            member_name.set(e.value());
+                            member_id.set(e.value().parse().ok());
                             login_error.set(None);
                         },
                         option {
                             value: "",
                             disabled: true,
-                            selected: member_name().is_empty(),
+                            selected: member_id().is_none(),
                             "Välj…"
                         }
                         for m in members.iter() {
                             option {
-                                value: "{m}",
-                                selected: member_name() == m.as_ref(),
-                                "{m}"
+                                value: "{m.id}",
+                                selected: member_id() == Some(m.id),
+                                "{m.name}"
                             }
                         }
                     }
@@ -67,7 +66,7 @@ pub fn ReviewLoginView(
 
             button {
                 class: "review-button",
-                disabled: member_name().is_empty() || password().is_empty() || is_logging_in(),
+                disabled: member_id().is_none() || password().is_empty() || is_logging_in(),
                 onclick: move |_| perform_login(()),
                 if is_logging_in() {
                     span { class: "spinner" }
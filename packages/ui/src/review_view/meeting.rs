@@ -0,0 +1,74 @@
+use super::{ReviewAlbumView, REVIEW_SCSS};
+use crate::errors::error_message;
+use crate::SiteFooter;
+use api::api_models::MeetingDetails;
+use api::{get_meeting, ApiError};
+use dioxus::prelude::*;
+
+/// The review page of any meeting, current or past, reached from the history page.
+#[component]
+pub fn MeetingReview(meeting_id: ReadSignal<String>) -> Element {
+    let meeting = use_resource(move || async move {
+        get_meeting(meeting_id()).await.map_err(|e| match e {
+            ApiError::NotFound(_) => "Mötet finns inte".to_string(),
+            other => error_message(&other),
+        })
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: REVIEW_SCSS }
+
+        div { class: "page-wrapper",
+            header {
+                h1 { "Recensera" }
+            }
+
+            match meeting() {
+                None => rsx! {
+                    div { class: "review-loading", "Laddar…" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "review-empty card",
+                        p { "Kunde inte ladda mötet: {err}" }
+                        p {
+                            a { href: "/history", "Tillbaka till historiken" }
+                        }
+                    }
+                },
+                Some(Ok(details)) => rsx! {
+                    MeetingReviewDetails { key: "{details.id}", details }
+                },
+            }
+
+            SiteFooter {}
+        }
+    }
+}
+
+#[component]
+fn MeetingReviewDetails(details: ReadSignal<MeetingDetails>) -> Element {
+    let meeting_id = use_memo(move || details().id);
+    let album = use_memo(move || details().album);
+    let members = use_memo(move || details().members);
+    let picker = use_memo(move || details().picker);
+
+    rsx! {
+        p { class: "review-meeting-date",
+            a { href: "/history", "← Historik" }
+            span {
+                if details().is_current {
+                    "Nuvarande album, möte {details().meeting.date}"
+                } else {
+                    "Möte {details().meeting.date}"
+                }
+            }
+        }
+
+        ReviewAlbumView {
+            meeting_id,
+            album,
+            members,
+            current_person: picker,
+        }
+    }
+}
@@ -1,22 +1,47 @@
 mod aggregate_scores;
 mod album_overview;
+mod availability;
+mod change_password;
 mod logged_in;
 mod login;
+mod meeting;
 
+use crate::errors::error_message;
 use crate::review_view::aggregate_scores::AggregateScores;
 use crate::review_view::album_overview::CurrentAlbumView;
+use crate::review_view::availability::AvailabilityView;
+use crate::review_view::change_password::ChangePasswordView;
 use crate::review_view::logged_in::ReviewLoggedInView;
 use crate::review_view::login::ReviewLoginView;
+use crate::session_storage::{
+    clear_session_token, store_session_token, stored_session_token, REVIEW_SESSION_KEY,
+};
 use crate::SiteFooter;
-use api::api_models::{Album, AlbumTrack, Data, Name, Reviews};
+use api::api_models::{Album, AlbumTrack, Data, Member, MemberId, MemberSession, Reviews};
 use api::{
-    get_album_tracks, get_current, get_reviews, submit_album_review, submit_track_review,
-    verify_member,
+    get_album_tracks, get_current, get_reviews, member_logout, member_session, submit_album_review,
+    submit_track_review, verify_member, ApiError,
 };
 use dioxus::prelude::*;
+pub use meeting::MeetingReview;
 
 const REVIEW_SCSS: Asset = asset!("/assets/styling/review.scss");
 
+/// Group tracks by disc number, each disc sorted by track number.
+fn tracks_by_disc(tracks: &[AlbumTrack]) -> Vec<(u32, Vec<AlbumTrack>)> {
+    let mut sorted = tracks.to_vec();
+    sorted.sort_by_key(|t| (t.disc_number, t.track_number));
+
+    let mut discs: Vec<(u32, Vec<AlbumTrack>)> = Vec::new();
+    for track in sorted {
+        match discs.last_mut() {
+            Some((disc, disc_tracks)) if *disc == track.disc_number => disc_tracks.push(track),
+            _ => discs.push((track.disc_number, vec![track])),
+        }
+    }
+    discs
+}
+
 #[component]
 pub fn Review() -> Element {
     let mut page_data: Signal<Option<Data>> = use_signal(|| None);
@@ -30,7 +55,7 @@ pub fn Review() -> Element {
 
             match data_response {
                 Ok(data) => page_data.set(Some(data)),
-                Err(err) => initial_load_error.set(Some(err.to_string())),
+                Err(err) => initial_load_error.set(Some(error_message(&err))),
             }
         });
     });
@@ -110,8 +135,8 @@ fn DisplayData(data: ReadSignal<Data>) -> Element {
 fn ReviewAlbumView(
     meeting_id: ReadSignal<String>,
     album: ReadSignal<Album>,
-    members: ReadSignal<Vec<Name>>,
-    current_person: ReadSignal<Name>,
+    members: ReadSignal<Vec<Member>>,
+    current_person: ReadSignal<Member>,
 ) -> Element {
     let mut tracks: Signal<Option<Vec<AlbumTrack>>> = use_signal(|| None);
     let mut reviews: Signal<Option<Reviews>> = use_signal(|| None);
@@ -127,7 +152,7 @@ fn ReviewAlbumView(
                     tracks.set(Some(ts));
                 }
                 Err(err) => {
-                    load_error.set(Some(err.to_string()));
+                    load_error.set(Some(error_message(&err)));
                 }
             }
         });
@@ -138,20 +163,21 @@ fn ReviewAlbumView(
     });
 
     let handle_reviews_response =
-        use_callback(move |reviews_response: Result<Reviews, ServerFnError>| {
+        use_callback(move |reviews_response: Result<Reviews, ApiError>| {
             match reviews_response {
                 Ok(r) => {
                     reviews.set(Some(r));
                 }
                 Err(err) => {
-                    load_error.set(Some(err.to_string()));
+                    load_error.set(Some(error_message(&err)));
                 }
             };
         });
 
     let load_reviews = use_callback(move |()| {
         spawn(async move {
-            let load_reviews_response = get_reviews(meeting_id()).await;
+            let token = stored_session_token(REVIEW_SESSION_KEY).await;
+            let load_reviews_response = get_reviews(meeting_id(), token).await;
             handle_reviews_response(load_reviews_response);
         });
     });
@@ -201,7 +227,7 @@ fn ReviewAlbumView(
 fn DisplayAndPerformReviewView(
     reviews: ReadSignal<Reviews>,
     tracks: ReadSignal<Vec<AlbumTrack>>,
-    members: ReadSignal<Vec<Name>>,
+    members: ReadSignal<Vec<Member>>,
     meeting_id: ReadSignal<String>,
     update_reviews: Callback<Reviews, ()>,
 ) -> Element {
@@ -222,31 +248,72 @@ fn DisplayAndPerformReviewView(
 fn PerformReviewView(
     reviews: ReadSignal<Reviews>,
     tracks: ReadSignal<Vec<AlbumTrack>>,
-    members: ReadSignal<Vec<Name>>,
+    members: ReadSignal<Vec<Member>>,
     meeting_id: ReadSignal<String>,
     update_reviews: Callback<Reviews, ()>,
 ) -> Element {
-    let mut member_name = use_signal(String::new);
+    let mut member_id = use_signal(|| None::<MemberId>);
     let mut password = use_signal(String::new);
 
-    let mut logged_in_as = use_signal(|| None::<String>);
+    let mut session_token = use_signal(|| None::<String>);
+    let mut logged_in_as = use_signal(|| None::<Member>);
     let mut login_error = use_signal(|| None::<String>);
     let mut is_logging_in = use_signal(|| false);
+    let mut is_restoring_session = use_signal(|| true);
 
     let mut album_review_error: Signal<Option<String>> = use_signal(|| None);
     let mut track_review_error: Signal<Option<String>> = use_signal(|| None);
 
+    // Pick up a login from an earlier visit.
+    use_future(move || async move {
+        if let Some(token) = stored_session_token(REVIEW_SESSION_KEY).await {
+            match member_session(token.clone()).await {
+                Ok(member) => {
+                    session_token.set(Some(token));
+                    logged_in_as.set(Some(member));
+                }
+                Err(ApiError::Unauthorized(_)) => clear_session_token(REVIEW_SESSION_KEY),
+                Err(e) => login_error.set(Some(error_message(&e))),
+            }
+        }
+        is_restoring_session.set(false);
+    });
+
+    // Which reviews are visible depends on who is logged in, so refetch on login and logout.
+    use_effect(move || {
+        if is_restoring_session() {
+            return;
+        }
+        let token = session_token();
+        spawn(async move {
+            if let Ok(r) = get_reviews(meeting_id(), token).await {
+                update_reviews(r);
+            }
+        });
+    });
+
     let perform_login = use_callback(move |_: ()| {
+        let Some(id) = member_id() else {
+            return;
+        };
         login_error.set(None);
         is_logging_in.set(true);
         spawn(async move {
-            match verify_member(member_name(), password()).await {
-                Ok(()) => {
+            match verify_member(id, password()).await {
+                Ok(session) => {
                     login_error.set(None);
-                    logged_in_as.set(Some(member_name()));
+                    password.set(String::new());
+                    store_session_token(REVIEW_SESSION_KEY, &session.token);
+                    session_token.set(Some(session.token));
+                    logged_in_as.set(Some(session.member));
+                }
+                Err(ApiError::Unauthorized(_)) => {
+                    login_error.set(Some(
+                        "Fel lösenord, eller inget lösenord satt – be en admin om en inbjudningslänk".to_string(),
+                    ));
                 }
                 Err(e) => {
-                    login_error.set(Some(e.to_string()));
+                    login_error.set(Some(error_message(&e)));
                 }
             }
             is_logging_in.set(false);
@@ -254,35 +321,54 @@ fn PerformReviewView(
     });
 
     let logout = use_callback(move |()| {
+        if let Some(token) = session_token() {
+            spawn(async move {
+                let _ = member_logout(token).await;
+            });
+        }
+        clear_session_token(REVIEW_SESSION_KEY);
+        session_token.set(None);
         logged_in_as.set(None);
-        member_name.set(String::new());
+        member_id.set(None);
         password.set(String::new());
     });
 
-    let review_album = use_callback(move |review| {
+    // The session expired, or the password was changed since logging in.
+    let expire_login = use_callback(move |()| {
+        logout(());
+        login_error.set(Some("Du har loggats ut, logga in igen".to_string()));
+    });
+
+    let review_album = use_callback(move |(review, text)| {
+        let Some(token) = session_token() else {
+            return;
+        };
         spawn(async move {
-            let result = submit_album_review(member_name(), password(), meeting_id(), review).await;
+            let result = submit_album_review(token, meeting_id(), review, text).await;
 
             match result {
                 Ok(r) => {
                     update_reviews(r);
                 }
+                Err(ApiError::Unauthorized(_)) => expire_login(()),
                 Err(err) => {
-                    album_review_error.set(Some(err.to_string()));
+                    album_review_error.set(Some(error_message(&err)));
                 }
             }
         });
     });
 
-    let review_track = use_callback(move |(track_id, review)| {
+    let review_track = use_callback(move |(track_id, review, comment)| {
+        let Some(token) = session_token() else {
+            return;
+        };
         spawn(async move {
-            let result =
-                submit_track_review(member_name(), password(), meeting_id(), track_id, review)
-                    .await;
+            let result = submit_track_review(token, meeting_id(), track_id, review, comment).await;
 
             match result {
                 Ok(r) => update_reviews(r),
-                Err(err) => track_review_error.set(Some(err.to_string())),
+                Err(ApiError::Unauthorized(_)) => expire_login(()),
+                Err(err) => track_review_error.set(Some(error_message(&err))),
             }
         });
     });
@@ -292,10 +378,28 @@ fn PerformReviewView(
         track_review_error.set(None);
     });
 
-    let Some(logged_in_as) = logged_in_as() else {
+    let password_changed = use_callback(move |session: MemberSession| {
+        session_token.set(Some(session.token));
+        logged_in_as.set(Some(session.member));
+    });
+
+    if is_restoring_session() {
+        return rsx! {
+            div { class: "review-loading", "Laddar…" }
+        };
+    }
+
+    let (Some(logged_in_as), Some(session_token)) = (logged_in_as(), session_token()) else {
+        if !reviews().window.open {
+            return rsx! {
+                div { class: "card review-empty",
+                    p { "Recensionerna för det här albumet är stängda." }
+                }
+            };
+        }
         return rsx! {
             ReviewLoginView {
-                member_name,
+                member_id,
                 password,
                 login_error,
                 is_logging_in,
@@ -307,7 +411,7 @@ fn PerformReviewView(
 
     rsx! {
         ReviewLoggedInView {
-            logged_in_as,
+            logged_in_as: logged_in_as.clone(),
             reviews,
             tracks,
             review_album,
@@ -317,5 +421,64 @@ fn PerformReviewView(
             track_review_error,
             reset_errors,
         }
+
+        ChangePasswordView { session_token: session_token.clone(), on_changed: password_changed }
+        AvailabilityView { session_token, member: logged_in_as }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(disc_number: u32, track_number: u32) -> AlbumTrack {
+        AlbumTrack {
+            track_id: format!("{disc_number}-{track_number}"),
+            disc_number,
+            track_number,
+            track_name: format!("Track {disc_number}.{track_number}"),
+            duration_ms: None,
+            spotify_url: None,
+        }
+    }
+
+    fn numbers(discs: &[(u32, Vec<AlbumTrack>)]) -> Vec<(u32, Vec<u32>)> {
+        discs
+            .iter()
+            .map(|(disc, tracks)| (*disc, tracks.iter().map(|t| t.track_number).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn groups_tracks_by_disc_in_order() {
+        let tracks = [
+            track(2, 2),
+            track(1, 2),
+            track(2, 1),
+            track(1, 1),
+            track(1, 3),
+        ];
+        assert_eq!(
+            numbers(&tracks_by_disc(&tracks)),
+            [(1, vec![1, 2, 3]), (2, vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn single_disc_and_empty_listings() {
+        assert_eq!(
+            numbers(&tracks_by_disc(&[track(1, 2), track(1, 1)])),
+            [(1, vec![1, 2])]
+        );
+        assert!(tracks_by_disc(&[]).is_empty());
+    }
+
+    #[test]
+    fn skipped_disc_numbers_are_kept() {
+        let tracks = [track(3, 1), track(1, 1)];
+        assert_eq!(
+            numbers(&tracks_by_disc(&tracks)),
+            [(1, vec![1]), (3, vec![1])]
+        );
     }
 }
@@ -0,0 +1,22 @@
+//! Keeps logins in `localStorage` so they survive page reloads.
+
+use dioxus::document::eval;
+
+pub const REVIEW_SESSION_KEY: &str = "albumklubben.review-session";
+pub const ADMIN_SESSION_KEY: &str = "albumklubben.admin-session";
+
+pub async fn stored_session_token(key: &str) -> Option<String> {
+    eval(&format!("return localStorage.getItem({key:?});"))
+        .join::<Option<String>>()
+        .await
+        .ok()
+        .flatten()
+}
+
+pub fn store_session_token(key: &str, token: &str) {
+    let _ = eval(&format!("localStorage.setItem({key:?}, {token:?});"));
+}
+
+pub fn clear_session_token(key: &str) {
+    let _ = eval(&format!("localStorage.removeItem({key:?});"));
+}
@@ -0,0 +1,395 @@
+use crate::components::stars::AverageStars;
+use crate::errors::error_message;
+use crate::SiteFooter;
+use api::api_models::{
+    ClubStats, Compatibility, CompatibilityBasis, Member, MemberId, MemberPair, TrackStat,
+    MIN_COMPATIBILITY_OVERLAP,
+};
+use api::{get_compatibility, get_stats};
+use dioxus::prelude::*;
+
+const STATS_SCSS: Asset = asset!("/assets/styling/stats.scss");
+
+#[component]
+pub fn Stats() -> Element {
+    let stats =
+        use_resource(move || async move { get_stats().await.map_err(|e| error_message(&e)) });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: STATS_SCSS }
+
+        div { class: "page-wrapper",
+            header {
+                h1 { "Statistik" }
+            }
+
+            match stats() {
+                None => rsx! {
+                    div { class: "stats-loading", "Laddar…" }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "stats-error", "Kunde inte ladda statistik: {e}" }
+                },
+                Some(Ok(stats)) if stats.albums.is_empty() => rsx! {
+                    div { class: "stats-empty",
+                        p { "Inga betyg att räkna på än." }
+                    }
+                },
+                Some(Ok(stats)) => rsx! {
+                    StatsOverview { stats }
+                },
+            }
+
+            SiteFooter {}
+        }
+    }
+}
+
+#[component]
+fn StatsOverview(stats: ReadSignal<ClubStats>) -> Element {
+    rsx! {
+        div { class: "stats-grid",
+            div { class: "card stats-section stats-section-wide",
+                h2 { "Topplista" }
+                table { class: "stats-table",
+                    thead {
+                        tr {
+                            th { "#" }
+                            th { "Album" }
+                            th { "Vald av" }
+                            th { "Betyg" }
+                        }
+                    }
+                    tbody {
+                        for (i , album) in stats().albums.into_iter().enumerate() {
+                            tr { key: "{album.meeting_id}",
+                                td { class: "stats-rank", "{i + 1}" }
+                                td {
+                                    a {
+                                        href: "/history/{album.meeting_id}",
+                                        class: "stats-album-name",
+                                        "{album.album_name}"
+                                    }
+                                    span { class: "stats-muted", "{album.album_artist}" }
+                                }
+                                td { "{album.picker}" }
+                                td {
+                                    div { class: "stats-score",
+                                        AverageStars { avg: album.average }
+                                        span { {format!("{:.1}", album.average)} }
+                                        span { class: "stats-muted", "({album.review_count})" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "card stats-section",
+                h2 { "Väljare" }
+                p { class: "stats-hint", "Snittbetyg på albumen de valt." }
+                table { class: "stats-table",
+                    thead {
+                        tr {
+                            th { "Namn" }
+                            th { "Album" }
+                            th { "Snitt" }
+                        }
+                    }
+                    tbody {
+                        for picker in stats().pickers {
+                            tr { key: "{picker.member.id}",
+                                td { "{picker.member.name}" }
+                                td { "{picker.albums_picked}" }
+                                td {
+                                    if let Some(avg) = picker.average_received {
+                                        {format!("{:.1}", avg)}
+                                    } else {
+                                        "–"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "card stats-section",
+                h2 { "Recensenter" }
+                p { class: "stats-hint", "Flest albumbetyg först, och snittet de gett." }
+                table { class: "stats-table",
+                    thead {
+                        tr {
+                            th { "Namn" }
+                            th { "Album" }
+                            th { "Låtar" }
+                            th { "Snitt" }
+                        }
+                    }
+                    tbody {
+                        for reviewer in stats().reviewers {
+                            tr { key: "{reviewer.member.id}",
+                                td { "{reviewer.member.name}" }
+                                td { "{reviewer.album_reviews}" }
+                                td { "{reviewer.track_reviews}" }
+                                td { {format!("{:.1}", reviewer.average_given)} }
+                            }
+                        }
+                    }
+                }
+            }
+
+            TrackList { title: "Bästa låtarna", tracks: stats().best_tracks }
+            TrackList { title: "Sämsta låtarna", tracks: stats().worst_tracks }
+
+            ScoreDistribution { title: "Albumbetyg", counts: stats().album_score_distribution }
+            ScoreDistribution { title: "Låtbetyg", counts: stats().track_score_distribution }
+
+            CompatibilityHeatmap {}
+        }
+    }
+}
+
+#[component]
+fn TrackList(title: &'static str, tracks: Vec<TrackStat>) -> Element {
+    rsx! {
+        div { class: "card stats-section",
+            h2 { "{title}" }
+            if tracks.is_empty() {
+                p { class: "stats-hint", "Inga låtar med tillräckligt många betyg än." }
+            } else {
+                ol { class: "stats-track-list",
+                    for track in tracks {
+                        li { key: "{track.meeting_id}-{track.track_name}",
+                            div { class: "stats-track-row",
+                                div {
+                                    span { class: "stats-track-name", "{track.track_name}" }
+                                    a {
+                                        href: "/history/{track.meeting_id}",
+                                        class: "stats-muted",
+                                        "{track.album_name} – {track.album_artist}"
+                                    }
+                                }
+                                span { class: "stats-score",
+                                    {format!("{:.1}", track.average)}
+                                    span { class: "stats-muted", "({track.review_count})" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A bar per score from 1 to 10, scaled to the most common score.
+#[component]
+fn ScoreDistribution(title: &'static str, counts: Vec<u32>) -> Element {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    rsx! {
+        div { class: "card stats-section",
+            h2 { "Fördelning: {title}" }
+            div { class: "stats-distribution",
+                for (i , count) in counts.iter().copied().enumerate() {
+                    div { key: "{i}", class: "stats-distribution-row",
+                        span { class: "stats-distribution-score", "{i + 1}" }
+                        div { class: "stats-distribution-track",
+                            div {
+                                class: "stats-distribution-bar",
+                                style: format!("width: {}%", count * 100 / max),
+                            }
+                        }
+                        span { class: "stats-muted", "{count}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Which number of a [`MemberPair`] the heatmap shows.
+#[derive(Clone, Copy, PartialEq)]
+enum Metric {
+    Pearson,
+    Spearman,
+    MeanAbsDiff,
+}
+
+impl Metric {
+    const ALL: [Metric; 3] = [Metric::Pearson, Metric::Spearman, Metric::MeanAbsDiff];
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Pearson => "Korrelation (Pearson)",
+            Metric::Spearman => "Rangkorrelation (Spearman)",
+            Metric::MeanAbsDiff => "Snittskillnad i betyg",
+        }
+    }
+
+    fn value(self, pair: &MemberPair) -> Option<f32> {
+        match self {
+            Metric::Pearson => pair.pearson,
+            Metric::Spearman => pair.spearman,
+            Metric::MeanAbsDiff => Some(pair.mean_abs_diff),
+        }
+    }
+
+    /// How much the pair agrees on a scale from 0 (not at all) to 1 (fully), for the colour.
+    fn agreement(self, value: f32) -> f32 {
+        match self {
+            Metric::Pearson | Metric::Spearman => (value + 1.0) / 2.0,
+            // Five or more points apart on average counts as no agreement at all.
+            Metric::MeanAbsDiff => 1.0 - (value / 5.0).min(1.0),
+        }
+    }
+}
+
+fn basis_label(basis: CompatibilityBasis) -> &'static str {
+    match basis {
+        CompatibilityBasis::Albums => "Album",
+        CompatibilityBasis::Tracks => "Låtar",
+    }
+}
+
+/// How alike members' taste is, as a member-by-member grid coloured from red to green.
+#[component]
+fn CompatibilityHeatmap() -> Element {
+    let mut basis = use_signal(CompatibilityBasis::default);
+    let mut metric = use_signal(|| Metric::Pearson);
+    let mut min_overlap = use_signal(|| 3_u32);
+
+    let compatibility = use_resource(move || async move {
+        get_compatibility(basis(), min_overlap())
+            .await
+            .map_err(|e| error_message(&e))
+    });
+
+    rsx! {
+        div { class: "card stats-section stats-section-wide",
+            h2 { "Smakkompatibilitet" }
+            p { class: "stats-hint",
+                "Hur lika medlemmarna betygsätter, över det båda har betygsatt."
+            }
+
+            div { class: "stats-controls",
+                select {
+                    onchange: move |e| {
+                        basis.set(match e.value().as_str() {
+                            "tracks" => CompatibilityBasis::Tracks,
+                            _ => CompatibilityBasis::Albums,
+                        });
+                    },
+                    for (value , b) in [
+                        ("albums", CompatibilityBasis::Albums),
+                        ("tracks", CompatibilityBasis::Tracks),
+                    ] {
+                        option { value, selected: basis() == b, "{basis_label(b)}" }
+                    }
+                }
+                select {
+                    onchange: move |e| {
+                        if let Some(m) = Metric::ALL.get(e.value().parse::<usize>().unwrap_or(0)) {
+                            metric.set(*m);
+                        }
+                    },
+                    for (i , m) in Metric::ALL.into_iter().enumerate() {
+                        option { value: "{i}", selected: metric() == m, "{m.label()}" }
+                    }
+                }
+                label { class: "stats-hint",
+                    "Minst "
+                    input {
+                        r#type: "number",
+                        class: "stats-overlap-input",
+                        min: MIN_COMPATIBILITY_OVERLAP as i64,
+                        value: "{min_overlap}",
+                        onchange: move |e| {
+                            if let Ok(n) = e.value().parse::<u32>() {
+                                min_overlap.set(n.max(MIN_COMPATIBILITY_OVERLAP));
+                            }
+                        },
+                    }
+                    " gemensamma"
+                }
+            }
+
+            match compatibility() {
+                None => rsx! {
+                    p { class: "stats-hint", "Laddar…" }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "stats-error", "Kunde inte ladda: {e}" }
+                },
+                Some(Ok(Compatibility { members, pairs, .. })) => rsx! {
+                    div { class: "stats-heatmap-wrap",
+                        table { class: "stats-heatmap",
+                            thead {
+                                tr {
+                                    th {}
+                                    for member in members.iter() {
+                                        th { key: "{member.id}", "{member.name}" }
+                                    }
+                                }
+                            }
+                            tbody {
+                                for row in members.iter() {
+                                    tr { key: "{row.id}",
+                                        th { class: "stats-heatmap-row", "{row.name}" }
+                                        for col in members.iter() {
+                                            HeatmapCell {
+                                                key: "{col.id}",
+                                                row: row.clone(),
+                                                col: col.clone(),
+                                                pair: pair_for(&pairs, row.id, col.id),
+                                                metric: metric(),
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// The pair for two members, or `None` on the diagonal and for pairs under the threshold.
+fn pair_for(pairs: &[MemberPair], x: MemberId, y: MemberId) -> Option<MemberPair> {
+    pairs
+        .iter()
+        .find(|p| (p.a, p.b) == (x.min(y), x.max(y)))
+        .cloned()
+}
+
+#[component]
+fn HeatmapCell(row: Member, col: Member, pair: Option<MemberPair>, metric: Metric) -> Element {
+    let value = pair.as_ref().and_then(|p| metric.value(p));
+    let style = value
+        .map(|v| {
+            let hue = metric.agreement(v).clamp(0.0, 1.0) * 140.0;
+            format!("background-color: hsl({hue:.0} 55% 32%)")
+        })
+        .unwrap_or_default();
+    let title = pair
+        .map(|p| format!("{} & {}: {} gemensamma", row.name, col.name, p.overlap))
+        .unwrap_or_default();
+
+    rsx! {
+        td {
+            class: "stats-heatmap-cell",
+            class: if row.id == col.id { "stats-heatmap-self" },
+            style,
+            title,
+            if let Some(v) = value {
+                "{v:.2}"
+            } else if row.id != col.id {
+                "–"
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use dioxus::prelude::*;
 
 use ui::{
-    AdminAlbum, AdminHistory, AdminPasswords, AdminRotation, AdminShell,
-    History as HistoryView, Main, Review as ReviewView, Setup,
+    AdminAlbum, AdminAuditLog, AdminCredentials, AdminHistory, AdminPasswords, AdminRotation,
+    AdminShell, AdminSpotify, AdminSpotifyCallback, History as HistoryView, Invite, Main,
+    MeetingReview, Review as ReviewView, Setup, Stats as StatsView,
 };
 
 fn main() {
@@ -22,7 +23,9 @@ fn main() {
                 std::process::exit(1);
             }
 
-            Ok(axum::Router::new().serve_dioxus_application(ServeConfig::new(), App))
+            Ok(axum::Router::new()
+                .serve_dioxus_application(ServeConfig::new(), App)
+                .merge(api::album_art_router()))
         });
     }
 
@@ -39,8 +42,16 @@ enum Route {
     Home {},
     #[route("/history")]
     HistoryPage {},
+    #[route("/history/:id")]
+    HistoryMeetingPage { id: String },
     #[route("/review")]
     ReviewPage {},
+    #[route("/stats")]
+    StatsPage {},
+    #[route("/invite/:token")]
+    InvitePage { token: String },
+    #[route("/admin/spotify/callback?:code&:state&:error")]
+    AdminSpotifyCallbackPage { code: String, state: String, error: String },
     #[layout(AdminLayout)]
         #[route("/admin")]
         AdminAlbumPage {},
@@ -50,6 +61,12 @@ enum Route {
         AdminHistoryPage {},
         #[route("/admin/lösenord")]
         AdminPasswordsPage {},
+        #[route("/admin/åtkomst")]
+        AdminCredentialsPage {},
+        #[route("/admin/logg")]
+        AdminAuditLogPage {},
+        #[route("/admin/spotify")]
+        AdminSpotifyPage {},
 }
 
 #[component]
@@ -72,13 +89,28 @@ fn HistoryPage() -> Element {
     rsx! { HistoryView {} }
 }
 
+#[component]
+fn HistoryMeetingPage(id: String) -> Element {
+    rsx! { MeetingReview { meeting_id: id } }
+}
+
 #[component]
 fn ReviewPage() -> Element {
     rsx! { ReviewView {} }
 }
 
+#[component]
+fn StatsPage() -> Element {
+    rsx! { StatsView {} }
+}
+
+#[component]
+fn InvitePage(token: String) -> Element {
+    rsx! { Invite { token } }
+}
+
 /// Shared admin layout: wraps all admin routes in `AdminShell` which loads
-/// data, provides `AdminCtx`, and renders the header, token input, and tab bar.
+/// data, provides `AdminCtx`, and renders the header, login, and tab bar.
 #[component]
 fn AdminLayout() -> Element {
     let route: Route = use_route();
@@ -88,6 +120,12 @@ fn AdminLayout() -> Element {
         "historik"
     } else if matches!(route, Route::AdminPasswordsPage {}) {
         "lossenord"
+    } else if matches!(route, Route::AdminCredentialsPage {}) {
+        "atkomst"
+    } else if matches!(route, Route::AdminAuditLogPage {}) {
+        "logg"
+    } else if matches!(route, Route::AdminSpotifyPage {}) {
+        "spotify"
     } else {
         "album"
     };
@@ -118,3 +156,23 @@ fn AdminHistoryPage() -> Element {
 fn AdminPasswordsPage() -> Element {
     rsx! { AdminPasswords {} }
 }
+
+#[component]
+fn AdminCredentialsPage() -> Element {
+    rsx! { AdminCredentials {} }
+}
+
+#[component]
+fn AdminAuditLogPage() -> Element {
+    rsx! { AdminAuditLog {} }
+}
+
+#[component]
+fn AdminSpotifyPage() -> Element {
+    rsx! { AdminSpotify {} }
+}
+
+#[component]
+fn AdminSpotifyCallbackPage(code: String, state: String, error: String) -> Element {
+    rsx! { AdminSpotifyCallback { code, state, error } }
+}